@@ -84,10 +84,12 @@ impl<'a, DB: Database> Acquire<'a> for &'_ Pool<DB> {
 
     type Connection = PoolConnection<DB>;
 
+    #[track_caller]
     fn acquire(self) -> BoxFuture<'static, Result<Self::Connection, Error>> {
         Box::pin(self.acquire())
     }
 
+    #[track_caller]
     fn begin(self) -> BoxFuture<'static, Result<Transaction<'a, DB>, Error>> {
         let conn = self.acquire();
 
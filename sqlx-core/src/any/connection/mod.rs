@@ -18,7 +18,7 @@ use crate::mysql;
 
 #[cfg(feature = "odbc")]
 use crate::odbc;
-use crate::transaction::Transaction;
+use crate::transaction::{Transaction, TransactionOptions};
 
 mod establish;
 mod executor;
@@ -178,6 +178,10 @@ impl Connection for AnyConnection {
         delegate_to_mut!(self.ping())
     }
 
+    fn reset_session(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+        delegate_to_mut!(self.reset_session())
+    }
+
     fn begin(&mut self) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
     where
         Self: Sized,
@@ -185,6 +189,16 @@ impl Connection for AnyConnection {
         Transaction::begin(self)
     }
 
+    fn begin_with(
+        &mut self,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
+    where
+        Self: Sized,
+    {
+        Transaction::begin_with(self, options)
+    }
+
     fn cached_statements_size(&self) -> usize {
         match &self.0 {
             #[cfg(feature = "postgres")]
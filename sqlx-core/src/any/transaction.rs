@@ -4,7 +4,7 @@ use crate::any::connection::AnyConnectionKind;
 use crate::any::{Any, AnyConnection};
 use crate::database::Database;
 use crate::error::Error;
-use crate::transaction::TransactionManager;
+use crate::transaction::{SavepointAction, TransactionManager, TransactionOptions};
 
 pub struct AnyTransactionManager;
 
@@ -12,30 +12,39 @@ impl TransactionManager for AnyTransactionManager {
     type Database = Any;
 
     fn begin(conn: &mut AnyConnection) -> BoxFuture<'_, Result<(), Error>> {
+        Self::begin_with(conn, TransactionOptions::default())
+    }
+
+    fn begin_with(
+        conn: &mut AnyConnection,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, Result<(), Error>> {
         match &mut conn.0 {
             #[cfg(feature = "postgres")]
             AnyConnectionKind::Postgres(conn) => {
-                <crate::postgres::Postgres as Database>::TransactionManager::begin(conn)
+                <crate::postgres::Postgres as Database>::TransactionManager::begin_with(
+                    conn, options,
+                )
             }
 
             #[cfg(feature = "mysql")]
             AnyConnectionKind::MySql(conn) => {
-                <crate::mysql::MySql as Database>::TransactionManager::begin(conn)
+                <crate::mysql::MySql as Database>::TransactionManager::begin_with(conn, options)
             }
 
             #[cfg(feature = "sqlite")]
             AnyConnectionKind::Sqlite(conn) => {
-                <crate::sqlite::Sqlite as Database>::TransactionManager::begin(conn)
+                <crate::sqlite::Sqlite as Database>::TransactionManager::begin_with(conn, options)
             }
 
             #[cfg(feature = "mssql")]
             AnyConnectionKind::Mssql(conn) => {
-                <crate::mssql::Mssql as Database>::TransactionManager::begin(conn)
+                <crate::mssql::Mssql as Database>::TransactionManager::begin_with(conn, options)
             }
 
             #[cfg(feature = "odbc")]
             AnyConnectionKind::Odbc(conn) => {
-                <crate::odbc::Odbc as Database>::TransactionManager::begin(conn)
+                <crate::odbc::Odbc as Database>::TransactionManager::begin_with(conn, options)
             }
         }
     }
@@ -126,4 +135,41 @@ impl TransactionManager for AnyTransactionManager {
             }
         }
     }
+
+    fn savepoint<'c>(
+        conn: &'c mut AnyConnection,
+        name: &'c str,
+        action: SavepointAction,
+    ) -> BoxFuture<'c, Result<(), Error>> {
+        match &mut conn.0 {
+            #[cfg(feature = "postgres")]
+            AnyConnectionKind::Postgres(conn) => {
+                <crate::postgres::Postgres as Database>::TransactionManager::savepoint(
+                    conn, name, action,
+                )
+            }
+
+            #[cfg(feature = "mysql")]
+            AnyConnectionKind::MySql(conn) => {
+                <crate::mysql::MySql as Database>::TransactionManager::savepoint(conn, name, action)
+            }
+
+            #[cfg(feature = "sqlite")]
+            AnyConnectionKind::Sqlite(conn) => {
+                <crate::sqlite::Sqlite as Database>::TransactionManager::savepoint(
+                    conn, name, action,
+                )
+            }
+
+            #[cfg(feature = "mssql")]
+            AnyConnectionKind::Mssql(conn) => {
+                <crate::mssql::Mssql as Database>::TransactionManager::savepoint(conn, name, action)
+            }
+
+            #[cfg(feature = "odbc")]
+            AnyConnectionKind::Odbc(conn) => {
+                <crate::odbc::Odbc as Database>::TransactionManager::savepoint(conn, name, action)
+            }
+        }
+    }
 }
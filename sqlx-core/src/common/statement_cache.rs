@@ -49,7 +49,12 @@ impl<T> StatementCache<T> {
     }
 
     /// Clear all cached statements from the cache.
-    #[cfg(feature = "sqlite")]
+    #[cfg(any(
+        feature = "postgres",
+        feature = "mysql",
+        feature = "mssql",
+        feature = "sqlite"
+    ))]
     pub fn clear(&mut self) {
         self.inner.clear();
     }
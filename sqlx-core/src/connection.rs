@@ -1,6 +1,6 @@
 use crate::database::{Database, HasStatementCache};
-use crate::error::Error;
-use crate::transaction::Transaction;
+use crate::error::{Error, RetryableError};
+use crate::transaction::{RetryPolicy, Transaction, TransactionOptions};
 use futures_core::future::BoxFuture;
 use log::LevelFilter;
 use std::fmt::Debug;
@@ -29,6 +29,23 @@ pub trait Connection: Send {
     /// Checks if a connection to the database is still valid.
     fn ping(&mut self) -> BoxFuture<'_, Result<(), Error>>;
 
+    /// Reset the session state of this connection to what it was right after connecting.
+    ///
+    /// This discards session variables, temporary tables, open cursors and prepared statements
+    /// left behind by previous users of the connection, and clears the statement cache to match.
+    ///
+    /// * Postgres: executes `DISCARD ALL`.
+    /// * MySQL: sends `COM_RESET_CONNECTION`, then re-applies the session settings made on connect.
+    /// * MSSQL: sends an empty batch with the `RESETCONNECTION` flag set.
+    /// * SQLite and ODBC: only clears the statement cache, as there is no portable way to reset
+    ///   the session.
+    ///
+    /// Used by the pool when [`PoolOptions::reset_on_release`][crate::pool::PoolOptions::reset_on_release]
+    /// is enabled. The default implementation does nothing.
+    fn reset_session(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move { Ok(()) })
+    }
+
     /// Begin a new transaction or establish a savepoint within the active transaction.
     ///
     /// Returns a [`Transaction`] for controlling and tracking the new transaction.
@@ -36,6 +53,49 @@ pub trait Connection: Send {
     where
         Self: Sized;
 
+    /// Begin a new transaction with the given isolation level and access mode.
+    ///
+    /// Unlike [`begin`][Self::begin], this cannot be used to establish a savepoint: if a
+    /// transaction is already active, an error is returned unless `options` is the default.
+    ///
+    /// The default implementation calls [`begin`][Self::begin] if `options` is the default,
+    /// and returns an error otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use sqlx_core_oldapi::connection::Connection;
+    /// use sqlx_core_oldapi::error::Error;
+    /// use sqlx_core_oldapi::postgres::PgConnection;
+    /// use sqlx_core_oldapi::transaction::{IsolationLevel, TransactionOptions};
+    ///
+    /// # pub async fn _f(conn: &mut PgConnection) -> Result<(), Error> {
+    /// let options = TransactionOptions::new()
+    ///     .isolation(IsolationLevel::Serializable)
+    ///     .read_only(true);
+    ///
+    /// let tx = conn.begin_with(options).await?;
+    /// # tx.commit().await
+    /// # }
+    /// ```
+    fn begin_with(
+        &mut self,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
+    where
+        Self: Sized,
+    {
+        if options.is_default() {
+            return self.begin();
+        }
+
+        Box::pin(async move {
+            Err(Error::Configuration(
+                "transaction options are not supported by this connection".into(),
+            ))
+        })
+    }
+
     /// Execute the function inside a transaction.
     ///
     /// If the function returns an error, the transaction will be rolled back. If it does not
@@ -85,6 +145,85 @@ pub trait Connection: Send {
         })
     }
 
+    /// Execute the function inside a transaction, retrying the whole transaction when it fails
+    /// because of a conflict with a concurrent transaction.
+    ///
+    /// The transaction is started with [`begin_with`][Self::begin_with]. If beginning it, the
+    /// function or the commit fails with an error for which
+    /// [`is_retryable_conflict`][RetryableError::is_retryable_conflict] returns `true`, the
+    /// transaction is rolled back and the function is called again in a new transaction, after
+    /// a delay given by `policy`. Other errors roll back the transaction and are returned
+    /// immediately, as are conflicts once `policy` allows no more retries.
+    ///
+    /// Lock timeouts are not retried: on SQLite, a transaction that failed with `SQLITE_BUSY`
+    /// because the database stayed locked is returned as an error, like a Postgres
+    /// `lock_not_available` error. Begin SQLite transactions that write with
+    /// `SqliteTransactionBehavior::Immediate` to wait for the lock when they begin instead.
+    ///
+    /// This is meant to be called outside of a transaction: the conflicts it retries abort the
+    /// whole transaction, which a savepoint cannot recover from.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use sqlx_core_oldapi::connection::Connection;
+    /// use sqlx_core_oldapi::error::Error;
+    /// use sqlx_core_oldapi::executor::Executor;
+    /// use sqlx_core_oldapi::postgres::PgConnection;
+    /// use sqlx_core_oldapi::transaction::{IsolationLevel, RetryPolicy, TransactionOptions};
+    ///
+    /// # pub async fn _f(conn: &mut PgConnection) -> Result<(), Error> {
+    /// let options = TransactionOptions::new().isolation(IsolationLevel::Serializable);
+    ///
+    /// conn.transaction_with_retry(options, RetryPolicy::new(), |conn| Box::pin(async move {
+    ///     conn.execute("UPDATE accounts SET balance = balance - 10 WHERE id = 1").await?;
+    ///     conn.execute("UPDATE accounts SET balance = balance + 10 WHERE id = 2").await?;
+    ///     Ok(())
+    /// })).await
+    /// # }
+    /// ```
+    fn transaction_with_retry<'a, F, R, E>(
+        &'a mut self,
+        options: TransactionOptions,
+        policy: RetryPolicy,
+        mut callback: F,
+    ) -> BoxFuture<'a, Result<R, E>>
+    where
+        for<'c> F: FnMut(&'c mut Transaction<'_, Self::Database>) -> BoxFuture<'c, Result<R, E>>
+            + 'a
+            + Send
+            + Sync,
+        Self: Sized,
+        R: Send,
+        E: From<Error> + RetryableError + Send,
+    {
+        Box::pin(async move {
+            let mut retries = 0;
+
+            loop {
+                let ret = match self.begin_with(options).await {
+                    Ok(mut transaction) => match callback(&mut transaction).await {
+                        Ok(ret) => transaction.commit().await.map(|_| ret).map_err(E::from),
+                        Err(err) => {
+                            transaction.rollback().await?;
+
+                            Err(err)
+                        }
+                    },
+                    Err(err) => Err(E::from(err)),
+                };
+
+                match ret {
+                    Err(err) if err.is_retryable_conflict() && policy.should_retry(retries) => {
+                        sqlx_rt::sleep(policy.backoff(retries)).await;
+                        retries += 1;
+                    }
+                    ret => return ret,
+                }
+            }
+        })
+    }
+
     /// The number of statements currently cached in the connection.
     fn cached_statements_size(&self) -> usize
     where
@@ -218,6 +218,18 @@ impl Error {
     }
 }
 
+/// Returns the text in `message` between `prefix` and the following `end`.
+///
+/// Used to extract object names from error messages for databases that do not report them
+/// separately.
+#[allow(dead_code)]
+pub(crate) fn text_between<'a>(message: &'a str, prefix: &str, end: char) -> Option<&'a str> {
+    let start = message.find(prefix)? + prefix.len();
+    let len = message[start..].find(end)?;
+
+    Some(&message[start..start + len])
+}
+
 pub(crate) fn mismatched_types<DB: Database, T: Type<DB>>(ty: &DB::TypeInfo) -> BoxDynError {
     let rust_sql_type = rust_sql_type::<DB, T>();
     Box::new(MismatchedTypeError {
@@ -275,14 +287,110 @@ pub trait DatabaseError: 'static + Send + Sync + StdError {
         false
     }
 
+    /// Returns `true` if the error is a serialization failure or a deadlock, meaning the
+    /// transaction was aborted because of a conflict with a concurrent transaction and may
+    /// succeed if retried from the beginning.
+    ///
+    /// * Postgres: SQLSTATE `40001` (`serialization_failure`) or `40P01` (`deadlock_detected`).
+    /// * MySQL: error 1213 (`ER_LOCK_DEADLOCK`) or SQLSTATE `40001`.
+    /// * MSSQL: error 1205 (deadlock victim) or 3960 (snapshot isolation update conflict).
+    /// * SQLite: `SQLITE_BUSY_SNAPSHOT`.
+    ///
+    /// Lock timeouts ([`ErrorKind::LockTimeout`]) are not retryable conflicts on any database,
+    /// including SQLite's other `SQLITE_BUSY` errors.
+    /// * ODBC: SQLSTATE `40001` or `40P01`.
+    ///
+    /// Used by [`Connection::transaction_with_retry`][crate::connection::Connection::transaction_with_retry].
+    fn is_retryable_conflict(&self) -> bool {
+        false
+    }
+
+    /// The category of the error, independent of the database.
+    ///
+    /// Returns [`ErrorKind::Other`] for errors that do not fit any of the categories.
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::Other
+    }
+
     /// Returns the name of the constraint that triggered the error, if applicable.
     /// If the error was caused by a conflict of a unique index, this will be the index name.
     ///
     /// ### Note
-    /// Currently only populated by the Postgres driver.
+    /// Postgres reports it with the error. MySQL, MSSQL and SQLite only include it in the
+    /// message, from which it is extracted for constraint violations.
     fn constraint(&self) -> Option<&str> {
         None
     }
+
+    /// Returns the name of the table the error is about, if applicable.
+    ///
+    /// See the note on [`constraint`][Self::constraint] for where this is available.
+    fn table(&self) -> Option<&str> {
+        None
+    }
+
+    /// Returns the name of the column the error is about, if applicable.
+    ///
+    /// See the note on [`constraint`][Self::constraint] for where this is available.
+    fn column(&self) -> Option<&str> {
+        None
+    }
+}
+
+/// The category of a [`DatabaseError`], as returned by [`DatabaseError::kind`].
+///
+/// This allows handling common errors without matching on the codes of each database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// A unique constraint or unique index was violated.
+    UniqueViolation,
+
+    /// A foreign key constraint was violated.
+    ForeignKeyViolation,
+
+    /// A `NULL` value was written to a column that does not allow it.
+    NotNullViolation,
+
+    /// A check constraint was violated.
+    CheckViolation,
+
+    /// The transaction could not be serialized because of concurrent transactions.
+    SerializationFailure,
+
+    /// The transaction was aborted to resolve a deadlock.
+    Deadlock,
+
+    /// A lock could not be acquired in time.
+    LockTimeout,
+
+    /// The statement was canceled, by request or because of a timeout.
+    QueryCanceled,
+
+    /// The server terminated the connection.
+    ConnectionLost,
+
+    /// Any other error.
+    Other,
+}
+
+/// An error that may have been caused by a conflict between concurrent transactions.
+///
+/// Implement this for the error type returned from the callback passed to
+/// [`Connection::transaction_with_retry`][crate::connection::Connection::transaction_with_retry],
+/// usually by delegating to the wrapped [`Error`].
+pub trait RetryableError {
+    /// Returns `true` if the transaction should be retried from the beginning.
+    fn is_retryable_conflict(&self) -> bool;
+}
+
+impl RetryableError for Error {
+    fn is_retryable_conflict(&self) -> bool {
+        match self {
+            Error::Database(error) => error.is_retryable_conflict(),
+            _ => false,
+        }
+    }
 }
 
 impl dyn DatabaseError {
@@ -3,9 +3,11 @@ use crate::connection::{Connection, LogSettings};
 use crate::error::Error;
 use crate::executor::Executor;
 use crate::mssql::connection::stream::MssqlStream;
+use crate::mssql::protocol::packet::{PacketType, Status};
+use crate::mssql::protocol::sql_batch::SqlBatch;
 use crate::mssql::statement::MssqlStatementMetadata;
 use crate::mssql::{Mssql, MssqlConnectOptions};
-use crate::transaction::Transaction;
+use crate::transaction::{Transaction, TransactionOptions};
 use futures_core::future::BoxFuture;
 use futures_util::{FutureExt, TryFutureExt};
 use std::fmt::{self, Debug, Formatter};
@@ -54,6 +56,30 @@ impl Connection for MssqlConnection {
         self.execute("/* SQLx ping */").map_ok(|_| ()).boxed()
     }
 
+    fn reset_session(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move {
+            self.stream.wait_until_ready().await?;
+            self.stream.pending_done_count += 1;
+
+            // The server resets the session before running the batch, like `sp_reset_connection`.
+            self.stream.write_packet_with_status(
+                PacketType::SqlBatch,
+                Status::RESET_CONN,
+                SqlBatch {
+                    transaction_descriptor: self.stream.transaction_descriptor,
+                    sql: "/* SQLx reset */",
+                },
+            );
+            self.stream.flush().await?;
+            self.stream.wait_until_ready().await?;
+
+            // Cached metadata may refer to temporary objects that were just dropped.
+            self.cache_statement.clear();
+
+            Ok(())
+        })
+    }
+
     fn begin(&mut self) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
     where
         Self: Sized,
@@ -61,6 +87,16 @@ impl Connection for MssqlConnection {
         Transaction::begin(self)
     }
 
+    fn begin_with(
+        &mut self,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
+    where
+        Self: Sized,
+    {
+        Transaction::begin_with(self, options)
+    }
+
     #[doc(hidden)]
     fn flush(&mut self) -> BoxFuture<'_, Result<(), Error>> {
         self.stream.wait_until_ready().boxed()
@@ -35,6 +35,10 @@ pub(crate) struct MssqlStream {
     pub(crate) transaction_descriptor: u64,
     pub(crate) transaction_depth: usize,
 
+    // isolation level of the session before the current transaction changed it,
+    // restored when the transaction ends
+    pub(crate) isolation_to_restore: Option<&'static str>,
+
     // current TabularResult from the server that we are iterating over
     response: Option<(PacketHeader, Bytes)>,
 
@@ -89,6 +93,7 @@ impl MssqlStream {
             pending_done_count: 0,
             transaction_descriptor: 0,
             transaction_depth: 0,
+            isolation_to_restore: None,
             max_packet_size: options
                 .requested_packet_size
                 .try_into()
@@ -104,6 +109,23 @@ impl MssqlStream {
         write_packets(&mut self.inner.wbuf, self.max_packet_size, ty, payload)
     }
 
+    // writes the packet out to the write buffer with the given status bits set on its first
+    // TDS packet, e.g. to ask the server to reset the connection before processing the message
+    pub(crate) fn write_packet_with_status<'en, T: Encode<'en>>(
+        &mut self,
+        ty: PacketType,
+        status: Status,
+        payload: T,
+    ) {
+        write_packets_with_status(
+            &mut self.inner.wbuf,
+            self.max_packet_size,
+            ty,
+            status,
+            payload,
+        )
+    }
+
     // writes the packet out to the write buffer, splitting it if neccessary, and flushing TDS packets one at a time
     pub(crate) async fn write_packet_and_flush<'en, T: Encode<'en>>(
         &mut self,
@@ -186,7 +208,9 @@ impl MssqlStream {
                                 self.transaction_descriptor = desc;
                             }
 
-                            EnvChange::CommitTransaction(_) | EnvChange::RollbackTransaction(_) => {
+                            EnvChange::CommitTransaction(_)
+                            | EnvChange::RollbackTransaction(_)
+                            | EnvChange::ResetConnectionCompletionAck => {
                                 self.transaction_descriptor = 0;
                             }
 
@@ -299,6 +323,17 @@ pub(crate) fn write_packets<'en, T: Encode<'en>>(
     max_packet_size: usize,
     ty: PacketType,
     payload: T,
+) {
+    write_packets_with_status(buffer, max_packet_size, ty, Status::NORMAL, payload)
+}
+
+// writes the packet out to the write buffer, setting `first_status` on the first packet
+fn write_packets_with_status<'en, T: Encode<'en>>(
+    buffer: &mut Vec<u8>,
+    max_packet_size: usize,
+    ty: PacketType,
+    first_status: Status,
+    payload: T,
 ) {
     assert!(buffer.is_empty());
 
@@ -342,14 +377,20 @@ pub(crate) fn write_packets<'en, T: Encode<'en>>(
             );
         }
 
+        let mut status = if is_last {
+            Status::END_OF_MESSAGE
+        } else {
+            Status::NORMAL
+        };
+
+        if packet_index == 0 {
+            status |= first_status;
+        }
+
         packet_header.truncate(0);
         PacketHeader {
             r#type: ty,
-            status: if is_last {
-                Status::END_OF_MESSAGE
-            } else {
-                Status::NORMAL
-            },
+            status,
             length: u16::try_from(packet_size).expect("packet size impossibly large"),
             server_process_id: 0,
             packet_id: 1,
@@ -1,7 +1,7 @@
 use std::error::Error as StdError;
 use std::fmt::{self, Debug, Display, Formatter};
 
-use crate::error::DatabaseError;
+use crate::error::{text_between, DatabaseError, ErrorKind};
 use crate::mssql::protocol::error::Error;
 
 /// An error returned from the MSSQL database.
@@ -38,6 +38,56 @@ impl DatabaseError for MssqlDatabaseError {
         &self.0.message
     }
 
+    fn kind(&self) -> ErrorKind {
+        // https://learn.microsoft.com/en-us/sql/relational-databases/errors-events/database-engine-events-and-errors
+        match self.0.number {
+            // unique constraint, unique index
+            2627 | 2601 => ErrorKind::UniqueViolation,
+            // 547 is reported for foreign key, reference and check constraints
+            547 if self.message().contains("CHECK constraint") => ErrorKind::CheckViolation,
+            547 => ErrorKind::ForeignKeyViolation,
+            515 => ErrorKind::NotNullViolation,
+            3960 => ErrorKind::SerializationFailure,
+            1205 => ErrorKind::Deadlock,
+            // lock request time out period exceeded
+            1222 => ErrorKind::LockTimeout,
+            // session is in the kill state, shutdown in progress
+            596 | 6005 => ErrorKind::ConnectionLost,
+            _ => ErrorKind::Other,
+        }
+    }
+
+    fn constraint(&self) -> Option<&str> {
+        match self.0.number {
+            2627 => text_between(self.message(), "constraint '", '\''),
+            2601 => text_between(self.message(), "unique index '", '\''),
+            547 => text_between(self.message(), "constraint \"", '"'),
+            _ => None,
+        }
+    }
+
+    fn table(&self) -> Option<&str> {
+        match self.0.number {
+            2627 | 2601 => text_between(self.message(), "object '", '\''),
+            547 => text_between(self.message(), "table \"", '"'),
+            515 => text_between(self.message(), "table '", '\''),
+            _ => None,
+        }
+    }
+
+    fn column(&self) -> Option<&str> {
+        match self.0.number {
+            547 | 515 => text_between(self.message(), "column '", '\''),
+            _ => None,
+        }
+    }
+
+    fn is_retryable_conflict(&self) -> bool {
+        // 1205: chosen as deadlock victim
+        // 3960: snapshot isolation transaction aborted due to update conflict
+        matches!(self.0.number, 1205 | 3960)
+    }
+
     #[doc(hidden)]
     fn as_error(&self) -> &(dyn StdError + Send + Sync + 'static) {
         self
@@ -53,3 +103,69 @@ impl DatabaseError for MssqlDatabaseError {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(number: i32, message: &str) -> MssqlDatabaseError {
+        MssqlDatabaseError(Error {
+            number,
+            state: 1,
+            class: 14,
+            message: message.to_owned(),
+            server: String::new(),
+            procedure: String::new(),
+            line: 1,
+        })
+    }
+
+    #[test]
+    fn unique_violation_reports_table_and_constraint() {
+        let err = error(
+            2627,
+            "Violation of UNIQUE KEY constraint 'UQ_users_email'. Cannot insert duplicate key \
+             in object 'dbo.users'. The duplicate key value is (a@b.c).",
+        );
+
+        assert_eq!(err.kind(), ErrorKind::UniqueViolation);
+        assert_eq!(err.constraint(), Some("UQ_users_email"));
+        assert_eq!(err.table(), Some("dbo.users"));
+    }
+
+    #[test]
+    fn constraint_conflict_distinguishes_check_and_foreign_key() {
+        let err = error(
+            547,
+            "The INSERT statement conflicted with the FOREIGN KEY constraint \"FK_child_parent\". \
+             The conflict occurred in database \"db\", table \"dbo.parent\", column 'id'.",
+        );
+
+        assert_eq!(err.kind(), ErrorKind::ForeignKeyViolation);
+        assert_eq!(err.constraint(), Some("FK_child_parent"));
+        assert_eq!(err.table(), Some("dbo.parent"));
+        assert_eq!(err.column(), Some("id"));
+
+        let err = error(
+            547,
+            "The INSERT statement conflicted with the CHECK constraint \"CK_users_age\". \
+             The conflict occurred in database \"db\", table \"dbo.users\", column 'age'.",
+        );
+
+        assert_eq!(err.kind(), ErrorKind::CheckViolation);
+        assert_eq!(err.constraint(), Some("CK_users_age"));
+    }
+
+    #[test]
+    fn not_null_violation_reports_table_and_column() {
+        let err = error(
+            515,
+            "Cannot insert the value NULL into column 'name', table 'db.dbo.users'; \
+             column does not allow nulls. INSERT fails.",
+        );
+
+        assert_eq!(err.kind(), ErrorKind::NotNullViolation);
+        assert_eq!(err.column(), Some("name"));
+        assert_eq!(err.table(), Some("db.dbo.users"));
+    }
+}
@@ -63,6 +63,8 @@ impl EnvChange {
                 EnvChange::RollbackTransaction(data.get_u64_le())
             }
 
+            18 => EnvChange::ResetConnectionCompletionAck,
+
             _ => {
                 return Err(err_protocol!("unexpected value {} for ENVCHANGE Type", ty));
             }
@@ -7,7 +7,10 @@ use crate::executor::Executor;
 use crate::mssql::protocol::packet::PacketType;
 use crate::mssql::protocol::sql_batch::SqlBatch;
 use crate::mssql::{Mssql, MssqlConnection};
-use crate::transaction::TransactionManager;
+use crate::query_scalar::query_scalar;
+use crate::transaction::{
+    check_savepoint_options, SavepointAction, TransactionManager, TransactionOptions,
+};
 
 /// Implementation of [`TransactionManager`] for MSSQL.
 pub struct MssqlTransactionManager;
@@ -16,18 +19,54 @@ impl TransactionManager for MssqlTransactionManager {
     type Database = Mssql;
 
     fn begin(conn: &mut MssqlConnection) -> BoxFuture<'_, Result<(), Error>> {
+        Self::begin_with(conn, TransactionOptions::default())
+    }
+
+    fn begin_with(
+        conn: &mut MssqlConnection,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, Result<(), Error>> {
         Box::pin(async move {
             let depth = conn.stream.transaction_depth;
 
-            let query = if depth == 0 {
-                Cow::Borrowed("BEGIN TRAN ")
-            } else {
+            check_savepoint_options(depth, &options)?;
+
+            if options.read_only {
+                return Err(Error::Configuration(
+                    "MSSQL does not support read-only transactions".into(),
+                ));
+            }
+
+            let mut restore = None;
+
+            let query = if depth > 0 {
                 Cow::Owned(format!("SAVE TRAN _sqlx_savepoint_{}", depth))
+            } else if let Some(isolation) = options.isolation {
+                // the isolation level stays in effect for the rest of the session,
+                // so remember the current one to restore it when the transaction ends
+                let current: i16 = query_scalar(
+                    "SELECT transaction_isolation_level FROM sys.dm_exec_sessions WHERE session_id = @@SPID",
+                )
+                .fetch_one(&mut *conn)
+                .await?;
+
+                restore = isolation_level_name(current);
+
+                Cow::Owned(format!(
+                    "SET TRANSACTION ISOLATION LEVEL {}; BEGIN TRAN ",
+                    isolation.as_str()
+                ))
+            } else {
+                Cow::Borrowed("BEGIN TRAN ")
             };
 
             conn.execute(&*query).await?;
             conn.stream.transaction_depth = depth + 1;
 
+            if depth == 0 {
+                conn.stream.isolation_to_restore = restore;
+            }
+
             Ok(())
         })
     }
@@ -39,7 +78,8 @@ impl TransactionManager for MssqlTransactionManager {
             if depth > 0 {
                 if depth == 1 {
                     // savepoints are not released in MSSQL
-                    conn.execute("COMMIT TRAN").await?;
+                    let query = end_transaction(conn, "COMMIT TRAN");
+                    conn.execute(&*query).await?;
                 }
 
                 conn.stream.transaction_depth = depth - 1;
@@ -55,7 +95,7 @@ impl TransactionManager for MssqlTransactionManager {
 
             if depth > 0 {
                 let query = if depth == 1 {
-                    Cow::Borrowed("ROLLBACK TRAN")
+                    end_transaction(conn, "ROLLBACK TRAN")
                 } else {
                     Cow::Owned(format!("ROLLBACK TRAN _sqlx_savepoint_{}", depth - 1))
                 };
@@ -73,7 +113,7 @@ impl TransactionManager for MssqlTransactionManager {
 
         if depth > 0 {
             let query = if depth == 1 {
-                Cow::Borrowed("ROLLBACK TRAN")
+                end_transaction(conn, "ROLLBACK TRAN")
             } else {
                 Cow::Owned(format!("ROLLBACK TRAN _sqlx_savepoint_{}", depth - 1))
             };
@@ -92,4 +132,47 @@ impl TransactionManager for MssqlTransactionManager {
             conn.stream.transaction_depth = depth - 1;
         }
     }
+
+    fn savepoint<'c>(
+        conn: &'c mut MssqlConnection,
+        name: &'c str,
+        action: SavepointAction,
+    ) -> BoxFuture<'c, Result<(), Error>> {
+        Box::pin(async move {
+            let query = match action {
+                SavepointAction::Create => format!("SAVE TRAN {}", name),
+                // savepoints are not released in MSSQL
+                SavepointAction::Release => return Ok(()),
+                SavepointAction::RollbackTo => format!("ROLLBACK TRAN {}", name),
+            };
+
+            conn.execute(&*query).await?;
+
+            Ok(())
+        })
+    }
+}
+
+/// Append restoring the isolation level changed by `begin_with` to the statement ending the
+/// transaction, if needed.
+fn end_transaction(conn: &mut MssqlConnection, statement: &'static str) -> Cow<'static, str> {
+    match conn.stream.isolation_to_restore.take() {
+        Some(isolation) => Cow::Owned(format!(
+            "{}; SET TRANSACTION ISOLATION LEVEL {}",
+            statement, isolation
+        )),
+        None => Cow::Borrowed(statement),
+    }
+}
+
+/// The name of an isolation level as reported in `sys.dm_exec_sessions`.
+fn isolation_level_name(level: i16) -> Option<&'static str> {
+    match level {
+        1 => Some("READ UNCOMMITTED"),
+        2 => Some("READ COMMITTED"),
+        3 => Some("REPEATABLE READ"),
+        4 => Some("SERIALIZABLE"),
+        5 => Some("SNAPSHOT"),
+        _ => None,
+    }
 }
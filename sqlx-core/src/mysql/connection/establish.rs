@@ -137,6 +137,7 @@ impl MySqlConnection {
             transaction_depth: 0,
             cache_statement: StatementCache::new(options.statement_cache_capacity),
             log_settings: options.log_settings.clone(),
+            pipes_as_concat: options.pipes_as_concat,
         })
     }
 }
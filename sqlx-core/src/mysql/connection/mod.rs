@@ -2,10 +2,10 @@ use crate::common::StatementCache;
 use crate::connection::{Connection, LogSettings};
 use crate::error::Error;
 use crate::mysql::protocol::statement::StmtClose;
-use crate::mysql::protocol::text::{Ping, Quit};
+use crate::mysql::protocol::text::{Ping, Quit, ResetConnection};
 use crate::mysql::statement::MySqlStatementMetadata;
 use crate::mysql::{MySql, MySqlConnectOptions};
-use crate::transaction::Transaction;
+use crate::transaction::{Transaction, TransactionOptions};
 use futures_core::future::BoxFuture;
 use futures_util::FutureExt;
 use std::fmt::{self, Debug, Formatter};
@@ -34,6 +34,9 @@ pub struct MySqlConnection {
     cache_statement: StatementCache<(u32, MySqlStatementMetadata)>,
 
     log_settings: LogSettings,
+
+    // whether `||` is configured as string concatenation for the session
+    pub(crate) pipes_as_concat: bool,
 }
 
 impl Debug for MySqlConnection {
@@ -73,6 +76,20 @@ impl Connection for MySqlConnection {
         })
     }
 
+    fn reset_session(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move {
+            self.stream.wait_until_ready().await?;
+            self.stream.send_packet(ResetConnection).await?;
+            self.stream.recv_ok().await?;
+
+            // The server deallocates all prepared statements on reset.
+            self.cache_statement.clear();
+
+            // The reset also restores the session variables we set on connect.
+            self.configure_session().await
+        })
+    }
+
     #[doc(hidden)]
     fn flush(&mut self) -> BoxFuture<'_, Result<(), Error>> {
         self.stream.wait_until_ready().boxed()
@@ -108,6 +125,16 @@ impl Connection for MySqlConnection {
         Transaction::begin(self)
     }
 
+    fn begin_with(
+        &mut self,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
+    where
+        Self: Sized,
+    {
+        Transaction::begin_with(self, options)
+    }
+
     fn dbms_name(&mut self) -> BoxFuture<'_, Result<String, Error>> {
         futures_util::future::ready(Ok("MySQL".to_string())).boxed()
     }
@@ -1,7 +1,7 @@
 use std::error::Error;
 use std::fmt::{self, Debug, Display, Formatter};
 
-use crate::error::DatabaseError;
+use crate::error::{text_between, DatabaseError, ErrorKind};
 use crate::mysql::protocol::response::ErrPacket;
 use smallvec::alloc::borrow::Cow;
 
@@ -27,6 +27,28 @@ impl MySqlDatabaseError {
     pub fn message(&self) -> &str {
         &self.0.error_message
     }
+
+    /// The table and key named by a duplicate entry error, e.g.
+    /// `Duplicate entry 'x' for key 'users.email'`. Servers before 8.0.19 only include the key.
+    fn duplicate_key(&self) -> Option<(Option<&str>, &str)> {
+        let message = self.message();
+        let start = message.rfind(" for key '")? + " for key '".len();
+        let key = message[start..].strip_suffix('\'')?;
+
+        Some(match key.split_once('.') {
+            Some((table, key)) => (Some(table), key),
+            None => (None, key),
+        })
+    }
+
+    /// The table named by a foreign key error, e.g.
+    /// ``a foreign key constraint fails (`db`.`child`, CONSTRAINT ..)``.
+    fn foreign_key_table(&self) -> Option<&str> {
+        let table = text_between(self.message(), "constraint fails (", ',')?;
+        let table = table.rsplit("`.`").next()?;
+
+        Some(table.trim_matches('`'))
+    }
 }
 
 impl Debug for MySqlDatabaseError {
@@ -62,6 +84,59 @@ impl DatabaseError for MySqlDatabaseError {
         self.code().map(Cow::Borrowed)
     }
 
+    fn kind(&self) -> ErrorKind {
+        // https://dev.mysql.com/doc/mysql-errors/8.0/en/server-error-reference.html
+        match self.number() {
+            // ER_DUP_ENTRY, ER_DUP_ENTRY_WITH_KEY_NAME
+            1062 | 1586 => ErrorKind::UniqueViolation,
+            // ER_ROW_IS_REFERENCED(_2), ER_NO_REFERENCED_ROW(_2)
+            1216 | 1217 | 1451 | 1452 => ErrorKind::ForeignKeyViolation,
+            // ER_BAD_NULL_ERROR, ER_NO_DEFAULT_FOR_FIELD
+            1048 | 1364 => ErrorKind::NotNullViolation,
+            // ER_CHECK_CONSTRAINT_VIOLATED
+            3819 => ErrorKind::CheckViolation,
+            // ER_LOCK_DEADLOCK
+            1213 => ErrorKind::Deadlock,
+            // ER_LOCK_WAIT_TIMEOUT, ER_LOCK_NOWAIT
+            1205 | 3572 => ErrorKind::LockTimeout,
+            // ER_QUERY_INTERRUPTED, ER_QUERY_TIMEOUT
+            1317 | 3024 => ErrorKind::QueryCanceled,
+            // ER_SERVER_SHUTDOWN, ER_CONNECTION_KILLED
+            1053 | 1927 => ErrorKind::ConnectionLost,
+            _ => ErrorKind::Other,
+        }
+    }
+
+    fn constraint(&self) -> Option<&str> {
+        match self.number() {
+            1062 | 1586 => self.duplicate_key().map(|(_, key)| key),
+            1216 | 1217 | 1451 | 1452 => text_between(self.message(), "CONSTRAINT `", '`'),
+            3819 => text_between(self.message(), "constraint '", '\''),
+            _ => None,
+        }
+    }
+
+    fn table(&self) -> Option<&str> {
+        match self.number() {
+            1062 | 1586 => self.duplicate_key().and_then(|(table, _)| table),
+            1216 | 1217 | 1451 | 1452 => self.foreign_key_table(),
+            _ => None,
+        }
+    }
+
+    fn column(&self) -> Option<&str> {
+        match self.number() {
+            1048 => text_between(self.message(), "Column '", '\''),
+            1364 => text_between(self.message(), "Field '", '\''),
+            _ => None,
+        }
+    }
+
+    fn is_retryable_conflict(&self) -> bool {
+        // ER_LOCK_DEADLOCK
+        self.number() == 1213 || self.code() == Some("40001")
+    }
+
     #[doc(hidden)]
     fn as_error(&self) -> &(dyn Error + Send + Sync + 'static) {
         self
@@ -77,3 +152,52 @@ impl DatabaseError for MySqlDatabaseError {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(error_code: u16, error_message: &str) -> MySqlDatabaseError {
+        MySqlDatabaseError(ErrPacket {
+            error_code,
+            sql_state: None,
+            error_message: error_message.to_owned(),
+        })
+    }
+
+    #[test]
+    fn duplicate_entry_reports_table_and_key() {
+        let err = error(1062, "Duplicate entry 'a@b.c' for key 'users.email'");
+
+        assert_eq!(err.kind(), ErrorKind::UniqueViolation);
+        assert_eq!(err.table(), Some("users"));
+        assert_eq!(err.constraint(), Some("email"));
+
+        let err = error(1062, "Duplicate entry '1' for key 'PRIMARY'");
+
+        assert_eq!(err.table(), None);
+        assert_eq!(err.constraint(), Some("PRIMARY"));
+    }
+
+    #[test]
+    fn foreign_key_error_reports_table_and_constraint() {
+        let err = error(
+            1452,
+            "Cannot add or update a child row: a foreign key constraint fails \
+             (`db`.`child`, CONSTRAINT `child_parent_fk` FOREIGN KEY (`parent_id`) \
+             REFERENCES `parent` (`id`))",
+        );
+
+        assert_eq!(err.kind(), ErrorKind::ForeignKeyViolation);
+        assert_eq!(err.table(), Some("child"));
+        assert_eq!(err.constraint(), Some("child_parent_fk"));
+    }
+
+    #[test]
+    fn not_null_error_reports_column() {
+        let err = error(1048, "Column 'name' cannot be null");
+
+        assert_eq!(err.kind(), ErrorKind::NotNullViolation);
+        assert_eq!(err.column(), Some("name"));
+    }
+}
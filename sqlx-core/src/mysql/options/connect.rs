@@ -18,57 +18,65 @@ impl ConnectOptions for MySqlConnectOptions {
 
             // After the connection is established, we initialize by configuring a few
             // connection parameters
+            conn.configure_session().await?;
 
-            // https://mariadb.com/kb/en/sql-mode/
+            Ok(conn)
+        })
+    }
 
-            // PIPES_AS_CONCAT - Allows using the pipe character (ASCII 124) as string concatenation operator.
-            //                   This means that "A" || "B" can be used in place of CONCAT("A", "B").
+    fn log_statements(&mut self, level: LevelFilter) -> &mut Self {
+        self.log_settings.log_statements(level);
+        self
+    }
 
-            // NO_ENGINE_SUBSTITUTION - If not set, if the available storage engine specified by a CREATE TABLE is
-            //                          not available, a warning is given and the default storage
-            //                          engine is used instead.
+    fn log_slow_statements(&mut self, level: LevelFilter, duration: Duration) -> &mut Self {
+        self.log_settings.log_slow_statements(level, duration);
+        self
+    }
+}
 
-            // NO_ZERO_DATE - Don't allow '0000-00-00'. This is invalid in Rust.
+impl MySqlConnection {
+    // Configure the session, after connecting and after it is reset
+    pub(crate) async fn configure_session(&mut self) -> Result<(), Error> {
+        // https://mariadb.com/kb/en/sql-mode/
 
-            // NO_ZERO_IN_DATE - Don't allow 'YYYY-00-00'. This is invalid in Rust.
+        // PIPES_AS_CONCAT - Allows using the pipe character (ASCII 124) as string concatenation operator.
+        //                   This means that "A" || "B" can be used in place of CONCAT("A", "B").
 
-            // --
+        // NO_ENGINE_SUBSTITUTION - If not set, if the available storage engine specified by a CREATE TABLE is
+        //                          not available, a warning is given and the default storage
+        //                          engine is used instead.
 
-            // Setting the time zone allows us to assume that the output
-            // from a TIMESTAMP field is UTC
+        // NO_ZERO_DATE - Don't allow '0000-00-00'. This is invalid in Rust.
 
-            // --
+        // NO_ZERO_IN_DATE - Don't allow 'YYYY-00-00'. This is invalid in Rust.
 
-            // https://mathiasbynens.be/notes/mysql-utf8mb4
+        // --
 
-            let mut options = String::new();
-            if self.pipes_as_concat {
-                options.push_str(r#"SET sql_mode=(SELECT CONCAT(@@sql_mode, ',PIPES_AS_CONCAT,NO_ENGINE_SUBSTITUTION')),"#);
-            } else {
-                options.push_str(
-                    r#"SET sql_mode=(SELECT CONCAT(@@sql_mode, ',NO_ENGINE_SUBSTITUTION')),"#,
-                );
-            }
-            options.push_str(r#"time_zone='+00:00',"#);
-            options.push_str(&format!(
-                r#"NAMES {} COLLATE {};"#,
-                conn.stream.charset.as_str(),
-                conn.stream.collation.as_str()
-            ));
+        // Setting the time zone allows us to assume that the output
+        // from a TIMESTAMP field is UTC
 
-            conn.execute(&*options).await?;
+        // --
 
-            Ok(conn)
-        })
-    }
+        // https://mathiasbynens.be/notes/mysql-utf8mb4
 
-    fn log_statements(&mut self, level: LevelFilter) -> &mut Self {
-        self.log_settings.log_statements(level);
-        self
-    }
+        let mut options = String::new();
+        if self.pipes_as_concat {
+            options.push_str(r#"SET sql_mode=(SELECT CONCAT(@@sql_mode, ',PIPES_AS_CONCAT,NO_ENGINE_SUBSTITUTION')),"#);
+        } else {
+            options.push_str(
+                r#"SET sql_mode=(SELECT CONCAT(@@sql_mode, ',NO_ENGINE_SUBSTITUTION')),"#,
+            );
+        }
+        options.push_str(r#"time_zone='+00:00',"#);
+        options.push_str(&format!(
+            r#"NAMES {} COLLATE {};"#,
+            self.stream.charset.as_str(),
+            self.stream.collation.as_str()
+        ));
 
-    fn log_slow_statements(&mut self, level: LevelFilter, duration: Duration) -> &mut Self {
-        self.log_settings.log_slow_statements(level, duration);
-        self
+        self.execute(&*options).await?;
+
+        Ok(())
     }
 }
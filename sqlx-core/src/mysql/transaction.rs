@@ -6,8 +6,9 @@ use crate::mysql::connection::Waiting;
 use crate::mysql::protocol::text::Query;
 use crate::mysql::{MySql, MySqlConnection};
 use crate::transaction::{
-    begin_ansi_transaction_sql, commit_ansi_transaction_sql, rollback_ansi_transaction_sql,
-    TransactionManager,
+    begin_ansi_transaction_sql, check_savepoint_options, commit_ansi_transaction_sql,
+    rollback_ansi_transaction_sql, savepoint_ansi_sql, SavepointAction, TransactionManager,
+    TransactionOptions,
 };
 
 /// Implementation of [`TransactionManager`] for MySQL.
@@ -17,10 +18,32 @@ impl TransactionManager for MySqlTransactionManager {
     type Database = MySql;
 
     fn begin(conn: &mut MySqlConnection) -> BoxFuture<'_, Result<(), Error>> {
+        Self::begin_with(conn, TransactionOptions::default())
+    }
+
+    fn begin_with(
+        conn: &mut MySqlConnection,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, Result<(), Error>> {
         Box::pin(async move {
             let depth = conn.transaction_depth;
 
-            conn.execute(&*begin_ansi_transaction_sql(depth)).await?;
+            check_savepoint_options(depth, &options)?;
+
+            if let Some(isolation) = options.isolation {
+                // applies to the next transaction only
+                conn.execute(&*format!(
+                    "SET TRANSACTION ISOLATION LEVEL {}",
+                    isolation.as_str()
+                ))
+                .await?;
+            }
+
+            if options.read_only {
+                conn.execute("START TRANSACTION READ ONLY").await?;
+            } else {
+                conn.execute(&*begin_ansi_transaction_sql(depth)).await?;
+            }
             conn.transaction_depth = depth + 1;
 
             Ok(())
@@ -65,4 +88,16 @@ impl TransactionManager for MySqlTransactionManager {
             conn.transaction_depth = depth - 1;
         }
     }
+
+    fn savepoint<'c>(
+        conn: &'c mut MySqlConnection,
+        name: &'c str,
+        action: SavepointAction,
+    ) -> BoxFuture<'c, Result<(), Error>> {
+        Box::pin(async move {
+            conn.execute(&*savepoint_ansi_sql(name, action)).await?;
+
+            Ok(())
+        })
+    }
 }
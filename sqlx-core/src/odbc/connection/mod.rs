@@ -5,17 +5,15 @@ use crate::odbc::{
     Odbc, OdbcArguments, OdbcBufferSettings, OdbcColumn, OdbcConnectOptions, OdbcQueryResult,
     OdbcRow, OdbcTypeInfo,
 };
-use crate::transaction::Transaction;
+use crate::transaction::{check_savepoint_options, Transaction, TransactionOptions};
 use either::Either;
 use sqlx_rt::spawn_blocking;
 mod odbc_bridge;
 use crate::odbc::{OdbcStatement, OdbcStatementMetadata};
 use futures_core::future::BoxFuture;
 use futures_util::future;
-use odbc_api::{
-    handles::StatementConnection, ConnectionTransitions, Prepared, ResultSetMetadata,
-    SharedConnection,
-};
+use odbc_api::handles::StatementConnection;
+use odbc_api::{ConnectionTransitions, Prepared, ResultSetMetadata, SharedConnection};
 use odbc_bridge::{establish_connection, execute_sql};
 use std::borrow::Cow;
 use std::sync::{Arc, Mutex};
@@ -134,6 +132,7 @@ pub struct OdbcConnection {
     pub(crate) stmt_cache: StatementCache<SharedPreparedStatement>,
     pub(crate) buffer_settings: OdbcBufferSettings,
     pub(crate) log_settings: LogSettings,
+    pub(crate) transaction_depth: usize,
 }
 
 impl std::fmt::Debug for OdbcConnection {
@@ -178,6 +177,7 @@ impl OdbcConnection {
             stmt_cache: StatementCache::new(options.statement_cache_capacity),
             buffer_settings: options.buffer_settings,
             log_settings: options.log_settings.clone(),
+            transaction_depth: 0,
         })
     }
 
@@ -189,15 +189,32 @@ impl OdbcConnection {
         .await
     }
 
-    pub(crate) async fn begin_blocking(&mut self) -> Result<(), Error> {
+    pub(crate) async fn begin_blocking(
+        &mut self,
+        options: TransactionOptions,
+    ) -> Result<(), Error> {
+        check_savepoint_options(self.transaction_depth, &options)?;
+
+        if !options.is_default() {
+            return Err(Error::Configuration(
+                "ODBC does not support transaction options".into(),
+            ));
+        }
+
         self.with_conn("begin", move |conn| {
             conn.set_autocommit(false)?;
             Ok(())
         })
-        .await
+        .await?;
+
+        self.transaction_depth += 1;
+
+        Ok(())
     }
 
     pub(crate) async fn commit_blocking(&mut self) -> Result<(), Error> {
+        self.transaction_depth = self.transaction_depth.saturating_sub(1);
+
         self.with_conn("commit", move |conn| {
             conn.commit()?;
             conn.set_autocommit(true)?;
@@ -207,6 +224,8 @@ impl OdbcConnection {
     }
 
     pub(crate) async fn rollback_blocking(&mut self) -> Result<(), Error> {
+        self.transaction_depth = self.transaction_depth.saturating_sub(1);
+
         self.with_conn("rollback", move |conn| {
             conn.rollback()?;
             conn.set_autocommit(true)?;
@@ -215,6 +234,17 @@ impl OdbcConnection {
         .await
     }
 
+    /// Roll back the transaction without waiting, for dropped transactions.
+    pub(crate) fn start_rollback(&mut self) {
+        self.transaction_depth = self.transaction_depth.saturating_sub(1);
+
+        if let Ok(conn) = self.conn.lock() {
+            if conn.rollback().is_ok() {
+                let _ = conn.set_autocommit(true);
+            }
+        }
+    }
+
     /// Launches a background task to execute the SQL statement and send the results to the returned channel.
     pub(crate) fn execute_stream(
         &mut self,
@@ -364,6 +394,11 @@ impl Connection for OdbcConnection {
         Box::pin(self.ping_blocking())
     }
 
+    /// ODBC has no portable way to reset a session, so this only clears the statement cache.
+    fn reset_session(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(self.clear_cached_statements())
+    }
+
     fn begin(&mut self) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
     where
         Self: Sized,
@@ -371,6 +406,16 @@ impl Connection for OdbcConnection {
         Transaction::begin(self)
     }
 
+    fn begin_with(
+        &mut self,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
+    where
+        Self: Sized,
+    {
+        Transaction::begin_with(self, options)
+    }
+
     fn cached_statements_size(&self) -> usize {
         self.stmt_cache.len()
     }
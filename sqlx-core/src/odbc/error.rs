@@ -1,4 +1,4 @@
-use crate::error::DatabaseError;
+use crate::error::{DatabaseError, ErrorKind};
 use odbc_api::{
     handles::{slice_to_cow_utf8, Record},
     Error as OdbcApiError,
@@ -68,6 +68,27 @@ impl DatabaseError for OdbcDatabaseError {
     fn code(&self) -> Option<Cow<'_, str>> {
         self.code.as_deref().map(Cow::Borrowed)
     }
+    fn kind(&self) -> ErrorKind {
+        let native_error = Self::diagnostic_record(&self.error).map(|record| record.native_error);
+
+        match self.code.as_deref() {
+            Some("23505") => ErrorKind::UniqueViolation,
+            Some("23503") => ErrorKind::ForeignKeyViolation,
+            Some("23502") => ErrorKind::NotNullViolation,
+            Some("23514") => ErrorKind::CheckViolation,
+            // SQL Server and MySQL report deadlocks as serialization failures
+            Some("40001") if matches!(native_error, Some(1205 | 1213)) => ErrorKind::Deadlock,
+            Some("40001") => ErrorKind::SerializationFailure,
+            Some("40P01") => ErrorKind::Deadlock,
+            Some("HYT00" | "HY008") => ErrorKind::QueryCanceled,
+            Some(code) if code.starts_with("08") => ErrorKind::ConnectionLost,
+            _ => ErrorKind::Other,
+        }
+    }
+    fn is_retryable_conflict(&self) -> bool {
+        // serialization failure, or deadlock on PostgreSQL drivers
+        matches!(self.code.as_deref(), Some("40001" | "40P01"))
+    }
     fn as_error(&self) -> &(dyn std::error::Error + Send + Sync + 'static) {
         self
     }
@@ -91,8 +112,15 @@ mod tests {
     use crate::error::DatabaseError;
     use odbc_api::handles::{Record, SqlChar, State};
 
+    // odbc-api uses the narrow (UTF-8) API, except on Windows where it uses the wide one
+    #[cfg(not(target_os = "windows"))]
     fn sql_chars(text: &str) -> Vec<SqlChar> {
-        text.bytes().map(Into::into).collect()
+        text.bytes().collect()
+    }
+
+    #[cfg(target_os = "windows")]
+    fn sql_chars(text: &str) -> Vec<SqlChar> {
+        text.encode_utf16().collect()
     }
 
     #[test]
@@ -109,4 +137,42 @@ mod tests {
         assert_eq!(error.message(), "syntax error near FROM");
         assert_eq!(error.code().as_deref(), Some("HY000"));
     }
+
+    #[test]
+    fn serialization_failure_is_retryable_conflict() {
+        let error = |state: &[u8; 5]| {
+            OdbcDatabaseError::from(OdbcApiError::Diagnostics {
+                function: "SQLExecDirect",
+                record: Record {
+                    state: State(*state),
+                    native_error: 0,
+                    message: sql_chars("conflict"),
+                },
+            })
+        };
+
+        assert!(error(b"40001").is_retryable_conflict());
+        assert!(error(b"40P01").is_retryable_conflict());
+        assert!(!error(b"23505").is_retryable_conflict());
+    }
+
+    #[test]
+    fn database_error_kind_uses_sqlstate_and_native_error() {
+        let error = |state: &[u8; 5], native_error: i32| {
+            OdbcDatabaseError::from(OdbcApiError::Diagnostics {
+                function: "SQLExecDirect",
+                record: Record {
+                    state: State(*state),
+                    native_error,
+                    message: sql_chars("error"),
+                },
+            })
+        };
+
+        assert_eq!(error(b"23505", 0).kind(), ErrorKind::UniqueViolation);
+        assert_eq!(error(b"40001", 1205).kind(), ErrorKind::Deadlock);
+        assert_eq!(error(b"40001", 0).kind(), ErrorKind::SerializationFailure);
+        assert_eq!(error(b"08S01", 0).kind(), ErrorKind::ConnectionLost);
+        assert_eq!(error(b"HY000", 0).kind(), ErrorKind::Other);
+    }
 }
@@ -1,6 +1,9 @@
 use crate::error::Error;
+use crate::executor::Executor;
 use crate::odbc::{Odbc, OdbcConnection};
-use crate::transaction::TransactionManager;
+use crate::transaction::{
+    savepoint_ansi_sql, SavepointAction, TransactionManager, TransactionOptions,
+};
 use futures_core::future::BoxFuture;
 
 pub struct OdbcTransactionManager;
@@ -11,7 +14,14 @@ impl TransactionManager for OdbcTransactionManager {
     fn begin(
         conn: &mut <Self::Database as crate::database::Database>::Connection,
     ) -> BoxFuture<'_, Result<(), Error>> {
-        Box::pin(async move { conn.begin_blocking().await })
+        Self::begin_with(conn, TransactionOptions::default())
+    }
+
+    fn begin_with(
+        conn: &mut <Self::Database as crate::database::Database>::Connection,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move { conn.begin_blocking(options).await })
     }
 
     fn commit(
@@ -27,10 +37,18 @@ impl TransactionManager for OdbcTransactionManager {
     }
 
     fn start_rollback(conn: &mut OdbcConnection) {
-        if let Ok(conn) = conn.conn.lock() {
-            if conn.rollback().is_ok() {
-                let _ = conn.set_autocommit(true);
-            }
-        }
+        conn.start_rollback();
+    }
+
+    fn savepoint<'c>(
+        conn: &'c mut OdbcConnection,
+        name: &'c str,
+        action: SavepointAction,
+    ) -> BoxFuture<'c, Result<(), Error>> {
+        Box::pin(async move {
+            conn.execute(&*savepoint_ansi_sql(name, action)).await?;
+
+            Ok(())
+        })
     }
 }
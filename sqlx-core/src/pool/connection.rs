@@ -1,5 +1,6 @@
 use std::fmt::{self, Debug, Formatter};
 use std::ops::{Deref, DerefMut};
+use std::panic::Location;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
@@ -10,6 +11,7 @@ use crate::database::Database;
 use crate::error::Error;
 
 use super::inner::{DecrementSizeGuard, PoolInner};
+use super::metrics::CloseReason;
 use crate::pool::options::PoolConnectionMetadata;
 use std::future::Future;
 
@@ -19,6 +21,8 @@ use std::future::Future;
 pub struct PoolConnection<DB: Database> {
     live: Option<Live<DB>>,
     pub(crate) pool: Arc<PoolInner<DB>>,
+    // identifies this connection in `PoolInner::checkouts`, if leak detection is enabled
+    checkout_id: Option<u64>,
 }
 
 pub(super) struct Live<DB: Database> {
@@ -132,8 +136,12 @@ impl<DB: Database> PoolConnection<DB> {
 /// Returns the connection to the [`Pool`][crate::pool::Pool] it was checked-out from.
 impl<DB: Database> Drop for PoolConnection<DB> {
     fn drop(&mut self) {
+        if let Some(id) = self.checkout_id {
+            self.pool.checkouts.unregister(id);
+        }
+
         // We still need to spawn a task to maintain `min_connections`.
-        if self.live.is_some() || self.pool.options.min_connections > 0 {
+        if self.live.is_some() || self.pool.min_connections() > 0 {
             if let Ok(handle) = sqlx_rt::Handle::try_current() {
                 handle.spawn(self.return_to_pool());
             }
@@ -183,15 +191,22 @@ impl<DB: Database> Floating<DB, Live<DB>> {
         }
     }
 
-    pub fn reattach(self) -> PoolConnection<DB> {
+    pub fn reattach(self, location: &'static Location<'static>) -> PoolConnection<DB> {
         let Floating { inner, guard } = self;
 
         let pool = Arc::clone(&guard.pool);
+        // Don't pay for the registry lock on every checkout if nothing is going to look at it.
+        let checkout_id = pool
+            .options
+            .leak_detection_threshold
+            .is_some()
+            .then(|| pool.checkouts.register(location));
 
         guard.cancel();
         PoolConnection {
             live: Some(inner),
             pool,
+            checkout_id,
         }
     }
 
@@ -203,9 +218,17 @@ impl<DB: Database> Floating<DB, Live<DB>> {
     ///
     /// Returns `true` if the connection was successfully returned, `false` if it was closed.
     async fn return_to_pool(mut self) -> bool {
+        self.guard.pool.metrics.record_release(&self.metadata());
+
         // Immediately close the connection.
         if self.guard.pool.is_closed() {
-            self.close().await;
+            self.close(CloseReason::PoolClosed).await;
+            return false;
+        }
+
+        // The pool was shrunk while this connection was checked out.
+        if self.guard.pool.try_shrink() {
+            self.retire().await;
             return false;
         }
 
@@ -214,19 +237,35 @@ impl<DB: Database> Floating<DB, Live<DB>> {
             match (test)(&mut self.inner.raw, meta).await {
                 Ok(true) => (),
                 Ok(false) => {
-                    self.close().await;
+                    self.close(CloseReason::AfterRelease).await;
                     return false;
                 }
                 Err(e) => {
                     log::warn!("error from after_release: {}", e);
                     // Connection is broken, don't try to gracefully close as
                     // something weird might happen.
-                    self.close_hard().await;
+                    self.close_hard(CloseReason::AfterRelease).await;
                     return false;
                 }
             }
         }
 
+        if self.guard.pool.options.reset_on_release {
+            // Resetting the session also flushes pending rollbacks and tests the connection.
+            if let Err(e) = self.raw.reset_session().await {
+                log::warn!(
+                    "error occurred while resetting the connection on-release: {}",
+                    e
+                );
+
+                self.close_hard(CloseReason::ResetFailed).await;
+                return false;
+            }
+
+            self.release();
+            return true;
+        }
+
         // test the connection on-release to ensure it is still viable,
         // and flush anything time-sensitive like transaction rollbacks
         // if an Executor future/stream is dropped during an `.await` call, the connection
@@ -241,7 +280,7 @@ impl<DB: Database> Floating<DB, Live<DB>> {
             );
 
             // Connection is broken, don't try to gracefully close.
-            self.close_hard().await;
+            self.close_hard(CloseReason::PingFailed).await;
             false
         } else {
             // if the connection is still viable, release it to the pool
@@ -250,17 +289,33 @@ impl<DB: Database> Floating<DB, Live<DB>> {
         }
     }
 
-    pub async fn close(self) {
+    pub async fn close(self, reason: CloseReason) {
+        self.guard.pool.metrics.record_close(reason);
+
         // This isn't used anywhere that we care about the return value
         let _ = self.inner.raw.close().await;
 
         // `guard` is dropped as intended
     }
 
-    pub async fn close_hard(self) {
+    pub async fn close_hard(self, reason: CloseReason) {
+        self.guard.pool.metrics.record_close(reason);
+
         let _ = self.inner.raw.close_hard().await;
     }
 
+    /// Close the connection after the pool size was already decremented by `try_shrink()`.
+    pub async fn retire(self) {
+        self.guard.pool.metrics.record_close(CloseReason::Retired);
+
+        let Floating { inner, guard } = self;
+
+        // Only the permit is left to release.
+        guard.release_permit();
+
+        let _ = inner.raw.close().await;
+    }
+
     pub fn detach(self) -> DB::Connection {
         self.inner.raw
     }
@@ -303,14 +358,18 @@ impl<DB: Database> Floating<DB, Idle<DB>> {
         }
     }
 
-    pub async fn close(self) -> DecrementSizeGuard<DB> {
+    pub async fn close(self, reason: CloseReason) -> DecrementSizeGuard<DB> {
+        self.guard.pool.metrics.record_close(reason);
+
         if let Err(e) = self.inner.live.raw.close().await {
             log::debug!("error occurred while closing the pool connection: {}", e);
         }
         self.guard
     }
 
-    pub async fn close_hard(self) -> DecrementSizeGuard<DB> {
+    pub async fn close_hard(self, reason: CloseReason) -> DecrementSizeGuard<DB> {
+        self.guard.pool.metrics.record_close(reason);
+
         let _ = self.inner.live.raw.close_hard().await;
 
         self.guard
@@ -1,10 +1,12 @@
 use super::connection::{Floating, Idle, Live};
+use super::leak::CheckoutRegistry;
+use super::metrics::{CloseReason, PoolMetrics, PoolMetricsRecorder};
 use crate::connection::ConnectOptions;
 use crate::connection::Connection;
 use crate::database::Database;
 use crate::error::Error;
-use crate::pool::{deadline_as_timeout, CloseEvent, Pool, PoolOptions};
-use crossbeam_queue::ArrayQueue;
+use crate::pool::{deadline_as_timeout, CheckedOutConnection, CloseEvent, Pool, PoolOptions};
+use crossbeam_queue::SegQueue;
 
 use futures_intrusive::sync::{Semaphore, SemaphoreReleaser};
 
@@ -21,12 +23,19 @@ use std::time::{Duration, Instant};
 
 pub(crate) struct PoolInner<DB: Database> {
     pub(super) connect_options: <DB::Connection as Connection>::Options,
-    pub(super) idle_conns: ArrayQueue<Idle<DB>>,
+    pub(super) idle_conns: SegQueue<Idle<DB>>,
     pub(super) semaphore: Semaphore,
     pub(super) size: AtomicU32,
     pub(super) num_idle: AtomicUsize,
+    max_connections: AtomicU32,
+    min_connections: AtomicU32,
+    /// Semaphore permits which should be taken out of circulation when they are next released,
+    /// after `max_connections` was lowered while connections were checked out.
+    permits_to_retire: AtomicU32,
     is_closed: AtomicBool,
     pub(super) on_closed: event_listener::Event,
+    pub(super) metrics: PoolMetricsRecorder,
+    pub(super) checkouts: CheckoutRegistry,
     pub(super) options: PoolOptions<DB>,
 }
 
@@ -48,12 +57,17 @@ impl<DB: Database> PoolInner<DB> {
 
         let pool = Self {
             connect_options,
-            idle_conns: ArrayQueue::new(capacity),
+            idle_conns: SegQueue::new(),
             semaphore: Semaphore::new(options.fair, semaphore_capacity),
             size: AtomicU32::new(0),
             num_idle: AtomicUsize::new(0),
+            max_connections: AtomicU32::new(options.max_connections),
+            min_connections: AtomicU32::new(options.min_connections),
+            permits_to_retire: AtomicU32::new(0),
             is_closed: AtomicBool::new(false),
             on_closed: event_listener::Event::new(),
+            metrics: PoolMetricsRecorder::new(options.observer.clone()),
+            checkouts: CheckoutRegistry::new(),
             options,
         };
 
@@ -77,6 +91,121 @@ impl<DB: Database> PoolInner<DB> {
         self.num_idle.load(Ordering::Acquire)
     }
 
+    pub(super) fn max_connections(&self) -> u32 {
+        self.max_connections.load(Ordering::Acquire)
+    }
+
+    pub(super) fn min_connections(&self) -> u32 {
+        self.min_connections.load(Ordering::Acquire)
+    }
+
+    pub(super) fn set_max_connections(self: &Arc<Self>, max: u32) {
+        let prev = self.max_connections.swap(max, Ordering::AcqRel);
+
+        // A child pool borrows its permits from the parent, so only the size limit applies.
+        if self.parent().is_some() || max == prev {
+            return;
+        }
+
+        if max > prev {
+            let mut added = max - prev;
+
+            // Cancel out any permits still waiting to be retired from a previous shrink first.
+            let retiring = self
+                .permits_to_retire
+                .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| {
+                    Some(n.saturating_sub(added))
+                })
+                .unwrap_or_else(|n| n);
+
+            added -= cmp::min(retiring, added);
+
+            self.semaphore.release(added as usize);
+            return;
+        }
+
+        self.permits_to_retire
+            .fetch_add(prev - max, Ordering::AcqRel);
+
+        // Retire any permits that aren't currently in use right away;
+        // the rest are retired as checked-out connections are returned.
+        while self.permits_to_retire.load(Ordering::Acquire) > 0 {
+            let Some(mut permit) = self.semaphore.try_acquire(1) else {
+                break;
+            };
+
+            permit.disarm();
+            self.release_permit();
+        }
+
+        // Close surplus idle connections, which don't hold a permit.
+        if self.size() > max && !self.idle_conns.is_empty() {
+            if let Ok(handle) = sqlx_rt::Handle::try_current() {
+                let pool = self.clone();
+                handle.spawn(async move { pool.close_surplus_idle().await });
+            }
+        }
+    }
+
+    pub(super) fn set_min_connections(self: &Arc<Self>, min: u32) {
+        let prev = self.min_connections.swap(min, Ordering::AcqRel);
+
+        if min > prev && !self.is_closed() {
+            if let Ok(handle) = sqlx_rt::Handle::try_current() {
+                let pool = self.clone();
+                handle.spawn(async move { pool.min_connections_maintenance(None).await });
+            }
+        }
+    }
+
+    /// Close idle connections until the pool is back within `max_connections`.
+    async fn close_surplus_idle(self: &Arc<Self>) {
+        while self.size() > self.max_connections() {
+            let Some(conn) = self.try_acquire() else {
+                break;
+            };
+
+            let conn = conn.into_live();
+
+            if !self.try_shrink() {
+                self.release(conn);
+                break;
+            }
+
+            conn.retire().await;
+        }
+    }
+
+    /// Decrement the size if the pool has more connections than `max_connections` allows.
+    ///
+    /// Returns `true` if the size was decremented, in which case the caller must close
+    /// a connection without decrementing the size again.
+    pub(super) fn try_shrink(&self) -> bool {
+        let max_connections = self.max_connections();
+
+        self.size
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |size| {
+                (size > max_connections).then(|| size - 1)
+            })
+            .is_ok()
+    }
+
+    /// Return a semaphore permit to the pool, unless it should be retired to shrink the pool.
+    fn release_permit(&self) {
+        let retired = self
+            .permits_to_retire
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| n.checked_sub(1))
+            .is_ok();
+
+        if !retired {
+            self.semaphore.release(1);
+        }
+    }
+
+    pub(super) fn metrics(&self) -> PoolMetrics {
+        self.metrics.snapshot(self.size(), self.num_idle())
+    }
+
     pub(super) fn is_closed(&self) -> bool {
         self.is_closed.load(Ordering::Acquire)
     }
@@ -86,10 +215,13 @@ impl<DB: Database> PoolInner<DB> {
         self.on_closed.notify(usize::MAX);
 
         async move {
-            for permits in 1..=self.options.max_connections as usize {
+            for permits in 1..=self.max_connections() as usize {
                 // Close any currently idle connections in the pool.
                 while let Some(idle) = self.idle_conns.pop() {
-                    let _ = idle.live.float((*self).clone()).close().await;
+                    idle.live
+                        .float((*self).clone())
+                        .close(CloseReason::PoolClosed)
+                        .await;
                 }
 
                 if self.size() == 0 {
@@ -117,7 +249,7 @@ impl<DB: Database> PoolInner<DB> {
             .parent()
             // If we're already at the max size, we shouldn't try to steal from the parent.
             // This is just going to cause unnecessary churn in `acquire()`.
-            .filter(|_| self.size() < self.options.max_connections);
+            .filter(|_| self.size() < self.max_connections());
 
         let acquire_self = self.semaphore.acquire(1).fuse();
         let mut close_event = self.close_event();
@@ -199,9 +331,7 @@ impl<DB: Database> PoolInner<DB> {
 
         let Floating { inner: idle, guard } = floating.into_idle();
 
-        if self.idle_conns.push(idle).is_err() {
-            panic!("BUG: connection queue overflow in release()");
-        }
+        self.idle_conns.push(idle);
 
         // NOTE: we need to make sure we drop the permit *after* we push to the idle queue
         // don't decrease the size
@@ -215,11 +345,12 @@ impl<DB: Database> PoolInner<DB> {
         self: &'a Arc<Self>,
         permit: SemaphoreReleaser<'a>,
     ) -> Result<DecrementSizeGuard<DB>, SemaphoreReleaser<'a>> {
+        let max_connections = self.max_connections();
+
         match self
             .size
             .fetch_update(Ordering::AcqRel, Ordering::Acquire, |size| {
-                size.checked_add(1)
-                    .filter(|size| size <= &self.options.max_connections)
+                size.checked_add(1).filter(|size| size <= &max_connections)
             }) {
             // we successfully incremented the size
             Ok(_) => Ok(DecrementSizeGuard::from_permit((*self).clone(), permit)),
@@ -233,9 +364,10 @@ impl<DB: Database> PoolInner<DB> {
             return Err(Error::PoolClosed);
         }
 
-        let deadline = Instant::now() + self.options.acquire_timeout;
+        let started_at = Instant::now();
+        let deadline = started_at + self.options.acquire_timeout;
 
-        sqlx_rt::timeout(
+        let res = sqlx_rt::timeout(
             self.options.acquire_timeout,
             async {
                 loop {
@@ -274,7 +406,16 @@ impl<DB: Database> PoolInner<DB> {
             }
         )
             .await
-            .map_err(|_| Error::PoolTimedOut)?
+            .map_err(|_| Error::PoolTimedOut)
+            .and_then(|res| res);
+
+        match &res {
+            Ok(_) => self.metrics.record_acquire(started_at.elapsed()),
+            Err(Error::PoolTimedOut) => self.metrics.record_acquire_timeout(started_at.elapsed()),
+            Err(_) => (),
+        }
+
+        res
     }
 
     pub(super) async fn connect(
@@ -291,6 +432,7 @@ impl<DB: Database> PoolInner<DB> {
 
         loop {
             let timeout = deadline_as_timeout(deadline)?;
+            let started_at = Instant::now();
 
             // result here is `Result<Result<C, Error>, TimeoutError>`
             // if this block does not return, sleep for the backoff timeout and try again
@@ -310,9 +452,13 @@ impl<DB: Database> PoolInner<DB> {
                     };
 
                     match res {
-                        Ok(()) => return Ok(Floating::new_live(raw, guard)),
+                        Ok(()) => {
+                            self.metrics.record_connect(started_at.elapsed());
+                            return Ok(Floating::new_live(raw, guard));
+                        }
                         Err(e) => {
                             log::error!("error returned from after_connect: {:?}", e);
+                            self.metrics.record_connect_failure(&e);
                             // The connection is broken, don't try to close nicely.
                             let _ = raw.close_hard().await;
 
@@ -321,18 +467,27 @@ impl<DB: Database> PoolInner<DB> {
                     }
                 }
 
-                // an IO error while connecting is assumed to be the system starting up
-                Ok(Err(Error::Io(e))) if e.kind() == std::io::ErrorKind::ConnectionRefused => (),
+                Ok(Err(e)) => {
+                    self.metrics.record_connect_failure(&e);
 
-                // We got a transient database error, retry.
-                Ok(Err(Error::Database(error))) if error.is_transient_in_connect_phase() => (),
+                    match e {
+                        // an IO error while connecting is assumed to be the system starting up
+                        Error::Io(e) if e.kind() == std::io::ErrorKind::ConnectionRefused => (),
 
-                // Any other error while connection should immediately
-                // terminate and bubble the error up
-                Ok(Err(e)) => return Err(e),
+                        // We got a transient database error, retry.
+                        Error::Database(error) if error.is_transient_in_connect_phase() => (),
+
+                        // Any other error while connection should immediately
+                        // terminate and bubble the error up
+                        e => return Err(e),
+                    }
+                }
 
                 // timed out
-                Err(_) => return Err(Error::PoolTimedOut),
+                Err(_) => {
+                    self.metrics.record_connect_failure(&Error::PoolTimedOut);
+                    return Err(Error::PoolTimedOut);
+                }
             }
 
             // If the connection is refused, wait in exponentially
@@ -354,7 +509,7 @@ impl<DB: Database> PoolInner<DB> {
             };
         }
 
-        while self.size() < self.options.min_connections {
+        while self.size() < self.min_connections() {
             // Don't wait for a semaphore permit.
             //
             // If no extra permits are available then we shouldn't be trying to spin up
@@ -420,7 +575,7 @@ async fn check_idle_conn<DB: Database>(
     // If the connection we pulled has expired, close the connection and
     // immediately create a new connection
     if is_beyond_max_lifetime(&conn, options) {
-        return Err(conn.close().await);
+        return Err(conn.close(CloseReason::MaxLifetime).await);
     }
 
     if options.test_before_acquire {
@@ -431,7 +586,7 @@ async fn check_idle_conn<DB: Database>(
             // the error itself here isn't necessarily unexpected so WARN is too strong
             log::info!("ping on idle connection returned error: {}", e);
             // connection is broken so don't try to close nicely
-            return Err(conn.close_hard().await);
+            return Err(conn.close_hard(CloseReason::PingFailed).await);
         }
     }
 
@@ -440,13 +595,13 @@ async fn check_idle_conn<DB: Database>(
         match test(&mut conn.live.raw, meta).await {
             Ok(false) => {
                 // connection was rejected by user-defined hook, close nicely
-                return Err(conn.close().await);
+                return Err(conn.close(CloseReason::BeforeAcquire).await);
             }
 
             Err(error) => {
                 log::warn!("error from `before_acquire`: {}", error);
                 // connection is broken so don't try to close nicely
-                return Err(conn.close_hard().await);
+                return Err(conn.close_hard(CloseReason::BeforeAcquire).await);
             }
 
             Ok(true) => {}
@@ -458,6 +613,10 @@ async fn check_idle_conn<DB: Database>(
 }
 
 fn spawn_maintenance_tasks<DB: Database>(pool: &Arc<PoolInner<DB>>) {
+    if let Some(threshold) = pool.options.leak_detection_threshold {
+        spawn_leak_detection(pool, threshold);
+    }
+
     let pool = Arc::clone(pool);
 
     let period = match (pool.options.max_lifetime, pool.options.idle_timeout) {
@@ -466,7 +625,7 @@ fn spawn_maintenance_tasks<DB: Database>(pool: &Arc<PoolInner<DB>>) {
         (Some(a), Some(b)) => cmp::min(a, b),
 
         (None, None) => {
-            if pool.options.min_connections > 0 {
+            if pool.min_connections() > 0 {
                 sqlx_rt::spawn(async move {
                     pool.min_connections_maintenance(None).await;
                 });
@@ -502,26 +661,76 @@ fn spawn_maintenance_tasks<DB: Database>(pool: &Arc<PoolInner<DB>>) {
     });
 }
 
+fn spawn_leak_detection<DB: Database>(pool: &Arc<PoolInner<DB>>, threshold: Duration) {
+    let pool = Arc::clone(pool);
+
+    // Report leaks at most half a threshold late, without spinning on tiny thresholds.
+    let period = cmp::max(threshold / 2, Duration::from_millis(10));
+
+    sqlx_rt::spawn(async move {
+        // Immediately cancel this task if the pool is closed.
+        let _ = pool
+            .close_event()
+            .do_until(async {
+                while !pool.is_closed() {
+                    sqlx_rt::sleep(period).await;
+
+                    for conn in pool.checkouts.find_leaks(threshold) {
+                        report_leak(&pool.options, &conn);
+                    }
+                }
+            })
+            .await;
+    });
+}
+
+fn report_leak<DB: Database>(options: &PoolOptions<DB>, conn: &CheckedOutConnection) {
+    if let Some(on_leak) = &options.on_leak {
+        on_leak(conn);
+        return;
+    }
+
+    match &conn.backtrace {
+        Some(backtrace) => log::warn!(
+            "connection acquired at {} has been checked out for {:?}; possible leak, acquired at:\n{}",
+            conn.location,
+            conn.checked_out_for,
+            backtrace
+        ),
+        None => log::warn!(
+            "connection acquired at {} has been checked out for {:?}; possible leak",
+            conn.location,
+            conn.checked_out_for
+        ),
+    }
+}
+
 async fn do_reap<DB: Database>(pool: &Arc<PoolInner<DB>>) {
     // reap at most the current size minus the minimum idle
-    let max_reaped = pool.size().saturating_sub(pool.options.min_connections);
+    let max_reaped = pool.size().saturating_sub(pool.min_connections());
 
     // collect connections to reap
-    let (reap, keep) = (0..max_reaped)
-        // only connections waiting in the queue
-        .filter_map(|_| pool.try_acquire())
-        .partition::<Vec<_>, _>(|conn| {
-            is_beyond_idle_timeout(conn, &pool.options)
-                || is_beyond_max_lifetime(conn, &pool.options)
-        });
+    let mut reap = Vec::new();
+    let mut keep = Vec::new();
+
+    // only connections waiting in the queue
+    for conn in (0..max_reaped).filter_map(|_| pool.try_acquire()) {
+        if is_beyond_max_lifetime(&conn, &pool.options) {
+            reap.push((conn, CloseReason::MaxLifetime));
+        } else if is_beyond_idle_timeout(&conn, &pool.options) {
+            reap.push((conn, CloseReason::IdleTimeout));
+        } else {
+            keep.push(conn);
+        }
+    }
 
     for conn in keep {
         // return valid connections to the pool first
         pool.release(conn.into_live());
     }
 
-    for conn in reap {
-        let _ = conn.close().await;
+    for (conn, reason) in reap {
+        let _ = conn.close(reason).await;
     }
 }
 
@@ -552,8 +761,8 @@ impl<DB: Database> DecrementSizeGuard<DB> {
     /// Release the semaphore permit without decreasing the pool size.
     ///
     /// If the permit was stolen from the pool's parent, it will be returned to the child's semaphore.
-    fn release_permit(self) {
-        self.pool.semaphore.release(1);
+    pub(super) fn release_permit(self) {
+        self.pool.release_permit();
         self.cancel();
     }
 
@@ -568,7 +777,7 @@ impl<DB: Database> Drop for DecrementSizeGuard<DB> {
             self.pool.size.fetch_sub(1, Ordering::AcqRel);
 
             // and here we release the permit we got on construction
-            self.pool.semaphore.release(1);
+            self.pool.release_permit();
         }
     }
 }
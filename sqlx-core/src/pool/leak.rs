@@ -0,0 +1,118 @@
+use crate::HashMap;
+use std::backtrace::{Backtrace, BacktraceStatus};
+use std::cmp::Reverse;
+use std::panic::Location;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// A connection that is currently checked out of a [`Pool`][crate::pool::Pool].
+///
+/// Returned by [`Pool::checked_out_connections`][crate::pool::Pool::checked_out_connections]
+/// and passed to [`PoolOptions::on_leak`][crate::pool::PoolOptions::on_leak].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct CheckedOutConnection {
+    /// The source location of the call that acquired the connection.
+    ///
+    /// For connections acquired implicitly by passing `&Pool` as an `Executor`, this points
+    /// inside SQLx; the [`backtrace`][Self::backtrace] has the full call stack.
+    pub location: &'static Location<'static>,
+
+    /// How long the connection has been checked out.
+    pub checked_out_for: Duration,
+
+    /// The call stack at the time the connection was acquired.
+    ///
+    /// Only captured if backtraces are enabled with the `RUST_BACKTRACE` or `RUST_LIB_BACKTRACE`
+    /// environment variables, see [`Backtrace::capture`].
+    pub backtrace: Option<Arc<Backtrace>>,
+}
+
+struct Checkout {
+    location: &'static Location<'static>,
+    acquired_at: Instant,
+    backtrace: Option<Arc<Backtrace>>,
+    // Only report each leaked connection once.
+    reported: bool,
+}
+
+/// Keeps track of the connections currently checked out of a pool.
+///
+/// Only used when [`PoolOptions::leak_detection_threshold`][crate::pool::PoolOptions::leak_detection_threshold]
+/// is set.
+pub(super) struct CheckoutRegistry {
+    next_id: AtomicU64,
+    checkouts: Mutex<HashMap<u64, Checkout>>,
+}
+
+impl CheckoutRegistry {
+    pub(super) fn new() -> Self {
+        Self {
+            next_id: AtomicU64::new(0),
+            checkouts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Record a connection being checked out, returning the ID to pass to `unregister()`.
+    pub(super) fn register(&self, location: &'static Location<'static>) -> u64 {
+        let backtrace = Some(Backtrace::capture())
+            .filter(|backtrace| backtrace.status() == BacktraceStatus::Captured)
+            .map(Arc::new);
+
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+
+        self.lock().insert(
+            id,
+            Checkout {
+                location,
+                acquired_at: Instant::now(),
+                backtrace,
+                reported: false,
+            },
+        );
+
+        id
+    }
+
+    pub(super) fn unregister(&self, id: u64) {
+        self.lock().remove(&id);
+    }
+
+    /// List all checked-out connections, the longest checked out first.
+    pub(super) fn list(&self) -> Vec<CheckedOutConnection> {
+        let mut list: Vec<_> = self.lock().values().map(Checkout::info).collect();
+
+        list.sort_by_key(|conn| Reverse(conn.checked_out_for));
+        list
+    }
+
+    /// Return the connections that exceeded `threshold` since the last call.
+    pub(super) fn find_leaks(&self, threshold: Duration) -> Vec<CheckedOutConnection> {
+        self.lock()
+            .values_mut()
+            .filter(|checkout| !checkout.reported && checkout.acquired_at.elapsed() > threshold)
+            .map(|checkout| {
+                checkout.reported = true;
+                checkout.info()
+            })
+            .collect()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<u64, Checkout>> {
+        // The map is always left in a consistent state, even if a thread panicked.
+        self.checkouts
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Checkout {
+    fn info(&self) -> CheckedOutConnection {
+        CheckedOutConnection {
+            location: self.location,
+            checked_out_for: self.acquired_at.elapsed(),
+            backtrace: self.backtrace.clone(),
+        }
+    }
+}
@@ -0,0 +1,470 @@
+use crate::error::Error;
+use crate::pool::PoolConnectionMetadata;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Upper bounds of the buckets used for [`AcquireWaitHistogram`].
+///
+/// Acquires that waited longer than the last bound are counted in a final overflow bucket.
+const ACQUIRE_WAIT_BUCKETS: [Duration; 10] = [
+    Duration::from_micros(100),
+    Duration::from_millis(1),
+    Duration::from_millis(5),
+    Duration::from_millis(10),
+    Duration::from_millis(50),
+    Duration::from_millis(100),
+    Duration::from_millis(500),
+    Duration::from_secs(1),
+    Duration::from_secs(5),
+    Duration::from_secs(30),
+];
+
+const NUM_BUCKETS: usize = ACQUIRE_WAIT_BUCKETS.len() + 1;
+
+/// The reason a connection owned by a [`Pool`][crate::pool::Pool] was closed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum CloseReason {
+    /// The connection exceeded [`PoolOptions::max_lifetime`][crate::pool::PoolOptions::max_lifetime].
+    MaxLifetime,
+
+    /// The connection sat in the idle queue for longer than
+    /// [`PoolOptions::idle_timeout`][crate::pool::PoolOptions::idle_timeout].
+    IdleTimeout,
+
+    /// The connection failed a liveness check, either before being acquired
+    /// (see [`PoolOptions::test_before_acquire`][crate::pool::PoolOptions::test_before_acquire])
+    /// or when being returned to the pool.
+    PingFailed,
+
+    /// The connection was rejected or errored in
+    /// [`PoolOptions::before_acquire`][crate::pool::PoolOptions::before_acquire].
+    BeforeAcquire,
+
+    /// The connection was rejected or errored in
+    /// [`PoolOptions::after_release`][crate::pool::PoolOptions::after_release].
+    AfterRelease,
+
+    /// Resetting the session state failed when the connection was returned to the pool
+    /// (see [`PoolOptions::reset_on_release`][crate::pool::PoolOptions::reset_on_release]).
+    ResetFailed,
+
+    /// The pool was closed with [`Pool::close`][crate::pool::Pool::close].
+    PoolClosed,
+
+    /// The pool had more connections than allowed after
+    /// [`Pool::set_max_connections`][crate::pool::Pool::set_max_connections] lowered the limit.
+    Retired,
+}
+
+/// Receives events from a [`Pool`][crate::pool::Pool] as they happen.
+///
+/// Set with [`PoolOptions::observer`][crate::pool::PoolOptions::observer]. All methods have
+/// default no-op implementations so only the events of interest need to be handled.
+///
+/// The methods are invoked synchronously from within the pool, often while a task is waiting
+/// on it, so they should return quickly and must not block. Forwarding the values to a metrics
+/// library or a channel is the intended use.
+pub trait PoolObserver: Send + Sync + 'static {
+    /// A connection was handed out by [`Pool::acquire`][crate::pool::Pool::acquire] or
+    /// [`Pool::try_acquire`][crate::pool::Pool::try_acquire] after waiting for `wait`.
+    fn on_acquire(&self, wait: Duration) {
+        let _ = wait;
+    }
+
+    /// [`Pool::acquire`][crate::pool::Pool::acquire] gave up with [`Error::PoolTimedOut`]
+    /// after waiting for `wait`.
+    fn on_acquire_timeout(&self, wait: Duration) {
+        let _ = wait;
+    }
+
+    /// A checked-out connection was returned to the pool.
+    ///
+    /// This is invoked before the connection is tested and put back in the idle queue,
+    /// so it may still be closed afterwards.
+    fn on_release(&self, meta: &PoolConnectionMetadata) {
+        let _ = meta;
+    }
+
+    /// A new connection was opened, which took `elapsed`
+    /// (including [`PoolOptions::after_connect`][crate::pool::PoolOptions::after_connect]).
+    fn on_connect(&self, elapsed: Duration) {
+        let _ = elapsed;
+    }
+
+    /// An attempt to open a new connection failed.
+    ///
+    /// The pool may retry, so a single [`Pool::acquire`][crate::pool::Pool::acquire] call can
+    /// generate several of these events.
+    fn on_connect_failure(&self, error: &Error) {
+        let _ = error;
+    }
+
+    /// A connection owned by the pool was closed.
+    fn on_close(&self, reason: CloseReason) {
+        let _ = reason;
+    }
+}
+
+/// Allows sharing an observer with the rest of the application, e.g. to read its state.
+impl<T: PoolObserver> PoolObserver for Arc<T> {
+    fn on_acquire(&self, wait: Duration) {
+        (**self).on_acquire(wait)
+    }
+
+    fn on_acquire_timeout(&self, wait: Duration) {
+        (**self).on_acquire_timeout(wait)
+    }
+
+    fn on_release(&self, meta: &PoolConnectionMetadata) {
+        (**self).on_release(meta)
+    }
+
+    fn on_connect(&self, elapsed: Duration) {
+        (**self).on_connect(elapsed)
+    }
+
+    fn on_connect_failure(&self, error: &Error) {
+        (**self).on_connect_failure(error)
+    }
+
+    fn on_close(&self, reason: CloseReason) {
+        (**self).on_close(reason)
+    }
+}
+
+/// A point-in-time snapshot of the state of a [`Pool`][crate::pool::Pool].
+///
+/// Returned by [`Pool::metrics`][crate::pool::Pool::metrics]. Counters are cumulative since
+/// the pool was created; compute the difference between two snapshots to get a rate.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct PoolMetrics {
+    /// The number of connections currently open, both idle and in use.
+    pub size: u32,
+
+    /// The number of connections currently in the idle queue.
+    pub num_idle: usize,
+
+    /// The number of connections currently checked out of the pool.
+    pub in_use: u32,
+
+    /// The number of connections successfully acquired.
+    pub acquires: u64,
+
+    /// The number of calls to `acquire()` that failed with [`Error::PoolTimedOut`].
+    pub acquire_timeouts: u64,
+
+    /// The distribution of the time spent waiting in successful calls to `acquire()`.
+    pub acquire_wait: AcquireWaitHistogram,
+
+    /// The number of connections opened by the pool.
+    pub connections_opened: u64,
+
+    /// The number of failed attempts to open a connection.
+    pub connect_failures: u64,
+
+    /// The number of connections closed because they exceeded `max_lifetime`.
+    pub max_lifetime_expirations: u64,
+
+    /// The number of connections closed because they exceeded `idle_timeout`.
+    pub idle_timeout_expirations: u64,
+
+    /// The number of connections closed because they failed a liveness check.
+    pub ping_failures: u64,
+
+    /// The number of connections closed by the `before_acquire` callback.
+    pub before_acquire_rejections: u64,
+
+    /// The number of connections closed by the `after_release` callback.
+    pub after_release_rejections: u64,
+
+    /// The number of connections closed because resetting their session state failed.
+    pub reset_failures: u64,
+
+    /// The number of connections closed because the pool was closed.
+    pub closed_with_pool: u64,
+
+    /// The number of connections closed because the pool was shrunk.
+    pub retired: u64,
+}
+
+impl PoolMetrics {
+    /// The number of connections closed by the pool with the given reason.
+    pub fn connections_closed_by(&self, reason: CloseReason) -> u64 {
+        match reason {
+            CloseReason::MaxLifetime => self.max_lifetime_expirations,
+            CloseReason::IdleTimeout => self.idle_timeout_expirations,
+            CloseReason::PingFailed => self.ping_failures,
+            CloseReason::BeforeAcquire => self.before_acquire_rejections,
+            CloseReason::AfterRelease => self.after_release_rejections,
+            CloseReason::ResetFailed => self.reset_failures,
+            CloseReason::PoolClosed => self.closed_with_pool,
+            CloseReason::Retired => self.retired,
+        }
+    }
+
+    /// The total number of connections closed by the pool, for any reason.
+    pub fn connections_closed(&self) -> u64 {
+        self.max_lifetime_expirations
+            + self.idle_timeout_expirations
+            + self.ping_failures
+            + self.before_acquire_rejections
+            + self.after_release_rejections
+            + self.reset_failures
+            + self.closed_with_pool
+            + self.retired
+    }
+}
+
+/// A histogram of the time spent waiting for a connection in `acquire()`.
+#[derive(Debug, Clone, Default)]
+pub struct AcquireWaitHistogram {
+    counts: [u64; NUM_BUCKETS],
+    sum: Duration,
+    max: Duration,
+}
+
+impl AcquireWaitHistogram {
+    /// The number of samples recorded.
+    pub fn count(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    /// The total time spent waiting across all samples.
+    pub fn sum(&self) -> Duration {
+        self.sum
+    }
+
+    /// The longest wait recorded.
+    pub fn max(&self) -> Duration {
+        self.max
+    }
+
+    /// The mean wait time, or `None` if no samples were recorded.
+    pub fn mean(&self) -> Option<Duration> {
+        let count = u32::try_from(self.count()).ok().filter(|&c| c > 0)?;
+        Some(self.sum / count)
+    }
+
+    /// Iterate over the buckets of this histogram as `(upper_bound, count)` pairs.
+    ///
+    /// The bounds are inclusive and the counts are not cumulative. The last bucket has an
+    /// upper bound of `None` and holds all samples greater than the previous bound.
+    pub fn buckets(&self) -> impl Iterator<Item = (Option<Duration>, u64)> + '_ {
+        ACQUIRE_WAIT_BUCKETS
+            .iter()
+            .copied()
+            .map(Some)
+            .chain(std::iter::once(None))
+            .zip(self.counts.iter().copied())
+    }
+}
+
+fn bucket_index(wait: Duration) -> usize {
+    ACQUIRE_WAIT_BUCKETS.partition_point(|bound| *bound < wait)
+}
+
+fn duration_to_nanos(duration: Duration) -> u64 {
+    u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX)
+}
+
+/// Atomic counters backing [`PoolMetrics`], which also forwards events to the [`PoolObserver`].
+pub(super) struct PoolMetricsRecorder {
+    observer: Option<Arc<dyn PoolObserver>>,
+    acquires: AtomicU64,
+    acquire_timeouts: AtomicU64,
+    acquire_wait_counts: [AtomicU64; NUM_BUCKETS],
+    acquire_wait_sum_nanos: AtomicU64,
+    acquire_wait_max_nanos: AtomicU64,
+    connections_opened: AtomicU64,
+    connect_failures: AtomicU64,
+    max_lifetime_expirations: AtomicU64,
+    idle_timeout_expirations: AtomicU64,
+    ping_failures: AtomicU64,
+    before_acquire_rejections: AtomicU64,
+    after_release_rejections: AtomicU64,
+    reset_failures: AtomicU64,
+    closed_with_pool: AtomicU64,
+    retired: AtomicU64,
+}
+
+impl PoolMetricsRecorder {
+    pub(super) fn new(observer: Option<Arc<dyn PoolObserver>>) -> Self {
+        Self {
+            observer,
+            acquires: AtomicU64::new(0),
+            acquire_timeouts: AtomicU64::new(0),
+            acquire_wait_counts: Default::default(),
+            acquire_wait_sum_nanos: AtomicU64::new(0),
+            acquire_wait_max_nanos: AtomicU64::new(0),
+            connections_opened: AtomicU64::new(0),
+            connect_failures: AtomicU64::new(0),
+            max_lifetime_expirations: AtomicU64::new(0),
+            idle_timeout_expirations: AtomicU64::new(0),
+            ping_failures: AtomicU64::new(0),
+            before_acquire_rejections: AtomicU64::new(0),
+            after_release_rejections: AtomicU64::new(0),
+            reset_failures: AtomicU64::new(0),
+            closed_with_pool: AtomicU64::new(0),
+            retired: AtomicU64::new(0),
+        }
+    }
+
+    pub(super) fn record_acquire(&self, wait: Duration) {
+        let nanos = duration_to_nanos(wait);
+
+        self.acquires.fetch_add(1, Ordering::Relaxed);
+        self.acquire_wait_counts[bucket_index(wait)].fetch_add(1, Ordering::Relaxed);
+        self.acquire_wait_sum_nanos
+            .fetch_add(nanos, Ordering::Relaxed);
+        self.acquire_wait_max_nanos
+            .fetch_max(nanos, Ordering::Relaxed);
+
+        if let Some(observer) = &self.observer {
+            observer.on_acquire(wait);
+        }
+    }
+
+    pub(super) fn record_acquire_timeout(&self, wait: Duration) {
+        self.acquire_timeouts.fetch_add(1, Ordering::Relaxed);
+
+        if let Some(observer) = &self.observer {
+            observer.on_acquire_timeout(wait);
+        }
+    }
+
+    pub(super) fn record_release(&self, meta: &PoolConnectionMetadata) {
+        if let Some(observer) = &self.observer {
+            observer.on_release(meta);
+        }
+    }
+
+    pub(super) fn record_connect(&self, elapsed: Duration) {
+        self.connections_opened.fetch_add(1, Ordering::Relaxed);
+
+        if let Some(observer) = &self.observer {
+            observer.on_connect(elapsed);
+        }
+    }
+
+    pub(super) fn record_connect_failure(&self, error: &Error) {
+        self.connect_failures.fetch_add(1, Ordering::Relaxed);
+
+        if let Some(observer) = &self.observer {
+            observer.on_connect_failure(error);
+        }
+    }
+
+    pub(super) fn record_close(&self, reason: CloseReason) {
+        let counter = match reason {
+            CloseReason::MaxLifetime => &self.max_lifetime_expirations,
+            CloseReason::IdleTimeout => &self.idle_timeout_expirations,
+            CloseReason::PingFailed => &self.ping_failures,
+            CloseReason::BeforeAcquire => &self.before_acquire_rejections,
+            CloseReason::AfterRelease => &self.after_release_rejections,
+            CloseReason::ResetFailed => &self.reset_failures,
+            CloseReason::PoolClosed => &self.closed_with_pool,
+            CloseReason::Retired => &self.retired,
+        };
+
+        counter.fetch_add(1, Ordering::Relaxed);
+
+        if let Some(observer) = &self.observer {
+            observer.on_close(reason);
+        }
+    }
+
+    pub(super) fn snapshot(&self, size: u32, num_idle: usize) -> PoolMetrics {
+        let load = |counter: &AtomicU64| counter.load(Ordering::Relaxed);
+
+        let idle = u32::try_from(num_idle).unwrap_or(u32::MAX);
+
+        PoolMetrics {
+            size,
+            num_idle,
+            in_use: size.saturating_sub(idle),
+            acquires: load(&self.acquires),
+            acquire_timeouts: load(&self.acquire_timeouts),
+            acquire_wait: AcquireWaitHistogram {
+                counts: std::array::from_fn(|i| load(&self.acquire_wait_counts[i])),
+                sum: Duration::from_nanos(load(&self.acquire_wait_sum_nanos)),
+                max: Duration::from_nanos(load(&self.acquire_wait_max_nanos)),
+            },
+            connections_opened: load(&self.connections_opened),
+            connect_failures: load(&self.connect_failures),
+            max_lifetime_expirations: load(&self.max_lifetime_expirations),
+            idle_timeout_expirations: load(&self.idle_timeout_expirations),
+            ping_failures: load(&self.ping_failures),
+            before_acquire_rejections: load(&self.before_acquire_rejections),
+            after_release_rejections: load(&self.after_release_rejections),
+            reset_failures: load(&self.reset_failures),
+            closed_with_pool: load(&self.closed_with_pool),
+            retired: load(&self.retired),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn it_buckets_acquire_wait() {
+        assert_eq!(bucket_index(Duration::ZERO), 0);
+        assert_eq!(bucket_index(Duration::from_micros(100)), 0);
+        assert_eq!(bucket_index(Duration::from_micros(101)), 1);
+        assert_eq!(bucket_index(Duration::from_millis(7)), 3);
+        assert_eq!(bucket_index(Duration::from_secs(30)), 9);
+        assert_eq!(bucket_index(Duration::from_secs(31)), 10);
+    }
+
+    #[test]
+    fn it_snapshots_recorded_events() {
+        #[derive(Default)]
+        struct Events(Mutex<Vec<CloseReason>>);
+
+        impl PoolObserver for Events {
+            fn on_close(&self, reason: CloseReason) {
+                self.0.lock().unwrap().push(reason);
+            }
+        }
+
+        let events = Arc::new(Events::default());
+        let recorder = PoolMetricsRecorder::new(Some(Arc::new(events.clone())));
+
+        recorder.record_acquire(Duration::from_millis(2));
+        recorder.record_acquire(Duration::from_millis(40));
+        recorder.record_acquire_timeout(Duration::from_secs(30));
+        recorder.record_connect(Duration::from_millis(3));
+        recorder.record_connect_failure(&Error::PoolTimedOut);
+        recorder.record_close(CloseReason::IdleTimeout);
+        recorder.record_close(CloseReason::MaxLifetime);
+        recorder.record_close(CloseReason::IdleTimeout);
+
+        let metrics = recorder.snapshot(3, 1);
+
+        assert_eq!(metrics.in_use, 2);
+        assert_eq!(metrics.acquires, 2);
+        assert_eq!(metrics.acquire_timeouts, 1);
+        assert_eq!(metrics.acquire_wait.count(), 2);
+        assert_eq!(metrics.acquire_wait.max(), Duration::from_millis(40));
+        assert_eq!(metrics.acquire_wait.mean(), Some(Duration::from_millis(21)));
+        assert_eq!(metrics.connections_opened, 1);
+        assert_eq!(metrics.connect_failures, 1);
+        assert_eq!(metrics.idle_timeout_expirations, 2);
+        assert_eq!(metrics.connections_closed_by(CloseReason::MaxLifetime), 1);
+        assert_eq!(metrics.connections_closed(), 3);
+
+        assert_eq!(
+            *events.0.lock().unwrap(),
+            [
+                CloseReason::IdleTimeout,
+                CloseReason::MaxLifetime,
+                CloseReason::IdleTimeout
+            ]
+        );
+    }
+}
@@ -53,6 +53,11 @@
 //! A connection or transaction may also be manually acquired with
 //! [`Pool::acquire`] or
 //! [`Pool::begin`].
+//!
+//! # Read replicas
+//!
+//! [`RoutedPool`] combines a pool for the primary database with pools for its read replicas,
+//! sending queries to the primary unless they are explicitly routed to a replica.
 
 use self::inner::PoolInner;
 #[cfg(all(
@@ -68,12 +73,13 @@ use crate::any::{Any, AnyKind};
 use crate::connection::Connection;
 use crate::database::Database;
 use crate::error::Error;
-use crate::transaction::Transaction;
+use crate::transaction::{Transaction, TransactionOptions};
 use event_listener::EventListener;
 use futures_core::FusedFuture;
 use futures_util::FutureExt;
 use std::fmt;
 use std::future::Future;
+use std::panic::Location;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
@@ -87,11 +93,17 @@ mod maybe;
 
 mod connection;
 mod inner;
+mod leak;
+mod metrics;
 mod options;
+mod routed;
 
 pub use self::connection::PoolConnection;
+pub use self::leak::CheckedOutConnection;
 pub(crate) use self::maybe::MaybePoolConnection;
+pub use self::metrics::{AcquireWaitHistogram, CloseReason, PoolMetrics, PoolObserver};
 pub use self::options::{PoolConnectionMetadata, PoolOptions};
+pub use self::routed::{ReplicaSelection, RoutedPool, RoutedPoolBuilder, RoutedReader};
 
 /// An asynchronous pool of SQLx database connections.
 ///
@@ -336,22 +348,61 @@ impl<DB: Database> Pool<DB> {
     ///
     /// This should eliminate any potential `.await` points between acquiring a connection and
     /// returning it.
+    #[track_caller]
     pub fn acquire(&self) -> impl Future<Output = Result<PoolConnection<DB>, Error>> + 'static {
+        self.acquire_at(Location::caller())
+    }
+
+    /// Like [`acquire`][Self::acquire], for a connection checked out on behalf of code at
+    /// `location`.
+    pub(crate) fn acquire_at(
+        &self,
+        location: &'static Location<'static>,
+    ) -> impl Future<Output = Result<PoolConnection<DB>, Error>> + 'static {
         let shared = self.0.clone();
-        async move { shared.acquire().await.map(|conn| conn.reattach()) }
+        async move { shared.acquire().await.map(|conn| conn.reattach(location)) }
     }
 
     /// Attempts to retrieve a connection from the pool if there is one available.
     ///
     /// Returns `None` immediately if there are no idle connections available in the pool
     /// or there are tasks waiting for a connection which have yet to wake.
+    #[track_caller]
     pub fn try_acquire(&self) -> Option<PoolConnection<DB>> {
-        self.0.try_acquire().map(|conn| conn.into_live().reattach())
+        self.try_acquire_at(Location::caller())
+    }
+
+    /// Like [`try_acquire`][Self::try_acquire], for a connection checked out on behalf of code
+    /// at `location`.
+    pub(crate) fn try_acquire_at(
+        &self,
+        location: &'static Location<'static>,
+    ) -> Option<PoolConnection<DB>> {
+        let conn = self.0.try_acquire()?;
+        self.0.metrics.record_acquire(Duration::ZERO);
+        Some(conn.into_live().reattach(location))
     }
 
     /// Retrieves a connection and immediately begins a new transaction.
-    pub async fn begin(&self) -> Result<Transaction<'static, DB>, Error> {
-        Transaction::begin(MaybePoolConnection::PoolConnection(self.acquire().await?)).await
+    #[track_caller]
+    pub fn begin(&self) -> impl Future<Output = Result<Transaction<'static, DB>, Error>> + 'static {
+        let acquire = self.acquire();
+        async move { Transaction::begin(MaybePoolConnection::PoolConnection(acquire.await?)).await }
+    }
+
+    /// Retrieves a connection and immediately begins a new transaction with the given options.
+    ///
+    /// See [`Connection::begin_with`] for details.
+    #[track_caller]
+    pub fn begin_with(
+        &self,
+        options: TransactionOptions,
+    ) -> impl Future<Output = Result<Transaction<'static, DB>, Error>> + 'static {
+        let acquire = self.acquire();
+        async move {
+            Transaction::begin_with(MaybePoolConnection::PoolConnection(acquire.await?), options)
+                .await
+        }
     }
 
     /// Attempts to retrieve a connection and immediately begins a new transaction if successful.
@@ -487,6 +538,76 @@ impl<DB: Database> Pool<DB> {
         self.0.num_idle()
     }
 
+    /// Returns the maximum number of connections the pool will open.
+    ///
+    /// This starts out as [`PoolOptions::max_connections`] and can be changed with
+    /// [`Pool::set_max_connections`].
+    pub fn max_connections(&self) -> u32 {
+        self.0.max_connections()
+    }
+
+    /// Change the maximum number of connections of a running pool.
+    ///
+    /// Raising the limit takes effect immediately: tasks waiting in [`Pool::acquire`] are
+    /// woken up and may open new connections.
+    ///
+    /// Lowering the limit never interrupts connections that are checked out. Idle connections
+    /// in excess of the new limit are closed in the background, and checked-out connections are
+    /// closed instead of being returned to the pool until the pool is back within the limit.
+    /// Until then, [`Pool::size`] may temporarily exceed the new limit but no new connections
+    /// are opened.
+    ///
+    /// [`Pool::options`] keeps returning the options the pool was created with;
+    /// use [`Pool::max_connections`] to read the current value.
+    ///
+    /// ### Note: Child Pools
+    /// For pools created with [`PoolOptions::parent`] this only changes the connection limit
+    /// of the child pool as it borrows its capacity from the parent.
+    pub fn set_max_connections(&self, max: u32) {
+        self.0.set_max_connections(max)
+    }
+
+    /// Returns the minimum number of connections the pool tries to maintain.
+    ///
+    /// This starts out as [`PoolOptions::min_connections`] and can be changed with
+    /// [`Pool::set_min_connections`].
+    pub fn min_connections(&self) -> u32 {
+        self.0.min_connections()
+    }
+
+    /// Change the minimum number of connections of a running pool.
+    ///
+    /// When raised, a background task is spawned to open the additional connections on a
+    /// best-effort basis, as described in [`PoolOptions::min_connections`]. When lowered,
+    /// surplus idle connections are left to be closed by the [`idle_timeout`] reaper.
+    ///
+    /// [`idle_timeout`]: PoolOptions::idle_timeout
+    pub fn set_min_connections(&self, min: u32) {
+        self.0.set_min_connections(min)
+    }
+
+    /// Returns a snapshot of the pool's current state and cumulative statistics.
+    ///
+    /// This includes the number of connections in use, the distribution of time spent waiting
+    /// in [`Pool::acquire`], and the number of connections opened and closed by reason.
+    ///
+    /// To be notified of events as they happen instead, see [`PoolOptions::observer`].
+    pub fn metrics(&self) -> PoolMetrics {
+        self.0.metrics()
+    }
+
+    /// Lists the connections currently checked out of the pool, the longest checked out first.
+    ///
+    /// Each entry has the location of the call that acquired the connection and how long ago
+    /// that was, which helps finding tasks that hold on to connections for too long.
+    ///
+    /// Connections are only tracked when [`PoolOptions::leak_detection_threshold`] is set,
+    /// otherwise this is always empty; the number of checked-out connections is still available
+    /// as [`PoolMetrics::in_use`].
+    pub fn checked_out_connections(&self) -> Vec<CheckedOutConnection> {
+        self.0.checkouts.list()
+    }
+
     /// Get the connection options for this pool
     pub fn connect_options(&self) -> &<DB::Connection as Connection>::Options {
         &self.0.connect_options
@@ -528,6 +649,7 @@ impl<DB: Database> fmt::Debug for Pool<DB> {
         fmt.debug_struct("Pool")
             .field("size", &self.0.size())
             .field("num_idle", &self.0.num_idle())
+            .field("max_connections", &self.0.max_connections())
             .field("is_closed", &self.0.is_closed())
             .field("options", &self.0.options)
             .finish()
@@ -2,7 +2,7 @@ use crate::connection::Connection;
 use crate::database::Database;
 use crate::error::Error;
 use crate::pool::inner::PoolInner;
-use crate::pool::Pool;
+use crate::pool::{CheckedOutConnection, Pool, PoolObserver};
 use futures_core::future::BoxFuture;
 use std::fmt::{self, Debug, Formatter};
 use std::sync::Arc;
@@ -44,6 +44,7 @@ use std::time::{Duration, Instant};
 #[derive(Clone)]
 pub struct PoolOptions<DB: Database> {
     pub(crate) test_before_acquire: bool,
+    pub(crate) reset_on_release: bool,
     pub(crate) after_connect: Option<
         Arc<
             dyn Fn(&mut DB::Connection, PoolConnectionMetadata) -> BoxFuture<'_, Result<(), Error>>
@@ -80,6 +81,9 @@ pub struct PoolOptions<DB: Database> {
     pub(crate) max_lifetime: Option<Duration>,
     pub(crate) idle_timeout: Option<Duration>,
     pub(crate) fair: bool,
+    pub(crate) observer: Option<Arc<dyn PoolObserver>>,
+    pub(crate) leak_detection_threshold: Option<Duration>,
+    pub(crate) on_leak: Option<Arc<dyn Fn(&CheckedOutConnection) + 'static + Send + Sync>>,
 
     pub(crate) parent_pool: Option<Pool<DB>>,
 }
@@ -120,6 +124,7 @@ impl<DB: Database> PoolOptions<DB> {
             before_acquire: None,
             after_release: None,
             test_before_acquire: true,
+            reset_on_release: false,
             // A production application will want to set a higher limit than this.
             max_connections: 10,
             min_connections: 0,
@@ -127,6 +132,9 @@ impl<DB: Database> PoolOptions<DB> {
             idle_timeout: Some(Duration::from_secs(10 * 60)),
             max_lifetime: Some(Duration::from_secs(30 * 60)),
             fair: true,
+            observer: None,
+            leak_detection_threshold: None,
+            on_leak: None,
             parent_pool: None,
         }
     }
@@ -136,6 +144,8 @@ impl<DB: Database> PoolOptions<DB> {
     /// Be mindful of the connection limits for your database as well as other applications
     /// which may want to connect to the same database (or even multiple instances of the same
     /// application in high-availability deployments).
+    ///
+    /// This can be changed later on a running pool with [`Pool::set_max_connections()`].
     pub fn max_connections(mut self, max: u32) -> Self {
         self.max_connections = max;
         self
@@ -160,6 +170,8 @@ impl<DB: Database> PoolOptions<DB> {
     /// then it should be checking this condition itself and returning
     /// a nicer error than a panic anyway.
     ///
+    /// This can be changed later on a running pool with [`Pool::set_min_connections()`].
+    ///
     /// [`max_lifetime`]: Self::max_lifetime
     /// [`idle_timeout`]: Self::idle_timeout
     /// [`max_connections`]: Self::max_connections
@@ -224,6 +236,27 @@ impl<DB: Database> PoolOptions<DB> {
         self
     }
 
+    /// If true, the session state of a connection is reset with [`Connection::reset_session`]
+    /// when it is returned to the pool.
+    ///
+    /// This ensures that session variables, temporary tables, open cursors and prepared
+    /// statements left behind by one user of the pool are not visible to the next one.
+    /// The reset replaces the liveness check normally done on release, but still costs a
+    /// round-trip to the database every time a connection is returned.
+    ///
+    /// The reset also undoes anything set up by [`after_connect`][Self::after_connect]: on
+    /// Postgres, for example, `DISCARD ALL` resets every `SET` it made to the default value.
+    /// Use [`before_acquire`][Self::before_acquire] for session state that must be present
+    /// every time a connection is handed out.
+    ///
+    /// If the reset fails, the connection is closed.
+    ///
+    /// Defaults to `false`.
+    pub fn reset_on_release(mut self, reset: bool) -> Self {
+        self.reset_on_release = reset;
+        self
+    }
+
     /// If set to `true`, calls to `acquire()` are fair and connections  are issued
     /// in first-come-first-serve order. If `false`, "drive-by" tasks may steal idle connections
     /// ahead of tasks that have been waiting.
@@ -403,6 +436,92 @@ impl<DB: Database> PoolOptions<DB> {
         self
     }
 
+    /// Set an observer which is notified of events in the pool as they happen.
+    ///
+    /// This can be used to export pool statistics to a metrics system. The same counters are also
+    /// available as a point-in-time snapshot from [`Pool::metrics()`], which does not require
+    /// an observer.
+    ///
+    /// See [`PoolObserver`] for the list of events.
+    ///
+    /// ```no_run
+    /// # async fn f() -> Result<(), Box<dyn std::error::Error>> {
+    /// use std::time::Duration;
+    /// use sqlx::pool::{CloseReason, PoolObserver};
+    /// use sqlx::postgres::PgPoolOptions;
+    ///
+    /// struct LogObserver;
+    ///
+    /// impl PoolObserver for LogObserver {
+    ///     fn on_acquire(&self, wait: Duration) {
+    ///         if wait > Duration::from_secs(1) {
+    ///             eprintln!("waited {:?} for a connection", wait);
+    ///         }
+    ///     }
+    ///
+    ///     fn on_close(&self, reason: CloseReason) {
+    ///         eprintln!("connection closed: {:?}", reason);
+    ///     }
+    /// }
+    ///
+    /// let pool = PgPoolOptions::new()
+    ///     .observer(LogObserver)
+    ///     .connect("postgres:// …").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn observer(mut self, observer: impl PoolObserver) -> Self {
+        self.observer = Some(Arc::new(observer));
+        self
+    }
+
+    /// Report connections that stay checked out of the pool for longer than `threshold`.
+    ///
+    /// A background task periodically checks for such connections and logs a warning with the
+    /// location of the call that acquired each of them, or calls [`on_leak`][Self::on_leak]
+    /// if it is set. Each connection is reported at most once.
+    ///
+    /// When this is set, every connection acquired is recorded along with a backtrace if
+    /// enabled with the `RUST_BACKTRACE` or `RUST_LIB_BACKTRACE` environment variables,
+    /// which can be expensive. The recorded connections can also be listed at any time with
+    /// [`Pool::checked_out_connections()`].
+    ///
+    /// Defaults to `None` (disabled).
+    pub fn leak_detection_threshold(mut self, threshold: impl Into<Option<Duration>>) -> Self {
+        self.leak_detection_threshold = threshold.into();
+        self
+    }
+
+    /// Call `callback` instead of logging a warning when a connection has been checked out
+    /// for longer than the [`leak_detection_threshold`][Self::leak_detection_threshold].
+    ///
+    /// The callback is invoked from the pool's background task, so it should not block.
+    ///
+    /// ```no_run
+    /// # async fn f() -> Result<(), Box<dyn std::error::Error>> {
+    /// use std::time::Duration;
+    /// use sqlx::postgres::PgPoolOptions;
+    ///
+    /// let pool = PgPoolOptions::new()
+    ///     .leak_detection_threshold(Duration::from_secs(60))
+    ///     .on_leak(|conn| {
+    ///         eprintln!(
+    ///             "connection acquired at {} checked out for {:?}",
+    ///             conn.location, conn.checked_out_for
+    ///         );
+    ///     })
+    ///     .connect("postgres:// …").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn on_leak<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&CheckedOutConnection) + 'static + Send + Sync,
+    {
+        self.on_leak = Some(Arc::new(callback));
+        self
+    }
+
     /// Set the parent `Pool` from which the new pool will inherit its semaphore.
     ///
     /// This is currently an internal-only API.
@@ -494,6 +613,9 @@ impl<DB: Database> Debug for PoolOptions<DB> {
             .field("max_lifetime", &self.max_lifetime)
             .field("idle_timeout", &self.idle_timeout)
             .field("test_before_acquire", &self.test_before_acquire)
+            .field("reset_on_release", &self.reset_on_release)
+            .field("has_observer", &self.observer.is_some())
+            .field("leak_detection_threshold", &self.leak_detection_threshold)
             .finish()
     }
 }
@@ -0,0 +1,631 @@
+use crate::acquire::Acquire;
+use crate::database::{Database, HasStatement};
+use crate::describe::Describe;
+use crate::error::Error;
+use crate::executor::{Execute, Executor};
+use crate::pool::{MaybePoolConnection, Pool, PoolConnection};
+use crate::transaction::{Transaction, TransactionOptions};
+use either::Either;
+use futures_core::future::BoxFuture;
+use futures_core::stream::BoxStream;
+use futures_util::TryStreamExt;
+use std::cmp::{self, Ordering as CmpOrdering};
+use std::fmt;
+use std::future::Future;
+use std::panic::Location;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Weak};
+use std::time::{Duration, Instant};
+
+/// A pool that routes queries between a primary database and its read replicas.
+///
+/// `RoutedPool` wraps one writer [`Pool`] and any number of reader pools. Using `&RoutedPool`
+/// directly as an [`Executor`], or calling [`acquire`][Self::acquire] or [`begin`][Self::begin],
+/// always goes to the writer, so code that isn't aware of replicas keeps working unchanged.
+/// Read-only queries can explicitly opt in to using a replica through [`reader`][Self::reader].
+///
+/// ```rust,no_run
+/// # async fn f() -> Result<(), Box<dyn std::error::Error>> {
+/// use sqlx::pool::{ReplicaSelection, RoutedPool};
+/// use sqlx::postgres::PgPool;
+///
+/// let pool = RoutedPool::builder(PgPool::connect("postgres://primary/app").await?)
+///     .replica(PgPool::connect_lazy("postgres://replica-1/app")?)
+///     .weighted_replica(PgPool::connect_lazy("postgres://replica-2/app")?, 2)
+///     .selection(ReplicaSelection::LeastBusy)
+///     .build();
+///
+/// // Goes to the primary.
+/// sqlx::query("UPDATE users SET last_seen = now() WHERE id = $1")
+///     .bind(1_i64)
+///     .execute(&pool)
+///     .await?;
+///
+/// // Goes to one of the replicas, or to the primary if none of them is available.
+/// let count: i64 = sqlx::query_scalar("SELECT count(*) FROM users")
+///     .fetch_one(pool.reader())
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// ### Replica Health
+/// When a replica cannot be connected to, because opening a connection fails with an I/O, TLS
+/// or protocol error or keeps failing until the acquire times out, the replica is taken out of
+/// rotation and the next one is tried. A background task then periodically tries to acquire a
+/// connection from it (see [`RoutedPoolBuilder::retry_interval`]) and puts it back into rotation
+/// once that succeeds. A replica that merely has all of its connections in use stays in rotation,
+/// but replicas with a connection to spare are tried first. If no replica is available, reads go
+/// to the writer.
+///
+/// All the replicas tried for a read, and the writer it may fall back to, share a single
+/// [`acquire_timeout`][crate::pool::PoolOptions::acquire_timeout] of the writer pool, so a read
+/// doesn't wait longer than acquiring a connection from one pool would.
+///
+/// Like [`Pool`], `RoutedPool` is cheap to clone.
+pub struct RoutedPool<DB: Database>(Arc<RoutedPoolInner<DB>>);
+
+struct RoutedPoolInner<DB: Database> {
+    writer: Pool<DB>,
+    replicas: Vec<Replica<DB>>,
+    selection: ReplicaSelection,
+    retry_interval: Duration,
+    next: AtomicUsize,
+}
+
+struct Replica<DB: Database> {
+    pool: Pool<DB>,
+    weight: usize,
+    healthy: AtomicBool,
+}
+
+/// How [`RoutedPool`] picks the replica that serves a read.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum ReplicaSelection {
+    /// Rotate through the replicas, picking each one in proportion to its weight.
+    #[default]
+    Weighted,
+
+    /// Pick the replica with the smallest share of its connections currently in use,
+    /// preferring the one with the higher weight on a tie.
+    LeastBusy,
+}
+
+/// Builder for a [`RoutedPool`], returned by [`RoutedPool::builder`].
+pub struct RoutedPoolBuilder<DB: Database> {
+    writer: Pool<DB>,
+    replicas: Vec<(Pool<DB>, usize)>,
+    selection: ReplicaSelection,
+    retry_interval: Duration,
+}
+
+/// A handle that sends queries to a replica of a [`RoutedPool`].
+///
+/// Returned by [`RoutedPool::reader`]. Each query, [`acquire`][Self::acquire] or
+/// [`begin`][Self::begin] call picks a replica separately.
+pub struct RoutedReader<DB: Database>(RoutedPool<DB>);
+
+impl<DB: Database> RoutedPool<DB> {
+    /// Start building a routed pool that sends writes to `writer`.
+    pub fn builder(writer: Pool<DB>) -> RoutedPoolBuilder<DB> {
+        RoutedPoolBuilder {
+            writer,
+            replicas: Vec::new(),
+            selection: ReplicaSelection::default(),
+            retry_interval: Duration::from_secs(5),
+        }
+    }
+
+    /// Create a routed pool with equally weighted replicas and the default settings.
+    pub fn new(writer: Pool<DB>, replicas: impl IntoIterator<Item = Pool<DB>>) -> Self {
+        replicas
+            .into_iter()
+            .fold(Self::builder(writer), RoutedPoolBuilder::replica)
+            .build()
+    }
+
+    /// The pool for the primary database.
+    pub fn writer(&self) -> &Pool<DB> {
+        &self.0.writer
+    }
+
+    /// A handle that executes queries on a replica.
+    ///
+    /// Falls back to the writer when there are no replicas or none of them is available.
+    pub fn reader(&self) -> RoutedReader<DB> {
+        RoutedReader(self.clone())
+    }
+
+    /// Iterate over the pools of all replicas, including the ones out of rotation.
+    pub fn replicas(&self) -> impl Iterator<Item = &Pool<DB>> + '_ {
+        self.0.replicas.iter().map(|replica| &replica.pool)
+    }
+
+    /// Returns the number of replicas currently in rotation.
+    pub fn num_healthy_replicas(&self) -> usize {
+        self.0.replicas.iter().filter(|r| r.is_healthy()).count()
+    }
+
+    /// Retrieves a connection from the writer pool.
+    #[track_caller]
+    pub fn acquire(&self) -> impl Future<Output = Result<PoolConnection<DB>, Error>> + 'static {
+        self.0.writer.acquire()
+    }
+
+    /// Retrieves a connection from the writer pool and immediately begins a new transaction.
+    #[track_caller]
+    pub fn begin(&self) -> impl Future<Output = Result<Transaction<'static, DB>, Error>> + 'static {
+        self.0.writer.begin()
+    }
+
+    /// Retrieves a connection from the writer pool and immediately begins a new transaction
+    /// with the given options.
+    #[track_caller]
+    pub fn begin_with(
+        &self,
+        options: TransactionOptions,
+    ) -> impl Future<Output = Result<Transaction<'static, DB>, Error>> + 'static {
+        self.0.writer.begin_with(options)
+    }
+
+    /// Shut down the writer pool and all replica pools.
+    ///
+    /// See [`Pool::close`] for details.
+    pub async fn close(&self) {
+        futures_util::future::join_all(
+            std::iter::once(&self.0.writer)
+                .chain(self.replicas())
+                .map(Pool::close),
+        )
+        .await;
+    }
+
+    /// Returns `true` if [`.close()`][Self::close] has been called on the pool.
+    pub fn is_closed(&self) -> bool {
+        self.0.writer.is_closed()
+    }
+
+    async fn acquire_reader(
+        &self,
+        location: &'static Location<'static>,
+    ) -> Result<PoolConnection<DB>, Error> {
+        let deadline = Instant::now() + self.0.writer.options().acquire_timeout;
+        let mut tried = vec![false; self.0.replicas.len()];
+
+        while let Some(index) = self.0.select(&tried) {
+            tried[index] = true;
+
+            let replica = &self.0.replicas[index];
+            let connect_failures = replica.pool.metrics().connect_failures;
+
+            match acquire_before(&replica.pool, deadline, location).await {
+                Ok(conn) => return Ok(conn),
+                // The replica was explicitly closed, don't try to recover it.
+                Err(Error::PoolClosed) => {}
+                // Timing out while connections couldn't be opened means the replica is down,
+                // otherwise it's only busy.
+                Err(e)
+                    if is_unavailable(&e)
+                        || replica.pool.metrics().connect_failures > connect_failures =>
+                {
+                    log::warn!(
+                        "taking read replica {} out of rotation after error: {}",
+                        index,
+                        e
+                    );
+                    self.mark_unhealthy(index);
+                }
+                Err(Error::PoolTimedOut) => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        acquire_before(&self.0.writer, deadline, location).await
+    }
+
+    fn mark_unhealthy(&self, index: usize) {
+        let replica = &self.0.replicas[index];
+
+        // Only spawn one recovery task per outage.
+        if !replica.healthy.swap(false, Ordering::AcqRel) {
+            return;
+        }
+
+        let Ok(handle) = sqlx_rt::Handle::try_current() else {
+            // Without a runtime to recover it, put it back so it's tried again next time.
+            replica.healthy.store(true, Ordering::Release);
+            return;
+        };
+
+        let inner = Arc::downgrade(&self.0);
+        let retry_interval = self.0.retry_interval;
+
+        handle.spawn(recover_replica(inner, index, retry_interval));
+    }
+}
+
+/// Periodically try to connect to an unhealthy replica until it succeeds,
+/// the replica is closed or the `RoutedPool` is dropped.
+async fn recover_replica<DB: Database>(
+    inner: Weak<RoutedPoolInner<DB>>,
+    index: usize,
+    retry_interval: Duration,
+) {
+    loop {
+        sqlx_rt::sleep(retry_interval).await;
+
+        let Some(inner) = inner.upgrade() else {
+            return;
+        };
+
+        let replica = &inner.replicas[index];
+
+        match replica.pool.acquire().await {
+            Ok(_) => {
+                log::info!("read replica {} is back in rotation", index);
+                replica.healthy.store(true, Ordering::Release);
+                return;
+            }
+            Err(Error::PoolClosed) => return,
+            Err(e) => log::debug!("read replica {} is still unavailable: {}", index, e),
+        }
+    }
+}
+
+/// Acquire a connection from `pool`, giving up with [`Error::PoolTimedOut`] at `deadline`.
+async fn acquire_before<DB: Database>(
+    pool: &Pool<DB>,
+    deadline: Instant,
+    location: &'static Location<'static>,
+) -> Result<PoolConnection<DB>, Error> {
+    match deadline.checked_duration_since(Instant::now()) {
+        Some(timeout) => sqlx_rt::timeout(timeout, pool.acquire_at(location))
+            .await
+            .unwrap_or(Err(Error::PoolTimedOut)),
+        // Still take an idle connection if there is one.
+        None => pool.try_acquire_at(location).ok_or(Error::PoolTimedOut),
+    }
+}
+
+/// Returns `true` if the error means a connection to the replica could not be established.
+fn is_unavailable(error: &Error) -> bool {
+    matches!(error, Error::Io(_) | Error::Tls(_) | Error::Protocol(_))
+}
+
+impl<DB: Database> RoutedPoolInner<DB> {
+    /// Pick the index of a healthy replica that wasn't tried yet, if any,
+    /// preferring the ones with a connection to spare.
+    fn select(&self, tried: &[bool]) -> Option<usize> {
+        let skip_busy = self
+            .replicas
+            .iter()
+            .enumerate()
+            .any(|(i, replica)| !tried[i] && replica.is_healthy() && !replica.is_busy());
+
+        let candidates = || {
+            self.replicas.iter().enumerate().filter(|(i, replica)| {
+                !tried[*i] && replica.is_healthy() && !(skip_busy && replica.is_busy())
+            })
+        };
+
+        match self.selection {
+            ReplicaSelection::Weighted => {
+                let total_weight: usize = candidates().map(|(_, r)| r.weight).sum();
+
+                if total_weight == 0 {
+                    return None;
+                }
+
+                let mut point = self.next.fetch_add(1, Ordering::Relaxed) % total_weight;
+
+                candidates()
+                    .find(|(_, replica)| {
+                        if point < replica.weight {
+                            return true;
+                        }
+
+                        point -= replica.weight;
+                        false
+                    })
+                    .map(|(i, _)| i)
+            }
+            ReplicaSelection::LeastBusy => candidates()
+                .min_by(|(_, a), (_, b)| a.compare_load(b))
+                .map(|(i, _)| i),
+        }
+    }
+}
+
+impl<DB: Database> Replica<DB> {
+    fn is_healthy(&self) -> bool {
+        self.healthy.load(Ordering::Acquire)
+    }
+
+    /// Returns `true` if acquiring a connection would have to wait for one to be released.
+    fn is_busy(&self) -> bool {
+        self.pool.num_idle() == 0 && self.pool.size() >= self.pool.max_connections()
+    }
+
+    /// Compare the share of connections in use, then the weight in reverse.
+    fn compare_load(&self, other: &Self) -> CmpOrdering {
+        let (a, b) = (self.pool.metrics(), other.pool.metrics());
+
+        // Compare `a.in_use / a.max` to `b.in_use / b.max` without dividing.
+        let a_load = u64::from(a.in_use) * u64::from(cmp::max(other.pool.max_connections(), 1));
+        let b_load = u64::from(b.in_use) * u64::from(cmp::max(self.pool.max_connections(), 1));
+
+        a_load
+            .cmp(&b_load)
+            .then_with(|| other.weight.cmp(&self.weight))
+    }
+}
+
+impl<DB: Database> RoutedPoolBuilder<DB> {
+    /// Add a replica with a weight of 1.
+    pub fn replica(self, pool: Pool<DB>) -> Self {
+        self.weighted_replica(pool, 1)
+    }
+
+    /// Add a replica with the given weight.
+    ///
+    /// With [`ReplicaSelection::Weighted`], a replica with a weight of 2 serves twice as many
+    /// reads as a replica with a weight of 1. A replica with a weight of 0 is never used.
+    pub fn weighted_replica(mut self, pool: Pool<DB>, weight: usize) -> Self {
+        self.replicas.push((pool, weight));
+        self
+    }
+
+    /// Set how the replica serving each read is chosen.
+    ///
+    /// Defaults to [`ReplicaSelection::Weighted`].
+    pub fn selection(mut self, selection: ReplicaSelection) -> Self {
+        self.selection = selection;
+        self
+    }
+
+    /// Set how often a replica taken out of rotation is checked to see if it's available again.
+    ///
+    /// Defaults to 5 seconds.
+    pub fn retry_interval(mut self, interval: Duration) -> Self {
+        self.retry_interval = interval;
+        self
+    }
+
+    /// Create the [`RoutedPool`].
+    pub fn build(self) -> RoutedPool<DB> {
+        RoutedPool(Arc::new(RoutedPoolInner {
+            writer: self.writer,
+            replicas: self
+                .replicas
+                .into_iter()
+                .map(|(pool, weight)| Replica {
+                    pool,
+                    weight,
+                    healthy: AtomicBool::new(true),
+                })
+                .collect(),
+            selection: self.selection,
+            retry_interval: self.retry_interval,
+            next: AtomicUsize::new(0),
+        }))
+    }
+}
+
+impl<DB: Database> RoutedReader<DB> {
+    /// Retrieves a connection from a replica, or from the writer if none is available.
+    #[track_caller]
+    pub fn acquire(&self) -> impl Future<Output = Result<PoolConnection<DB>, Error>> + 'static {
+        let location = Location::caller();
+        let pool = self.0.clone();
+        async move { pool.acquire_reader(location).await }
+    }
+
+    /// Retrieves a connection from a replica, or from the writer if none is available,
+    /// and immediately begins a new transaction.
+    #[track_caller]
+    pub fn begin(&self) -> impl Future<Output = Result<Transaction<'static, DB>, Error>> + 'static {
+        let acquire = self.acquire();
+        async move { Transaction::begin(MaybePoolConnection::PoolConnection(acquire.await?)).await }
+    }
+
+    /// Retrieves a connection from a replica, or from the writer if none is available,
+    /// and immediately begins a new transaction with the given options.
+    #[track_caller]
+    pub fn begin_with(
+        &self,
+        options: TransactionOptions,
+    ) -> impl Future<Output = Result<Transaction<'static, DB>, Error>> + 'static {
+        let acquire = self.acquire();
+        async move {
+            Transaction::begin_with(MaybePoolConnection::PoolConnection(acquire.await?), options)
+                .await
+        }
+    }
+}
+
+/// Returns a new [RoutedPool] tied to the same writer and replica pools.
+impl<DB: Database> Clone for RoutedPool<DB> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<DB: Database> Clone for RoutedReader<DB> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<DB: Database> fmt::Debug for RoutedPool<DB> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RoutedPool")
+            .field("writer", &self.0.writer)
+            .field("replicas", &self.0.replicas)
+            .field("selection", &self.0.selection)
+            .field("retry_interval", &self.0.retry_interval)
+            .finish()
+    }
+}
+
+impl<DB: Database> fmt::Debug for Replica<DB> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Replica")
+            .field("pool", &self.pool)
+            .field("weight", &self.weight)
+            .field("healthy", &self.is_healthy())
+            .finish()
+    }
+}
+
+impl<DB: Database> fmt::Debug for RoutedReader<DB> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RoutedReader").field(&self.0).finish()
+    }
+}
+
+impl<DB: Database> fmt::Debug for RoutedPoolBuilder<DB> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RoutedPoolBuilder")
+            .field("writer", &self.writer)
+            .field("replicas", &self.replicas)
+            .field("selection", &self.selection)
+            .field("retry_interval", &self.retry_interval)
+            .finish()
+    }
+}
+
+impl<'p, DB: Database> Executor<'p> for &'_ RoutedPool<DB>
+where
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+{
+    type Database = DB;
+
+    fn fetch_many<'e, 'q: 'e, E>(
+        self,
+        query: E,
+    ) -> BoxStream<'e, Result<Either<DB::QueryResult, DB::Row>, Error>>
+    where
+        E: Execute<'q, Self::Database> + 'q,
+    {
+        self.writer().fetch_many(query)
+    }
+
+    fn fetch_optional<'e, 'q: 'e, E>(
+        self,
+        query: E,
+    ) -> BoxFuture<'e, Result<Option<DB::Row>, Error>>
+    where
+        E: Execute<'q, Self::Database> + 'q,
+    {
+        self.writer().fetch_optional(query)
+    }
+
+    fn prepare_with<'e, 'q: 'e>(
+        self,
+        sql: &'q str,
+        parameters: &'e [<Self::Database as Database>::TypeInfo],
+    ) -> BoxFuture<'e, Result<<Self::Database as HasStatement<'q>>::Statement, Error>> {
+        self.writer().prepare_with(sql, parameters)
+    }
+
+    #[doc(hidden)]
+    fn describe<'e, 'q: 'e>(
+        self,
+        sql: &'q str,
+    ) -> BoxFuture<'e, Result<Describe<Self::Database>, Error>> {
+        self.writer().describe(sql)
+    }
+}
+
+impl<'p, DB: Database> Executor<'p> for RoutedReader<DB>
+where
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+{
+    type Database = DB;
+
+    fn fetch_many<'e, 'q: 'e, E>(
+        self,
+        query: E,
+    ) -> BoxStream<'e, Result<Either<DB::QueryResult, DB::Row>, Error>>
+    where
+        E: Execute<'q, Self::Database> + 'q,
+    {
+        Box::pin(try_stream! {
+            let mut conn = self.acquire().await?;
+            let mut s = conn.fetch_many(query);
+
+            while let Some(v) = s.try_next().await? {
+                r#yield!(v);
+            }
+
+            Ok(())
+        })
+    }
+
+    fn fetch_optional<'e, 'q: 'e, E>(
+        self,
+        query: E,
+    ) -> BoxFuture<'e, Result<Option<DB::Row>, Error>>
+    where
+        E: Execute<'q, Self::Database> + 'q,
+    {
+        Box::pin(async move { self.acquire().await?.fetch_optional(query).await })
+    }
+
+    fn prepare_with<'e, 'q: 'e>(
+        self,
+        sql: &'q str,
+        parameters: &'e [<Self::Database as Database>::TypeInfo],
+    ) -> BoxFuture<'e, Result<<Self::Database as HasStatement<'q>>::Statement, Error>> {
+        Box::pin(async move { self.acquire().await?.prepare_with(sql, parameters).await })
+    }
+
+    #[doc(hidden)]
+    fn describe<'e, 'q: 'e>(
+        self,
+        sql: &'q str,
+    ) -> BoxFuture<'e, Result<Describe<Self::Database>, Error>> {
+        Box::pin(async move { self.acquire().await?.describe(sql).await })
+    }
+}
+
+impl<'a, DB: Database> Acquire<'a> for &'_ RoutedPool<DB> {
+    type Database = DB;
+
+    type Connection = PoolConnection<DB>;
+
+    #[track_caller]
+    fn acquire(self) -> BoxFuture<'static, Result<Self::Connection, Error>> {
+        Box::pin(self.acquire())
+    }
+
+    #[track_caller]
+    fn begin(self) -> BoxFuture<'static, Result<Transaction<'a, DB>, Error>> {
+        let conn = self.acquire();
+
+        Box::pin(async move {
+            Transaction::begin(MaybePoolConnection::PoolConnection(conn.await?)).await
+        })
+    }
+}
+
+impl<'a, DB: Database> Acquire<'a> for RoutedReader<DB> {
+    type Database = DB;
+
+    type Connection = PoolConnection<DB>;
+
+    #[track_caller]
+    fn acquire(self) -> BoxFuture<'static, Result<Self::Connection, Error>> {
+        Box::pin(RoutedReader::acquire(&self))
+    }
+
+    #[track_caller]
+    fn begin(self) -> BoxFuture<'static, Result<Transaction<'a, DB>, Error>> {
+        let conn = RoutedReader::acquire(&self);
+
+        Box::pin(async move {
+            Transaction::begin(MaybePoolConnection::PoolConnection(conn.await?)).await
+        })
+    }
+}
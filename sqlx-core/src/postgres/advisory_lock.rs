@@ -1,10 +1,13 @@
-use crate::error::Result;
-use crate::postgres::PgConnection;
+use crate::connection::Connection;
+use crate::error::{Error, ErrorKind, Result};
+use crate::postgres::{PgConnection, Postgres};
+use crate::transaction::Transaction;
 use crate::Either;
 use hkdf::Hkdf;
 use once_cell::sync::OnceCell;
 use sha2::Sha256;
 use std::ops::{Deref, DerefMut};
+use std::time::Duration;
 
 /// A mutex-like type utilizing [Postgres advisory locks].
 ///
@@ -16,13 +19,13 @@ use std::ops::{Deref, DerefMut};
 /// advisory locks use, as well as RAII guards for releasing advisory locks when they fall out
 /// of scope.
 ///
-/// This API only handles session-scoped advisory locks (explicitly locked and unlocked, or
-/// automatically released when a connection is closed).
+/// Session-scoped advisory locks are explicitly locked and unlocked, or automatically released
+/// when a connection is closed. They are taken with [`Self::acquire()`] and similar methods,
+/// which return a guard that releases the lock.
 ///
-/// It is also possible to use transaction-scoped locks but those can be used by beginning a
-/// transaction and calling the appropriate lock functions (e.g. `SELECT pg_advisory_xact_lock()`)
-/// manually, and cannot be explicitly released, but are automatically released when a transaction
-/// ends (is committed or rolled back).
+/// Transaction-scoped advisory locks are taken with [`Self::acquire_xact()`] and similar methods
+/// on a [`Transaction`], and cannot be explicitly released, but are automatically released when
+/// the transaction ends (is committed or rolled back).
 ///
 /// Session-level locks can be acquired either inside or outside a transaction and are not
 /// tied to transaction semantics; a lock acquired inside a transaction is still held when that
@@ -38,6 +41,8 @@ pub struct PgAdvisoryLock {
     key: PgAdvisoryLockKey,
     /// The query to execute to release this lock.
     release_query: OnceCell<String>,
+    /// The query to execute to release this lock when held in shared mode.
+    release_shared_query: OnceCell<String>,
 }
 
 /// A key type natively used by Postgres advisory locks.
@@ -66,8 +71,8 @@ pub enum PgAdvisoryLockKey {
 
 /// A wrapper for `PgConnection` (or a similar type) that represents a held Postgres advisory lock.
 ///
-/// Can be acquired by [`PgAdvisoryLock::acquire()`] or [`PgAdvisoryLock::try_acquire()`].
-/// Released on-drop or via [`Self::release_now()`].
+/// Can be acquired by [`PgAdvisoryLock::acquire()`] or [`PgAdvisoryLock::try_acquire()`], or their
+/// shared and timeout variants. Released on-drop or via [`Self::release_now()`].
 ///
 /// ### Note: Release-on-drop is not immediate!
 /// On drop, this guard queues a `pg_advisory_unlock()` call on the connection which will be
@@ -80,6 +85,7 @@ pub enum PgAdvisoryLockKey {
 pub struct PgAdvisoryLockGuard<'lock, C: AsMut<PgConnection>> {
     lock: &'lock PgAdvisoryLock,
     conn: Option<C>,
+    shared: bool,
 }
 
 impl PgAdvisoryLock {
@@ -165,6 +171,7 @@ impl PgAdvisoryLock {
         Self {
             key,
             release_query: OnceCell::new(),
+            release_shared_query: OnceCell::new(),
         }
     }
 
@@ -203,23 +210,27 @@ impl PgAdvisoryLock {
         &self,
         mut conn: C,
     ) -> Result<PgAdvisoryLockGuard<'_, C>> {
-        match &self.key {
-            PgAdvisoryLockKey::BigInt(key) => {
-                crate::query::query("SELECT pg_advisory_lock($1)")
-                    .bind(key)
-                    .execute(conn.as_mut())
-                    .await?;
-            }
-            PgAdvisoryLockKey::IntPair(key1, key2) => {
-                crate::query::query("SELECT pg_advisory_lock($1, $2)")
-                    .bind(key1)
-                    .bind(key2)
-                    .execute(conn.as_mut())
-                    .await?;
-            }
-        }
+        self.call("pg_advisory_lock", conn.as_mut()).await?;
+
+        Ok(PgAdvisoryLockGuard::new(self, conn, false))
+    }
+
+    /// Acquires a shared lock using `pg_advisory_lock_shared()`, waiting until the lock is
+    /// acquired.
+    ///
+    /// Any number of connections can hold a shared lock at the same time, but a shared lock
+    /// cannot be acquired while an exclusive lock is held for the same key, and the other way
+    /// around.
+    ///
+    /// The returned guard queues a `pg_advisory_unlock_shared()` call on the connection when
+    /// dropped. Otherwise, this behaves like [`Self::acquire()`].
+    pub async fn acquire_shared<C: AsMut<PgConnection>>(
+        &self,
+        mut conn: C,
+    ) -> Result<PgAdvisoryLockGuard<'_, C>> {
+        self.call("pg_advisory_lock_shared", conn.as_mut()).await?;
 
-        Ok(PgAdvisoryLockGuard::new(self, conn))
+        Ok(PgAdvisoryLockGuard::new(self, conn, true))
     }
 
     /// Acquires an exclusive lock using `pg_try_advisory_lock()`, returning immediately
@@ -246,29 +257,146 @@ impl PgAdvisoryLock {
         &self,
         mut conn: C,
     ) -> Result<Either<PgAdvisoryLockGuard<'_, C>, C>> {
-        let locked: bool = match &self.key {
-            PgAdvisoryLockKey::BigInt(key) => {
-                crate::query_scalar::query_scalar("SELECT pg_try_advisory_lock($1)")
-                    .bind(key)
-                    .fetch_one(conn.as_mut())
-                    .await?
-            }
-            PgAdvisoryLockKey::IntPair(key1, key2) => {
-                crate::query_scalar::query_scalar("SELECT pg_try_advisory_lock($1, $2)")
-                    .bind(key1)
-                    .bind(key2)
-                    .fetch_one(conn.as_mut())
-                    .await?
-            }
-        };
+        if self
+            .call_bool("pg_try_advisory_lock", conn.as_mut())
+            .await?
+        {
+            Ok(Either::Left(PgAdvisoryLockGuard::new(self, conn, false)))
+        } else {
+            Ok(Either::Right(conn))
+        }
+    }
+
+    /// Acquires a shared lock using `pg_try_advisory_lock_shared()`, returning immediately
+    /// if the lock could not be acquired.
+    ///
+    /// Otherwise, this behaves like [`Self::acquire_shared()`].
+    pub async fn try_acquire_shared<C: AsMut<PgConnection>>(
+        &self,
+        mut conn: C,
+    ) -> Result<Either<PgAdvisoryLockGuard<'_, C>, C>> {
+        if self
+            .call_bool("pg_try_advisory_lock_shared", conn.as_mut())
+            .await?
+        {
+            Ok(Either::Left(PgAdvisoryLockGuard::new(self, conn, true)))
+        } else {
+            Ok(Either::Right(conn))
+        }
+    }
+
+    /// Acquires an exclusive lock using `pg_advisory_lock()`, waiting at most `timeout` for the
+    /// lock to be acquired, and returning the connection if it could not be.
+    ///
+    /// The wait is limited by setting `lock_timeout` for the call, which is made in its own
+    /// transaction. The timeout is rounded down to milliseconds, and is between one millisecond
+    /// and `i32::MAX` milliseconds.
+    ///
+    /// If the connection is already in a transaction, the call is made in a savepoint that is
+    /// rolled back when the timeout expires, so that the transaction can still be used.
+    ///
+    /// Otherwise, this behaves like [`Self::acquire()`].
+    pub async fn acquire_timeout<C: AsMut<PgConnection>>(
+        &self,
+        mut conn: C,
+        timeout: Duration,
+    ) -> Result<Either<PgAdvisoryLockGuard<'_, C>, C>> {
+        if self
+            .call_with_timeout("pg_advisory_lock", conn.as_mut(), timeout)
+            .await?
+        {
+            Ok(Either::Left(PgAdvisoryLockGuard::new(self, conn, false)))
+        } else {
+            Ok(Either::Right(conn))
+        }
+    }
 
-        if locked {
-            Ok(Either::Left(PgAdvisoryLockGuard::new(self, conn)))
+    /// Acquires a shared lock using `pg_advisory_lock_shared()`, waiting at most `timeout` for
+    /// the lock to be acquired, and returning the connection if it could not be.
+    ///
+    /// See [`Self::acquire_timeout()`] for how the wait is limited.
+    pub async fn acquire_shared_timeout<C: AsMut<PgConnection>>(
+        &self,
+        mut conn: C,
+        timeout: Duration,
+    ) -> Result<Either<PgAdvisoryLockGuard<'_, C>, C>> {
+        if self
+            .call_with_timeout("pg_advisory_lock_shared", conn.as_mut(), timeout)
+            .await?
+        {
+            Ok(Either::Left(PgAdvisoryLockGuard::new(self, conn, true)))
         } else {
             Ok(Either::Right(conn))
         }
     }
 
+    /// Acquires an exclusive transaction-scoped lock using `pg_advisory_xact_lock()`, waiting
+    /// until the lock is acquired.
+    ///
+    /// The lock is held until the transaction is committed or rolled back, and cannot be
+    /// released before. When `tx` is a savepoint, rolling back to it does not release the lock.
+    ///
+    /// Transaction-scoped and session-scoped locks share the same keys, and a transaction-scoped
+    /// lock cannot be acquired while another connection holds a session-scoped lock for the same
+    /// key, and the other way around.
+    pub async fn acquire_xact(&self, tx: &mut Transaction<'_, Postgres>) -> Result<()> {
+        self.call("pg_advisory_xact_lock", tx).await
+    }
+
+    /// Acquires a shared transaction-scoped lock using `pg_advisory_xact_lock_shared()`,
+    /// waiting until the lock is acquired.
+    ///
+    /// Otherwise, this behaves like [`Self::acquire_xact()`].
+    pub async fn acquire_xact_shared(&self, tx: &mut Transaction<'_, Postgres>) -> Result<()> {
+        self.call("pg_advisory_xact_lock_shared", tx).await
+    }
+
+    /// Acquires an exclusive transaction-scoped lock using `pg_try_advisory_xact_lock()`,
+    /// returning `false` immediately if the lock could not be acquired.
+    ///
+    /// Otherwise, this behaves like [`Self::acquire_xact()`].
+    pub async fn try_acquire_xact(&self, tx: &mut Transaction<'_, Postgres>) -> Result<bool> {
+        self.call_bool("pg_try_advisory_xact_lock", tx).await
+    }
+
+    /// Acquires a shared transaction-scoped lock using `pg_try_advisory_xact_lock_shared()`,
+    /// returning `false` immediately if the lock could not be acquired.
+    ///
+    /// Otherwise, this behaves like [`Self::acquire_xact()`].
+    pub async fn try_acquire_xact_shared(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+    ) -> Result<bool> {
+        self.call_bool("pg_try_advisory_xact_lock_shared", tx).await
+    }
+
+    /// Acquires an exclusive transaction-scoped lock using `pg_advisory_xact_lock()`, waiting
+    /// at most `timeout` for the lock to be acquired, and returning `false` if it could not be.
+    ///
+    /// See [`Self::acquire_timeout()`] for how the wait is limited.
+    pub async fn acquire_xact_timeout(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        timeout: Duration,
+    ) -> Result<bool> {
+        self.call_with_timeout("pg_advisory_xact_lock", tx, timeout)
+            .await
+    }
+
+    /// Acquires a shared transaction-scoped lock using `pg_advisory_xact_lock_shared()`,
+    /// waiting at most `timeout` for the lock to be acquired, and returning `false` if it could
+    /// not be.
+    ///
+    /// See [`Self::acquire_timeout()`] for how the wait is limited.
+    pub async fn acquire_xact_shared_timeout(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        timeout: Duration,
+    ) -> Result<bool> {
+        self.call_with_timeout("pg_advisory_xact_lock_shared", tx, timeout)
+            .await
+    }
+
     /// Execute `pg_advisory_unlock()` for this lock's key on the given connection.
     ///
     /// This is used by [`PgAdvisoryLockGuard::release_now()`] and is also provided for manually
@@ -281,32 +409,145 @@ impl PgAdvisoryLock {
     /// indicates that the lock was not actually held by the given connection and that a warning
     /// has been logged by the Postgres server.
     pub async fn force_release<C: AsMut<PgConnection>>(&self, mut conn: C) -> Result<(C, bool)> {
-        let released: bool = match &self.key {
+        let released = self.call_bool("pg_advisory_unlock", conn.as_mut()).await?;
+
+        Ok((conn, released))
+    }
+
+    /// Execute `pg_advisory_unlock_shared()` for this lock's key on the given connection,
+    /// releasing a lock held in shared mode.
+    ///
+    /// Otherwise, this behaves like [`Self::force_release()`].
+    pub async fn force_release_shared<C: AsMut<PgConnection>>(
+        &self,
+        mut conn: C,
+    ) -> Result<(C, bool)> {
+        let released = self
+            .call_bool("pg_advisory_unlock_shared", conn.as_mut())
+            .await?;
+
+        Ok((conn, released))
+    }
+
+    fn get_release_query(&self, shared: bool) -> &str {
+        if shared {
+            self.release_shared_query
+                .get_or_init(|| self.call_sql("pg_advisory_unlock_shared", true))
+        } else {
+            self.release_query
+                .get_or_init(|| self.call_sql("pg_advisory_unlock", true))
+        }
+    }
+
+    // `SELECT function(key)`, with the key inlined or as parameters
+    fn call_sql(&self, function: &str, inline: bool) -> String {
+        match (&self.key, inline) {
+            (PgAdvisoryLockKey::BigInt(key), true) => format!("SELECT {}({})", function, key),
+            (PgAdvisoryLockKey::BigInt(_), false) => format!("SELECT {}($1)", function),
+            (PgAdvisoryLockKey::IntPair(key1, key2), true) => {
+                format!("SELECT {}({}, {})", function, key1, key2)
+            }
+            (PgAdvisoryLockKey::IntPair(..), false) => format!("SELECT {}($1, $2)", function),
+        }
+    }
+
+    async fn call(&self, function: &str, conn: &mut PgConnection) -> Result<()> {
+        let sql = self.call_sql(function, false);
+
+        match &self.key {
             PgAdvisoryLockKey::BigInt(key) => {
-                crate::query_scalar::query_scalar("SELECT pg_advisory_unlock($1)")
-                    .bind(key)
-                    .fetch_one(conn.as_mut())
-                    .await?
+                crate::query::query(&sql).bind(key).execute(conn).await?;
             }
             PgAdvisoryLockKey::IntPair(key1, key2) => {
-                crate::query_scalar::query_scalar("SELECT pg_advisory_unlock($1, $2)")
+                crate::query::query(&sql)
                     .bind(key1)
                     .bind(key2)
-                    .fetch_one(conn.as_mut())
-                    .await?
+                    .execute(conn)
+                    .await?;
             }
-        };
+        }
 
-        Ok((conn, released))
+        Ok(())
     }
 
-    fn get_release_query(&self) -> &str {
-        self.release_query.get_or_init(|| match &self.key {
-            PgAdvisoryLockKey::BigInt(key) => format!("SELECT pg_advisory_unlock({})", key),
+    async fn call_bool(&self, function: &str, conn: &mut PgConnection) -> Result<bool> {
+        let sql = self.call_sql(function, false);
+
+        match &self.key {
+            PgAdvisoryLockKey::BigInt(key) => {
+                crate::query_scalar::query_scalar(&sql)
+                    .bind(key)
+                    .fetch_one(conn)
+                    .await
+            }
             PgAdvisoryLockKey::IntPair(key1, key2) => {
-                format!("SELECT pg_advisory_unlock({}, {})", key1, key2)
+                crate::query_scalar::query_scalar(&sql)
+                    .bind(key1)
+                    .bind(key2)
+                    .fetch_one(conn)
+                    .await
+            }
+        }
+    }
+
+    // returns `false` if `lock_timeout` expired before the lock was acquired
+    async fn call_with_timeout(
+        &self,
+        function: &str,
+        conn: &mut PgConnection,
+        timeout: Duration,
+    ) -> Result<bool> {
+        // `lock_timeout` is an `int` of milliseconds
+        let timeout = timeout.as_millis().clamp(1, i32::MAX as u128);
+        let timeout = format!("{}ms", timeout);
+
+        // the call is made in a transaction, or in a savepoint of the current one, so that
+        // `lock_timeout` is only set for the call, and an expired `lock_timeout` can be rolled
+        // back without aborting the transaction of the caller
+        let nested = conn.transaction_depth > 0;
+        let mut tx = conn.begin().await?;
+
+        // settings made in a savepoint are kept when it is released, until the end of the
+        // transaction, so the previous value is restored by hand in that case
+        let previous: Option<String> = if nested {
+            let previous =
+                crate::query_scalar::query_scalar("SELECT current_setting('lock_timeout')")
+                    .fetch_one(&mut *tx)
+                    .await?;
+
+            Some(previous)
+        } else {
+            None
+        };
+
+        crate::query::query("SELECT set_config('lock_timeout', $1, true)")
+            .bind(timeout)
+            .execute(&mut *tx)
+            .await?;
+
+        match self.call(function, &mut tx).await {
+            Ok(()) => {
+                if let Some(previous) = previous {
+                    crate::query::query("SELECT set_config('lock_timeout', $1, true)")
+                        .bind(previous)
+                        .execute(&mut *tx)
+                        .await?;
+                }
+
+                tx.commit().await?;
+
+                Ok(true)
             }
-        })
+
+            Err(Error::Database(error)) if error.kind() == ErrorKind::LockTimeout => {
+                tx.rollback().await?;
+
+                Ok(false)
+            }
+
+            // the transaction is rolled back when it is dropped
+            Err(error) => Err(error),
+        }
     }
 }
 
@@ -324,10 +565,11 @@ impl PgAdvisoryLockKey {
 const NONE_ERR: &str = "BUG: PgAdvisoryLockGuard.conn taken";
 
 impl<'lock, C: AsMut<PgConnection>> PgAdvisoryLockGuard<'lock, C> {
-    fn new(lock: &'lock PgAdvisoryLock, conn: C) -> Self {
+    fn new(lock: &'lock PgAdvisoryLock, conn: C, shared: bool) -> Self {
         PgAdvisoryLockGuard {
             lock,
             conn: Some(conn),
+            shared,
         }
     }
 
@@ -340,10 +582,13 @@ impl<'lock, C: AsMut<PgConnection>> PgAdvisoryLockGuard<'lock, C> {
     /// well as the Postgres server. This would only happen if the lock was released without
     /// using this guard, or the connection was swapped using [`std::mem::replace()`].
     pub async fn release_now(mut self) -> Result<C> {
-        let (conn, released) = self
-            .lock
-            .force_release(self.conn.take().expect(NONE_ERR))
-            .await?;
+        let conn = self.conn.take().expect(NONE_ERR);
+
+        let (conn, released) = if self.shared {
+            self.lock.force_release_shared(conn).await?
+        } else {
+            self.lock.force_release(conn).await?
+        };
 
         if !released {
             log::warn!(
@@ -357,7 +602,8 @@ impl<'lock, C: AsMut<PgConnection>> PgAdvisoryLockGuard<'lock, C> {
 
     /// Cancel the release of the advisory lock, keeping it held until the connection is closed.
     ///
-    /// To manually release the lock later, see [`PgAdvisoryLock::force_release()`].
+    /// To manually release the lock later, see [`PgAdvisoryLock::force_release()`], or
+    /// [`PgAdvisoryLock::force_release_shared()`] for a lock held in shared mode.
     pub fn leak(mut self) -> C {
         self.conn.take().expect(NONE_ERR)
     }
@@ -405,7 +651,8 @@ impl<'lock, C: AsMut<PgConnection>> AsMut<PgConnection> for PgAdvisoryLockGuard<
     }
 }
 
-/// Queues a `pg_advisory_unlock()` call on the wrapped connection which will be flushed
+/// Queues a `pg_advisory_unlock()` (or `pg_advisory_unlock_shared()`) call on the wrapped
+/// connection which will be flushed
 /// to the server the next time it is used, or when it is returned to [`PgPool`][crate::postgres::PgPool]
 /// in the case of [`PoolConnection<Postgres>`][crate::pool::PoolConnection].
 impl<'lock, C: AsMut<PgConnection>> Drop for PgAdvisoryLockGuard<'lock, C> {
@@ -415,7 +662,7 @@ impl<'lock, C: AsMut<PgConnection>> Drop for PgAdvisoryLockGuard<'lock, C> {
             // The `async fn` versions can safely use the prepared statement protocol,
             // but this is the safest way to queue a query to execute on the next opportunity.
             conn.as_mut()
-                .queue_simple_query(self.lock.get_release_query());
+                .queue_simple_query(self.lock.get_release_query(self.shared));
         }
     }
 }
@@ -80,22 +80,60 @@ impl PgArguments {
         conn: &mut PgConnection,
         parameters: &[PgTypeInfo],
     ) -> Result<(), Error> {
+        self.apply_value_patches(parameters);
+
         let PgArgumentBuffer {
-            ref patches,
             ref type_holes,
             ref mut buffer,
             ..
         } = self.buffer;
 
+        for (offset, name) in type_holes {
+            let oid = conn.fetch_type_id_by_name(name).await?;
+            buffer[*offset..(*offset + 4)].copy_from_slice(&oid.0.to_be_bytes());
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn has_patches(&self) -> bool {
+        !self.buffer.patches.is_empty()
+    }
+
+    // Let values that were encoded before their type was known patch themselves, now that
+    // `types` gives the type of each argument
+    pub(crate) fn apply_value_patches(&mut self, types: &[PgTypeInfo]) {
+        let PgArgumentBuffer {
+            ref patches,
+            ref mut buffer,
+            ..
+        } = self.buffer;
+
         for (offset, ty, callback) in patches {
             let buf = &mut buffer[*offset..];
-            let ty = &parameters[*ty];
+            let ty = &types[*ty];
 
             callback(buf, ty);
         }
+    }
+
+    // Fill in type holes from the types already cached by the connection, for when it can't be
+    // asked to look them up because it is in the middle of a `COPY`
+    pub(crate) fn apply_cached_type_holes(&mut self, conn: &PgConnection) -> Result<(), Error> {
+        let PgArgumentBuffer {
+            ref type_holes,
+            ref mut buffer,
+            ..
+        } = self.buffer;
 
         for (offset, name) in type_holes {
-            let oid = conn.fetch_type_id_by_name(name).await?;
+            let oid = conn.cached_type_id_by_name(name).ok_or_else(|| {
+                err_protocol!(
+                    "the OID of type `{}` is unknown, use it in a query on this connection first",
+                    name
+                )
+            })?;
+
             buffer[*offset..(*offset + 4)].copy_from_slice(&oid.0.to_be_bytes());
         }
 
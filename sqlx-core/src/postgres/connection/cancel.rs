@@ -0,0 +1,76 @@
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+
+use bytes::Bytes;
+
+use crate::error::Error;
+use crate::postgres::connection::{stream::PgStream, tls};
+use crate::postgres::message::CancelRequest;
+use crate::postgres::PgConnectOptions;
+
+// https://www.postgresql.org/docs/current/protocol-flow.html#PROTOCOL-FLOW-CANCELING-REQUESTS
+
+/// A handle for cancelling the query currently running on a [`PgConnection`].
+///
+/// Returned by [`PgConnection::cancel_token`]. It is cheap to clone and can be sent to
+/// another task, which is how a query that is blocking its connection gets stopped.
+///
+/// [`PgConnection`]: crate::postgres::PgConnection
+/// [`PgConnection::cancel_token`]: crate::postgres::PgConnection::cancel_token
+#[derive(Clone)]
+pub struct PgCancelToken {
+    options: Arc<PgConnectOptions>,
+    process_id: u32,
+    secret_key: u32,
+}
+
+impl PgCancelToken {
+    pub(super) fn new(options: &PgConnectOptions, process_id: u32, secret_key: u32) -> Self {
+        Self {
+            options: Arc::new(options.clone()),
+            process_id,
+            secret_key,
+        }
+    }
+
+    /// Ask the server to cancel the query currently running on the connection.
+    ///
+    /// This opens a new connection to the server, with the same host, socket and TLS
+    /// settings as the original connection, to send a `CancelRequest`.
+    ///
+    /// The cancellation is best-effort: if the query finishes before the request is processed,
+    /// or if no query is running, nothing happens. Otherwise the query fails with an error
+    /// whose [`kind`][crate::error::DatabaseError::kind] is
+    /// [`ErrorKind::QueryCanceled`][crate::error::ErrorKind::QueryCanceled].
+    pub async fn cancel(&self) -> Result<(), Error> {
+        let mut stream = PgStream::connect(&self.options).await?;
+
+        tls::maybe_upgrade(&mut stream, &self.options).await?;
+
+        stream
+            .send(CancelRequest {
+                process_id: self.process_id,
+                secret_key: self.secret_key,
+            })
+            .await?;
+
+        // The server closes the connection without replying once it has processed the request,
+        // wait for that so the query has been signalled by the time we return.
+        let _ = stream.read::<Bytes>(1).await;
+
+        Ok(())
+    }
+
+    /// The process ID of the backend this token cancels queries on.
+    pub fn process_id(&self) -> u32 {
+        self.process_id
+    }
+}
+
+impl Debug for PgCancelToken {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PgCancelToken")
+            .field("process_id", &self.process_id)
+            .finish()
+    }
+}
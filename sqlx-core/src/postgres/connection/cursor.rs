@@ -0,0 +1,153 @@
+use futures_core::stream::Stream;
+
+use crate::error::Error;
+use crate::executor::{Execute, Executor};
+use crate::postgres::quote::quote_identifier;
+use crate::postgres::{PgConnection, PgRow, Postgres};
+use crate::query::{query, query_with};
+
+// https://www.postgresql.org/docs/current/sql-declare.html
+
+/// A cursor declared with `DECLARE ... CURSOR`, whose rows are fetched in batches with
+/// `FETCH`.
+///
+/// Created by [`PgConnection::declare_cursor`]. A cursor can only be declared inside a
+/// transaction, and it is closed at the end of the transaction if it was not
+/// [closed][Self::close] before.
+///
+/// Unlike a [`PgPortal`][crate::postgres::PgPortal], the size of each batch can vary, and
+/// a cursor stays valid if the connection is used for other queries in the meantime, as long
+/// as the transaction is not ended.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use sqlx_core_oldapi::error::Error;
+/// # use sqlx_core_oldapi::connection::Connection;
+/// # use sqlx_core_oldapi::postgres::PgConnection;
+/// # use sqlx_core_oldapi::query::query;
+/// # async fn _f(conn: &mut PgConnection) -> Result<(), Error> {
+/// let mut tx = conn.begin().await?;
+///
+/// let mut cursor = tx
+///     .declare_cursor("events", query("SELECT * FROM events WHERE kind = $1").bind("click"))
+///     .await?;
+///
+/// loop {
+///     let rows = cursor.fetch(10_000).await?;
+///
+///     if rows.is_empty() {
+///         break;
+///     }
+/// }
+///
+/// cursor.close().await?;
+/// tx.commit().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct PgCursor<'c> {
+    conn: &'c mut PgConnection,
+    name: String,
+}
+
+impl PgConnection {
+    /// Declare a cursor named `name` for a query returning rows, with
+    /// `DECLARE name NO SCROLL CURSOR FOR query`.
+    ///
+    /// The connection must be in a transaction.
+    pub async fn declare_cursor<'q, E>(
+        &mut self,
+        name: &str,
+        mut query: E,
+    ) -> Result<PgCursor<'_>, Error>
+    where
+        E: Execute<'q, Postgres>,
+    {
+        let sql = format!(
+            "DECLARE {} NO SCROLL CURSOR FOR {}",
+            quote_identifier(name),
+            query.sql()
+        );
+
+        match query.take_arguments() {
+            Some(arguments) => {
+                query_with(&sql, arguments)
+                    .persistent(false)
+                    .execute(&mut *self)
+                    .await?
+            }
+
+            None => self.execute(&*sql).await?,
+        };
+
+        Ok(PgCursor {
+            conn: self,
+            name: name.to_owned(),
+        })
+    }
+}
+
+impl<'c> PgCursor<'c> {
+    /// The name of the cursor.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Fetch the next `count` rows of the cursor, or all its remaining rows if there are
+    /// fewer. A `count` of `0` fetches all the remaining rows.
+    ///
+    /// Returns an empty batch once all the rows have been fetched.
+    pub async fn fetch(&mut self, count: u32) -> Result<Vec<PgRow>, Error> {
+        // `FETCH FORWARD 0` would fetch the current row again rather than move forward
+        let count = match count {
+            0 => "ALL".to_owned(),
+            count => count.to_string(),
+        };
+
+        let sql = format!(
+            "FETCH FORWARD {} FROM {}",
+            count,
+            quote_identifier(&self.name)
+        );
+
+        // the statement is not cached, as its columns depend on the query of the cursor
+        query(&sql)
+            .persistent(false)
+            .fetch_all(&mut *self.conn)
+            .await
+    }
+
+    /// Close the cursor with `CLOSE`, freeing its resources before the end of the
+    /// transaction.
+    pub async fn close(self) -> Result<(), Error> {
+        let sql = format!("CLOSE {}", quote_identifier(&self.name));
+
+        self.conn.execute(&*sql).await?;
+
+        Ok(())
+    }
+
+    /// Turn this into a [`Stream`] of the rows of the cursor, fetched `batch_size` at a time
+    /// when the rows of the previous batch have all been consumed.
+    ///
+    /// As with [`fetch`][Self::fetch], a `batch_size` of `0` fetches all the rows at once.
+    pub fn into_stream(mut self, batch_size: u32) -> impl Stream<Item = Result<PgRow, Error>> + 'c {
+        Box::pin(try_stream! {
+            loop {
+                let rows = self.fetch(batch_size).await?;
+
+                if rows.is_empty() {
+                    break;
+                }
+
+                for row in rows {
+                    r#yield!(row);
+                }
+            }
+
+            Ok(())
+        })
+    }
+}
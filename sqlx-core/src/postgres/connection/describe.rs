@@ -24,6 +24,7 @@ enum TypType {
     Enum,
     Pseudo,
     Range,
+    MultiRange,
 }
 
 impl TryFrom<u8> for TypType {
@@ -37,6 +38,7 @@ impl TryFrom<u8> for TypType {
             b'e' => Self::Enum,
             b'p' => Self::Pseudo,
             b'r' => Self::Range,
+            b'm' => Self::MultiRange,
             _ => return Err(()),
         };
         Ok(t)
@@ -222,6 +224,10 @@ impl PgConnection {
                     self.fetch_range_by_oid(oid, name).await
                 }
 
+                (Ok(TypType::MultiRange), Ok(TypCategory::Range)) => {
+                    self.fetch_multirange_by_oid(oid, name).await
+                }
+
                 (Ok(TypType::Enum), Ok(TypCategory::Enum)) => {
                     self.fetch_enum_by_oid(oid, name).await
                 }
@@ -340,6 +346,37 @@ WHERE rngtypid = $1
         })
     }
 
+    fn fetch_multirange_by_oid(
+        &mut self,
+        oid: Oid,
+        name: String,
+    ) -> BoxFuture<'_, Result<PgTypeInfo, Error>> {
+        Box::pin(async move {
+            let range_oid: Oid = query_scalar(
+                r#"
+SELECT rngtypid
+FROM pg_catalog.pg_range
+WHERE rngmultitypid = $1
+                "#,
+            )
+            .bind(oid)
+            .fetch_one(&mut *self)
+            .await?;
+
+            let range = self.maybe_fetch_type_info_by_oid(range_oid, true).await?;
+
+            Ok(PgTypeInfo(PgType::Custom(Arc::new(PgCustomType {
+                kind: PgTypeKind::MultiRange(range),
+                name: name.into(),
+                oid,
+            }))))
+        })
+    }
+
+    pub(crate) fn cached_type_id_by_name(&self, name: &str) -> Option<Oid> {
+        self.cache_type_oid.get(name).copied()
+    }
+
     pub(crate) async fn fetch_type_id_by_name(&mut self, name: &str) -> Result<Oid, Error> {
         if let Some(oid) = self.cache_type_oid.get(name) {
             return Ok(*oid);
@@ -1,20 +1,133 @@
 use crate::HashMap;
 
+use rand::seq::SliceRandom;
+
 use crate::common::StatementCache;
+use crate::connection::Connection;
 use crate::error::Error;
+use crate::executor::Executor;
 use crate::io::Decode;
-use crate::postgres::connection::{sasl, stream::PgStream, tls};
+use crate::postgres::connection::{sasl, stream::PgStream, tls, PgCancelToken};
 use crate::postgres::message::{
     Authentication, BackendKeyData, MessageFormat, Password, ReadyForQuery, Startup,
 };
 use crate::postgres::types::Oid;
-use crate::postgres::{PgConnectOptions, PgConnection};
+use crate::postgres::{PgConnectOptions, PgConnection, PgRow, PgTargetSessionAttrs};
+use crate::row::Row;
 
 // https://www.postgresql.org/docs/current/protocol-flow.html#id-1.10.5.7.3
 // https://www.postgresql.org/docs/current/protocol-flow.html#id-1.10.5.7.11
 
 impl PgConnection {
     pub(crate) async fn establish(options: &PgConnectOptions) -> Result<Self, Error> {
+        if options.additional_hosts.is_empty()
+            && options.target_session_attrs == PgTargetSessionAttrs::Any
+        {
+            return Self::establish_with_host(options).await;
+        }
+
+        let mut hosts = options.hosts();
+
+        if options.load_balance_hosts {
+            hosts.shuffle(&mut rand::rng());
+        }
+
+        // like libpq, look for a standby first and settle for any server on a second pass
+        let passes: &[PgTargetSessionAttrs] = match options.target_session_attrs {
+            PgTargetSessionAttrs::PreferStandby => {
+                &[PgTargetSessionAttrs::Standby, PgTargetSessionAttrs::Any]
+            }
+            ref target_session_attrs => std::slice::from_ref(target_session_attrs),
+        };
+
+        let mut last_error = None;
+
+        for &target_session_attrs in passes {
+            for host in &hosts {
+                let mut conn = match Self::establish_with_host(host).await {
+                    Ok(conn) => conn,
+                    Err(error) => {
+                        log::debug!(
+                            "failed to connect to {}:{}: {}",
+                            host.host,
+                            host.port,
+                            error
+                        );
+
+                        last_error = Some(error);
+                        continue;
+                    }
+                };
+
+                match conn.has_session_attrs(target_session_attrs).await {
+                    Ok(true) => return Ok(conn),
+
+                    Ok(false) => {
+                        last_error = Some(Error::Configuration(
+                            format!(
+                                "server at {}:{} does not match `target_session_attrs` {:?}",
+                                host.host, host.port, target_session_attrs
+                            )
+                            .into(),
+                        ));
+                    }
+
+                    Err(error) => last_error = Some(error),
+                }
+
+                let _ = conn.close().await;
+            }
+        }
+
+        Err(last_error.expect("at least one host is tried"))
+    }
+
+    async fn has_session_attrs(
+        &mut self,
+        target_session_attrs: PgTargetSessionAttrs,
+    ) -> Result<bool, Error> {
+        Ok(match target_session_attrs {
+            PgTargetSessionAttrs::Any => true,
+            PgTargetSessionAttrs::ReadWrite => !self.is_read_only().await?,
+            PgTargetSessionAttrs::ReadOnly => self.is_read_only().await?,
+            PgTargetSessionAttrs::Primary => !self.is_in_hot_standby().await?,
+            PgTargetSessionAttrs::Standby | PgTargetSessionAttrs::PreferStandby => {
+                self.is_in_hot_standby().await?
+            }
+        })
+    }
+
+    // servers since Postgres 14 report these settings when connecting, saving a query
+    // https://www.postgresql.org/docs/current/protocol-flow.html#PROTOCOL-ASYNC
+
+    async fn is_read_only(&mut self) -> Result<bool, Error> {
+        let statuses = &self.stream.parameter_statuses;
+
+        if let (Some(in_hot_standby), Some(default_transaction_read_only)) = (
+            statuses.get("in_hot_standby"),
+            statuses.get("default_transaction_read_only"),
+        ) {
+            return Ok(in_hot_standby == "on" || default_transaction_read_only == "on");
+        }
+
+        let row: PgRow = self.fetch_one("SHOW transaction_read_only").await?;
+
+        Ok(row.try_get::<&str, _>(0)? == "on")
+    }
+
+    async fn is_in_hot_standby(&mut self) -> Result<bool, Error> {
+        if let Some(in_hot_standby) = self.stream.parameter_statuses.get("in_hot_standby") {
+            return Ok(in_hot_standby == "on");
+        }
+
+        let row: PgRow = self
+            .fetch_one("SELECT pg_catalog.pg_is_in_recovery()")
+            .await?;
+
+        row.try_get(0)
+    }
+
+    async fn establish_with_host(options: &PgConnectOptions) -> Result<Self, Error> {
         let mut stream = PgStream::connect(options).await?;
 
         // Upgrade to TLS if we were asked to and the server supports it
@@ -46,6 +159,10 @@ impl PgConnection {
             params.push(("options", options));
         }
 
+        if options.replication {
+            params.push(("replication", "database"));
+        }
+
         stream
             .send(Startup {
                 username: Some(&options.username),
@@ -139,8 +256,9 @@ impl PgConnection {
 
         Ok(PgConnection {
             stream,
-            process_id,
-            secret_key,
+            cancel_token: PgCancelToken::new(options, process_id, secret_key),
+            cancel_abandoned_queries: options.cancel_abandoned_queries,
+            query_in_progress: false,
             transaction_status,
             transaction_depth: 0,
             pending_ready_for_query_count: 0,
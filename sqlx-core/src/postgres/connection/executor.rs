@@ -160,7 +160,7 @@ impl PgConnection {
         self.pending_ready_for_query_count += 1;
     }
 
-    async fn get_or_prepare(
+    pub(super) async fn get_or_prepare(
         &mut self,
         sql: &str,
         parameters: &[PgTypeInfo],
@@ -270,6 +270,9 @@ impl PgConnection {
             PgValueFormat::Text
         };
 
+        // only queries with unlimited results are worth cancelling when abandoned,
+        // `fetch_optional()` stops reading once it has its row and that is expected
+        self.query_in_progress = limit == 0;
         self.stream.flush().await?;
 
         Ok(try_stream! {
@@ -7,7 +7,8 @@ use futures_util::FutureExt;
 
 use crate::common::StatementCache;
 use crate::connection::{Connection, LogSettings};
-use crate::error::Error;
+use crate::error::{Error, ErrorKind};
+use crate::executor::Executor;
 use crate::ext::ustr::UStr;
 use crate::io::Decode;
 use crate::postgres::message::{
@@ -16,13 +17,21 @@ use crate::postgres::message::{
 use crate::postgres::statement::PgStatementMetadata;
 use crate::postgres::types::Oid;
 use crate::postgres::{PgConnectOptions, PgTypeInfo, Postgres};
-use crate::transaction::Transaction;
+use crate::transaction::{Transaction, TransactionOptions};
 
+pub use self::cancel::PgCancelToken;
+pub use self::cursor::PgCursor;
+pub use self::pipeline::{PgPipeline, PgPipelineResult};
+pub use self::portal::PgPortal;
 pub use self::stream::PgStream;
 
+mod cancel;
+mod cursor;
 pub(crate) mod describe;
 mod establish;
 mod executor;
+mod pipeline;
+mod portal;
 mod sasl;
 mod stream;
 mod tls;
@@ -34,15 +43,16 @@ pub struct PgConnection {
     // wrapped in a buffered stream
     pub(crate) stream: PgStream,
 
-    // process id of this backend
+    // process id and secret key of this backend
     // used to send cancel requests
-    #[allow(dead_code)]
-    process_id: u32,
+    cancel_token: PgCancelToken,
 
-    // secret key of this backend
-    // used to send cancel requests
-    #[allow(dead_code)]
-    secret_key: u32,
+    // whether to cancel a query whose results were abandoned before
+    // draining them the next time the connection is used
+    cancel_abandoned_queries: bool,
+
+    // set while the results of a query are being streamed back
+    pub(crate) query_in_progress: bool,
 
     // sequence of statement IDs for use in preparing statements
     // in PostgreSQL, the statement is prepared to a user-supplied identifier
@@ -71,14 +81,73 @@ impl PgConnection {
         self.stream.server_version_num
     }
 
+    /// The current value of a parameter reported by the server, such as `TimeZone`,
+    /// `search_path` (Postgres 18 and later) or `in_hot_standby` (Postgres 14 and later).
+    ///
+    /// The server reports the parameters when connecting and again whenever they change, so
+    /// this reflects `SET` commands once their results have been received. Other parameters
+    /// can be read with `SHOW`.
+    ///
+    /// See the [list of reported parameters](https://www.postgresql.org/docs/current/protocol-flow.html#PROTOCOL-ASYNC).
+    pub fn server_parameter(&self, name: &str) -> Option<&str> {
+        self.stream.parameter_statuses.get(name).map(String::as_str)
+    }
+
+    /// Returns a handle that can be used to cancel the query running on this connection
+    /// from another task.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use sqlx_core_oldapi::error::Error;
+    /// # use sqlx_core_oldapi::executor::Executor;
+    /// # use sqlx_core_oldapi::postgres::PgConnection;
+    /// # async fn _f(conn: &mut PgConnection) -> Result<(), Error> {
+    /// let token = conn.cancel_token();
+    ///
+    /// sqlx_rt::spawn(async move {
+    ///     sqlx_rt::sleep(std::time::Duration::from_secs(5)).await;
+    ///     let _ = token.cancel().await;
+    /// });
+    ///
+    /// // fails with `ErrorKind::QueryCanceled` after 5 seconds
+    /// conn.execute("SELECT pg_sleep(60)").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn cancel_token(&self) -> PgCancelToken {
+        self.cancel_token.clone()
+    }
+
     // will return when the connection is ready for another query
     pub(in crate::postgres) async fn wait_until_ready(&mut self) -> Result<(), Error> {
         if !self.stream.wbuf.is_empty() {
             self.stream.flush().await?;
         }
 
+        // the results of the last query were dropped before it completed,
+        // so we would have to wait for the whole query to run to drain them
+        let cancelled = self.query_in_progress && self.cancel_abandoned_queries;
+
+        if cancelled {
+            if let Err(error) = self.cancel_token.cancel().await {
+                log::warn!("failed to cancel abandoned query: {}", error);
+            }
+        }
+
         while self.pending_ready_for_query_count > 0 {
-            let message = self.stream.recv().await?;
+            let message = match self.stream.recv().await {
+                Ok(message) => message,
+
+                // the error caused by our own cancel request
+                Err(Error::Database(error))
+                    if cancelled && error.kind() == ErrorKind::QueryCanceled =>
+                {
+                    continue;
+                }
+
+                Err(error) => return Err(error),
+            };
 
             if let MessageFormat::ReadyForQuery = message.format {
                 self.handle_ready_for_query(message)?;
@@ -95,13 +164,18 @@ impl PgConnection {
             .await?;
 
         self.pending_ready_for_query_count -= 1;
+        self.query_in_progress = false;
         self.transaction_status = r.transaction_status;
 
         Ok(())
     }
 
-    fn handle_ready_for_query(&mut self, message: Message) -> Result<(), Error> {
+    pub(in crate::postgres) fn handle_ready_for_query(
+        &mut self,
+        message: Message,
+    ) -> Result<(), Error> {
         self.pending_ready_for_query_count -= 1;
+        self.query_in_progress = false;
         self.transaction_status = ReadyForQuery::decode(message.contents)?.transaction_status;
 
         Ok(())
@@ -162,6 +236,17 @@ impl Connection for PgConnection {
         })
     }
 
+    fn reset_session(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move {
+            // `DISCARD ALL` also deallocates all prepared statements on the server,
+            // so there is nothing to close for the cached ones.
+            self.execute("DISCARD ALL").await?;
+            self.cache_statement.clear();
+
+            Ok(())
+        })
+    }
+
     fn begin(&mut self) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
     where
         Self: Sized,
@@ -169,6 +254,16 @@ impl Connection for PgConnection {
         Transaction::begin(self)
     }
 
+    fn begin_with(
+        &mut self,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
+    where
+        Self: Sized,
+    {
+        Transaction::begin_with(self, options)
+    }
+
     fn cached_statements_size(&self) -> usize {
         self.cache_statement.len()
     }
@@ -0,0 +1,294 @@
+use std::collections::HashSet;
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+
+use crate::error::Error;
+use crate::executor::Execute;
+use crate::logger::QueryLogger;
+use crate::postgres::message::{self, Bind, CommandComplete, DataRow, MessageFormat};
+use crate::postgres::statement::PgStatementMetadata;
+use crate::postgres::{PgArguments, PgConnection, PgQueryResult, PgRow, PgValueFormat, Postgres};
+
+// https://www.postgresql.org/docs/current/protocol-flow.html#PROTOCOL-FLOW-PIPELINING
+
+/// A batch of queries sent to the server together and executed in a single round trip.
+///
+/// Created by [`PgConnection::pipeline`]. Queries are added with [`push`][Self::push] and
+/// run in order by [`execute`][Self::execute], which returns one [`PgPipelineResult`] per query.
+///
+/// # Errors
+///
+/// The first query that fails aborts the pipeline: the server skips the queries after it,
+/// which are returned as [`PgPipelineResult::Skipped`].
+///
+/// Outside of a transaction, the queries of a pipeline run in a single implicit transaction,
+/// so an error also rolls back the queries that completed before it. Inside a transaction,
+/// an error aborts the transaction, which then has to be rolled back.
+///
+/// Each distinct query that is not in the statement cache yet is prepared before the pipeline
+/// is sent, which costs a round trip. An error preparing a query, such as a syntax error, is
+/// returned by [`execute`][Self::execute] before any query of the pipeline runs. As with other
+/// prepared statements, a query can only contain a single SQL statement.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use sqlx_core_oldapi::error::Error;
+/// # use sqlx_core_oldapi::postgres::{PgConnection, PgPipelineResult};
+/// # use sqlx_core_oldapi::query::query;
+/// # async fn _f(conn: &mut PgConnection) -> Result<(), Error> {
+/// let mut pipeline = conn.pipeline();
+///
+/// for id in 1..=10 {
+///     pipeline.push(query("INSERT INTO items (id) VALUES ($1)").bind(id));
+/// }
+///
+/// for result in pipeline.execute().await? {
+///     if let PgPipelineResult::Failed(error) = result {
+///         return Err(error);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct PgPipeline<'c, 'q> {
+    conn: &'c mut PgConnection,
+    queries: Vec<PipelinedQuery<'q>>,
+}
+
+struct PipelinedQuery<'q> {
+    sql: &'q str,
+    arguments: PgArguments,
+    persistent: bool,
+    metadata: Option<Arc<PgStatementMetadata>>,
+}
+
+/// The outcome of one query of a [`PgPipeline`].
+pub enum PgPipelineResult {
+    /// The query completed, returning these rows.
+    ///
+    /// Its changes are only kept if no later query of the pipeline failed, see
+    /// [`PgPipeline`].
+    Completed(PgQueryResult, Vec<PgRow>),
+
+    /// The query failed, aborting the rest of the pipeline.
+    Failed(Error),
+
+    /// The query was not run because an earlier query of the pipeline failed.
+    Skipped,
+}
+
+impl PgConnection {
+    /// Start a [`PgPipeline`] to execute several queries in a single round trip.
+    pub fn pipeline(&mut self) -> PgPipeline<'_, '_> {
+        PgPipeline {
+            conn: self,
+            queries: Vec::new(),
+        }
+    }
+}
+
+impl<'c, 'q> PgPipeline<'c, 'q> {
+    /// Add a query to the end of the pipeline.
+    pub fn push<E>(&mut self, mut query: E) -> &mut Self
+    where
+        E: Execute<'q, Postgres>,
+    {
+        self.queries.push(PipelinedQuery {
+            sql: query.sql(),
+            metadata: query.statement().map(|s| Arc::clone(&s.metadata)),
+            arguments: query.take_arguments().unwrap_or_default(),
+            persistent: query.persistent(),
+        });
+
+        self
+    }
+
+    /// The number of queries in the pipeline.
+    pub fn len(&self) -> usize {
+        self.queries.len()
+    }
+
+    /// Returns `true` if no query was added to the pipeline.
+    pub fn is_empty(&self) -> bool {
+        self.queries.is_empty()
+    }
+
+    /// Send all queries to the server and wait for their results.
+    ///
+    /// Returns one result per query, in the order they were added. An error is returned only
+    /// if the pipeline could not be run at all, see [`PgPipeline`].
+    pub async fn execute(self) -> Result<Vec<PgPipelineResult>, Error> {
+        let PgPipeline { conn, queries } = self;
+
+        if queries.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        conn.wait_until_ready().await?;
+
+        // every query is prepared before the first one is bound, so they must all fit in the
+        // statement cache at once or they could evict and close each other in the meantime
+        let cached: HashSet<&str> = queries
+            .iter()
+            .filter(|query| query.persistent)
+            .map(|query| query.sql)
+            .collect();
+
+        let fits_in_cache = cached.len() <= conn.cache_statement.capacity();
+
+        let mut statements = Vec::with_capacity(queries.len());
+
+        for mut query in queries {
+            let (statement, metadata) = conn
+                .get_or_prepare(
+                    query.sql,
+                    &query.arguments.types,
+                    query.persistent && fits_in_cache,
+                    query.metadata,
+                )
+                .await?;
+
+            // patch holes created during encoding
+            query
+                .arguments
+                .apply_patches(conn, &metadata.parameters)
+                .await?;
+
+            statements.push((query.sql, statement, metadata, query.arguments));
+        }
+
+        // consume messages till `ReadyForQuery` before bind and execute
+        conn.wait_until_ready().await?;
+
+        let mut loggers = Vec::with_capacity(statements.len());
+        let mut metadata = Vec::with_capacity(statements.len());
+
+        for (sql, statement, statement_metadata, arguments) in &statements {
+            conn.stream.write(Bind {
+                portal: None,
+                statement: *statement,
+                formats: &[PgValueFormat::Binary],
+                #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+                num_params: arguments.types.len() as i16,
+                params: &arguments.buffer,
+                result_formats: &[PgValueFormat::Binary],
+            });
+
+            conn.stream.write(message::Execute {
+                portal: None,
+                limit: 0,
+            });
+
+            conn.stream.write(message::Close::Portal(None));
+
+            loggers.push(QueryLogger::new(sql, conn.log_settings.clone()));
+            metadata.push(Arc::clone(statement_metadata));
+        }
+
+        // a single [Sync] for the whole pipeline, the server executes everything before it
+        // and skips to it after an error
+        conn.write_sync();
+        conn.query_in_progress = true;
+        conn.stream.flush().await?;
+
+        let mut results = Vec::with_capacity(statements.len());
+        let mut rows = Vec::new();
+
+        loop {
+            let message = match conn.stream.recv().await {
+                Ok(message) => message,
+
+                // the failed query aborts the pipeline, the next message is `ReadyForQuery`
+                Err(error @ Error::Database(_)) => {
+                    rows.clear();
+                    results.push(PgPipelineResult::Failed(error));
+                    continue;
+                }
+
+                Err(error) => return Err(error),
+            };
+
+            match message.format {
+                MessageFormat::BindComplete | MessageFormat::CloseComplete => {
+                    // harmless messages to ignore
+                }
+
+                MessageFormat::DataRow => {
+                    let index = results.len();
+
+                    if let Some(logger) = loggers.get_mut(index) {
+                        logger.increment_rows_returned();
+                    }
+
+                    let data: DataRow = message.decode()?;
+                    rows.push(PgRow {
+                        data,
+                        format: PgValueFormat::Binary,
+                        metadata: Arc::clone(
+                            metadata.get(index).ok_or_else(|| {
+                                err_protocol!("pipeline: more results than queries")
+                            })?,
+                        ),
+                    });
+                }
+
+                MessageFormat::CommandComplete | MessageFormat::EmptyQueryResponse => {
+                    let rows_affected = if message.format == MessageFormat::CommandComplete {
+                        let cc: CommandComplete = message.decode()?;
+                        cc.rows_affected()
+                    } else {
+                        0
+                    };
+
+                    if let Some(logger) = loggers.get_mut(results.len()) {
+                        logger.increase_rows_affected(rows_affected);
+                    }
+
+                    results.push(PgPipelineResult::Completed(
+                        PgQueryResult { rows_affected },
+                        std::mem::take(&mut rows),
+                    ));
+                }
+
+                MessageFormat::ReadyForQuery => {
+                    conn.handle_ready_for_query(message)?;
+                    break;
+                }
+
+                _ => {
+                    return Err(err_protocol!(
+                        "pipeline: unexpected message: {:?}",
+                        message.format
+                    ));
+                }
+            }
+        }
+
+        results.resize_with(statements.len(), || PgPipelineResult::Skipped);
+
+        Ok(results)
+    }
+}
+
+impl Debug for PgPipeline<'_, '_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.queries.iter().map(|query| query.sql))
+            .finish()
+    }
+}
+
+impl Debug for PgPipelineResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            PgPipelineResult::Completed(result, rows) => f
+                .debug_struct("Completed")
+                .field("rows_affected", &result.rows_affected)
+                .field("rows", &rows.len())
+                .finish(),
+            PgPipelineResult::Failed(error) => f.debug_tuple("Failed").field(error).finish(),
+            PgPipelineResult::Skipped => f.write_str("Skipped"),
+        }
+    }
+}
@@ -0,0 +1,254 @@
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+
+use futures_core::stream::Stream;
+
+use crate::error::Error;
+use crate::executor::Execute;
+use crate::postgres::message::{self, Bind, Close, DataRow, Flush, MessageFormat};
+use crate::postgres::statement::PgStatementMetadata;
+use crate::postgres::{PgConnection, PgRow, PgValueFormat, Postgres};
+
+// https://www.postgresql.org/docs/current/protocol-flow.html#PROTOCOL-FLOW-EXT-QUERY
+
+/// A query whose rows are fetched from the server in batches of a fixed size, only when
+/// they are asked for.
+///
+/// Created by [`PgConnection::portal`]. The server runs the query until it produced a batch
+/// of rows and then waits for the next [`next_batch`][Self::next_batch] call, so that the
+/// rows of a huge result set are not all sent at once, faster than they can be processed.
+///
+/// Outside of a transaction, the query runs in an implicit transaction which stays open
+/// until the portal is completed, closed or dropped. The connection cannot be used for
+/// anything else in the meantime; a portal that is dropped before it is completed is closed
+/// the next time the connection is used.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use sqlx_core_oldapi::error::Error;
+/// # use sqlx_core_oldapi::postgres::PgConnection;
+/// # use sqlx_core_oldapi::query::query;
+/// # async fn _f(conn: &mut PgConnection) -> Result<(), Error> {
+/// let mut portal = conn.portal(query("SELECT * FROM events"), 10_000).await?;
+///
+/// loop {
+///     let rows = portal.next_batch().await?;
+///
+///     if rows.is_empty() {
+///         break;
+///     }
+///
+///     // the next rows are only computed by the server once these are processed
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct PgPortal<'c> {
+    conn: &'c mut PgConnection,
+    metadata: Arc<PgStatementMetadata>,
+    max_rows: u32,
+    done: bool,
+}
+
+impl PgConnection {
+    /// Start executing a query whose rows are fetched `max_rows` at a time with
+    /// [`PgPortal::next_batch`].
+    ///
+    /// A `max_rows` of `0` fetches all the rows in the first batch.
+    pub async fn portal<'q, E>(
+        &mut self,
+        mut query: E,
+        max_rows: u32,
+    ) -> Result<PgPortal<'_>, Error>
+    where
+        E: Execute<'q, Postgres>,
+    {
+        let sql = query.sql();
+        let metadata = query.statement().map(|s| Arc::clone(&s.metadata));
+        let mut arguments = query.take_arguments().unwrap_or_default();
+        let persistent = query.persistent();
+
+        self.wait_until_ready().await?;
+
+        let (statement, metadata) = self
+            .get_or_prepare(sql, &arguments.types, persistent, metadata)
+            .await?;
+
+        // patch holes created during encoding
+        arguments.apply_patches(self, &metadata.parameters).await?;
+
+        self.wait_until_ready().await?;
+
+        self.stream.write(Bind {
+            portal: None,
+            statement,
+            formats: &[PgValueFormat::Binary],
+            #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+            num_params: arguments.types.len() as i16,
+            params: &arguments.buffer,
+            result_formats: &[PgValueFormat::Binary],
+        });
+
+        // no `Sync` is sent until the portal is completed, as it would destroy the portal
+        // at the end of the implicit transaction
+        self.stream.write(Flush);
+        self.stream.flush().await?;
+
+        let mut portal = PgPortal {
+            conn: self,
+            metadata,
+            max_rows,
+            done: false,
+        };
+
+        let bound: Result<(), Error> = portal
+            .conn
+            .stream
+            .recv_expect(MessageFormat::BindComplete)
+            .await;
+
+        if let Err(error) = bound {
+            portal.recover().await?;
+            return Err(error);
+        }
+
+        Ok(portal)
+    }
+}
+
+impl<'c> PgPortal<'c> {
+    /// Returns `true` once all the rows of the query have been fetched.
+    pub fn is_completed(&self) -> bool {
+        self.done
+    }
+
+    /// Fetch the next batch of rows, resuming the query where the previous batch stopped.
+    ///
+    /// Returns an empty batch once all the rows have been fetched.
+    pub async fn next_batch(&mut self) -> Result<Vec<PgRow>, Error> {
+        if self.done {
+            return Ok(Vec::new());
+        }
+
+        self.conn.stream.write(message::Execute {
+            portal: None,
+            limit: self.max_rows,
+        });
+        self.conn.stream.write(Flush);
+
+        match self.recv_batch().await {
+            Ok(rows) => Ok(rows),
+
+            Err(error) => {
+                self.recover().await?;
+                Err(error)
+            }
+        }
+    }
+
+    /// Close the portal before all its rows were fetched, and make the connection usable
+    /// again.
+    pub async fn close(mut self) -> Result<(), Error> {
+        if !self.done {
+            self.finish();
+        }
+
+        self.conn.wait_until_ready().await
+    }
+
+    /// Turn this into a [`Stream`] of the rows of the query, fetching the next batch when
+    /// the rows of the previous one have all been consumed.
+    pub fn into_stream(mut self) -> impl Stream<Item = Result<PgRow, Error>> + 'c {
+        Box::pin(try_stream! {
+            loop {
+                let rows = self.next_batch().await?;
+
+                if rows.is_empty() {
+                    break;
+                }
+
+                for row in rows {
+                    r#yield!(row);
+                }
+            }
+
+            Ok(())
+        })
+    }
+
+    async fn recv_batch(&mut self) -> Result<Vec<PgRow>, Error> {
+        self.conn.stream.flush().await?;
+
+        let mut rows = Vec::new();
+
+        loop {
+            let message = self.conn.stream.recv().await?;
+
+            match message.format {
+                MessageFormat::DataRow => {
+                    let data: DataRow = message.decode()?;
+
+                    rows.push(PgRow {
+                        data,
+                        format: PgValueFormat::Binary,
+                        metadata: Arc::clone(&self.metadata),
+                    });
+                }
+
+                // the batch is complete, the rest of the rows are fetched by the next `Execute`
+                MessageFormat::PortalSuspended => {
+                    return Ok(rows);
+                }
+
+                MessageFormat::CommandComplete | MessageFormat::EmptyQueryResponse => {
+                    self.finish();
+                    self.conn.wait_until_ready().await?;
+
+                    return Ok(rows);
+                }
+
+                _ => {
+                    return Err(err_protocol!(
+                        "portal: unexpected message: {:?}",
+                        message.format
+                    ));
+                }
+            }
+        }
+    }
+
+    // close the portal and end the implicit transaction, the `ReadyForQuery` is received the
+    // next time the connection waits until it is ready
+    fn finish(&mut self) {
+        self.done = true;
+
+        self.conn.stream.write(Close::Portal(None));
+        self.conn.write_sync();
+    }
+
+    // after an error, the server discards the messages it receives until the next `Sync`
+    async fn recover(&mut self) -> Result<(), Error> {
+        self.done = true;
+
+        self.conn.write_sync();
+        self.conn.wait_until_ready().await
+    }
+}
+
+impl Drop for PgPortal<'_> {
+    fn drop(&mut self) {
+        if !self.done {
+            self.finish();
+        }
+    }
+}
+
+impl Debug for PgPortal<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PgPortal")
+            .field("max_rows", &self.max_rows)
+            .field("completed", &self.done)
+            .finish()
+    }
+}
@@ -11,7 +11,8 @@ use crate::error::Error;
 use crate::io::{BufStream, Decode, Encode};
 use crate::net::{MaybeTlsStream, Socket};
 use crate::postgres::message::{Message, MessageFormat, Notice, Notification, ParameterStatus};
-use crate::postgres::{PgConnectOptions, PgDatabaseError, PgSeverity};
+use crate::postgres::notice::PgNoticeHandler;
+use crate::postgres::{PgConnectOptions, PgDatabaseError, PgNotice, PgSeverity};
 
 // the stream is a separate type from the connection to uphold the invariant where an instantiated
 // [PgConnection] is a **valid** connection to postgres
@@ -32,6 +33,8 @@ pub struct PgStream {
 
     pub(crate) parameter_statuses: BTreeMap<String, String>,
 
+    notice_handler: Option<PgNoticeHandler>,
+
     pub(crate) server_version_num: Option<u32>,
 }
 
@@ -48,6 +51,7 @@ impl PgStream {
             inner,
             notifications: None,
             parameter_statuses: BTreeMap::default(),
+            notice_handler: options.notice_handler.clone(),
             server_version_num: None,
         })
     }
@@ -119,15 +123,12 @@ impl PgStream {
                     let ParameterStatus { name, value } = message.decode()?;
                     // TODO: handle `client_encoding`, `DateStyle` change
 
-                    match name.as_str() {
-                        "server_version" => {
-                            self.server_version_num = parse_server_version(&value);
-                        }
-                        _ => {
-                            self.parameter_statuses.insert(name, value);
-                        }
+                    if name == "server_version" {
+                        self.server_version_num = parse_server_version(&value);
                     }
 
+                    self.parameter_statuses.insert(name, value);
+
                     continue;
                 }
 
@@ -159,6 +160,10 @@ impl PgStream {
                         );
                     }
 
+                    if let Some(PgNoticeHandler(handler)) = &self.notice_handler {
+                        handler(&PgNotice(notice));
+                    }
+
                     continue;
                 }
 
@@ -1,16 +1,33 @@
-use crate::error::{Error, Result};
+use crate::encode::Encode;
+use crate::error::{mismatched_types, Error, Result};
+use crate::executor::Executor;
 use crate::ext::async_stream::TryAsyncStream;
+use crate::from_row::FromRow;
+use crate::io::Decode;
 use crate::pool::{Pool, PoolConnection};
 use crate::postgres::connection::PgConnection;
 use crate::postgres::message::{
-    CommandComplete, CopyData, CopyDone, CopyFail, CopyResponse, MessageFormat, Query,
+    CommandComplete, CopyData, CopyDone, CopyFail, CopyResponse, DataRow, MessageFormat, Query,
 };
-use crate::postgres::Postgres;
-use bytes::{BufMut, Bytes};
+use crate::postgres::quote::quote_identifier;
+use crate::postgres::{PgArguments, PgRow, PgTypeInfo, PgValueFormat, Postgres};
+use crate::query_scalar::query_scalar;
+use crate::types::Type;
+use byteorder::{BigEndian, ByteOrder};
+use bytes::{Buf, BufMut, Bytes, BytesMut};
 use futures_core::stream::BoxStream;
+use futures_core::Stream;
+use futures_util::{pin_mut, StreamExt, TryStreamExt};
 use smallvec::alloc::borrow::Cow;
 use sqlx_rt::{AsyncRead, AsyncReadExt, AsyncWriteExt};
 use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+
+// https://www.postgresql.org/docs/current/sql-copy.html#id-1.9.3.55.9.4
+const BINARY_SIGNATURE: &[u8] = b"PGCOPY\n\xff\r\n\0";
+
+// rows are sent in `CopyData` messages of about this size
+const BINARY_CHUNK_SIZE: usize = 64 * 1024;
 
 impl PgConnection {
     /// Issue a `COPY FROM STDIN` statement and transition the connection to streaming data
@@ -30,6 +47,54 @@ impl PgConnection {
         PgCopyIn::begin(self, statement).await
     }
 
+    /// Issue a binary `COPY FROM STDIN` statement and send `rows` to Postgres, encoding each
+    /// field with its [`Encode`] implementation.
+    ///
+    /// `statement` must be a `COPY ... FROM STDIN WITH (FORMAT binary)` command. See
+    /// [`PgCopyIn::send_rows`] for the rows that are accepted.
+    ///
+    /// If sending the rows fails, the `COPY` is aborted and no rows are inserted. Otherwise the
+    /// number of rows inserted is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use sqlx_core_oldapi::error::Error;
+    /// # use sqlx_core_oldapi::postgres::PgConnection;
+    /// # async fn _f(conn: &mut PgConnection) -> Result<(), Error> {
+    /// let rows = vec![(1_i32, "apple"), (2, "banana")];
+    ///
+    /// let inserted = conn
+    ///     .copy_in_rows("COPY fruits (id, name) FROM STDIN WITH (FORMAT binary)", &rows)
+    ///     .await?;
+    ///
+    /// assert_eq!(inserted, 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn copy_in_rows<I>(&mut self, statement: &str, rows: I) -> Result<u64>
+    where
+        I: IntoIterator,
+        I::Item: PgCopyRow,
+    {
+        let copy = PgCopyIn::begin_rows(self, statement).await?;
+        copy.send_rows_and_finish(rows).await
+    }
+
+    /// Issue a binary `COPY FROM STDIN` statement and send the rows produced by `rows` to
+    /// Postgres.
+    ///
+    /// This is the same as [`copy_in_rows`][Self::copy_in_rows], for rows that are produced
+    /// asynchronously.
+    pub async fn copy_in_row_stream<S>(&mut self, statement: &str, rows: S) -> Result<u64>
+    where
+        S: Stream,
+        S::Item: PgCopyRow,
+    {
+        let copy = PgCopyIn::begin_rows(self, statement).await?;
+        copy.send_row_stream_and_finish(rows).await
+    }
+
     /// Issue a `COPY TO STDOUT` statement and transition the connection to streaming data
     /// from Postgres. This is a more efficient way to export data from Postgres but
     /// arrives in chunks of one of a few data formats (text/CSV/binary).
@@ -56,6 +121,42 @@ impl PgConnection {
     ) -> Result<BoxStream<'c, Result<Bytes>>> {
         pg_begin_copy_out(self, statement).await
     }
+
+    /// Export the results of `query` with a binary `COPY TO STDOUT`, decoding each row into `T`.
+    ///
+    /// This runs `COPY (query) TO STDOUT (FORMAT binary)`, which streams rows with less overhead
+    /// than [`fetch`][crate::executor::Executor::fetch]. `query` is described first to learn the
+    /// names and types of its columns, so rows can be decoded into tuples or [`FromRow`] types
+    /// exactly as the rows of a query would be. `query` can not have bind parameters.
+    ///
+    /// As with [`copy_out_raw`][Self::copy_out_raw], if you don't read the stream to completion,
+    /// the next time the connection is used it will need to read and discard all the remaining
+    /// queued data.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use sqlx_core_oldapi::error::Error;
+    /// # use sqlx_core_oldapi::postgres::PgConnection;
+    /// use futures_util::TryStreamExt;
+    ///
+    /// # async fn _f(conn: &mut PgConnection) -> Result<(), Error> {
+    /// let mut rows = conn
+    ///     .copy_out_rows::<(i32, String)>("SELECT id, name FROM fruits")
+    ///     .await?;
+    ///
+    /// while let Some((id, name)) = rows.try_next().await? {
+    ///     println!("{}: {}", id, name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn copy_out_rows<'c, T>(&'c mut self, query: &str) -> Result<BoxStream<'c, Result<T>>>
+    where
+        T: for<'r> FromRow<'r, PgRow> + Send + 'c,
+    {
+        pg_begin_copy_out_rows(self, query).await
+    }
 }
 
 impl Pool<Postgres> {
@@ -78,6 +179,36 @@ impl Pool<Postgres> {
         PgCopyIn::begin(self.acquire().await?, statement).await
     }
 
+    /// Issue a binary `COPY FROM STDIN` statement and send `rows` to Postgres, encoding each
+    /// field with its [`Encode`] implementation.
+    ///
+    /// A single connection will be checked out for the duration.
+    ///
+    /// See [`PgConnection::copy_in_rows`] for details.
+    pub async fn copy_in_rows<I>(&self, statement: &str, rows: I) -> Result<u64>
+    where
+        I: IntoIterator,
+        I::Item: PgCopyRow,
+    {
+        let copy = PgCopyIn::begin_rows(self.acquire().await?, statement).await?;
+        copy.send_rows_and_finish(rows).await
+    }
+
+    /// Issue a binary `COPY FROM STDIN` statement and send the rows produced by `rows` to
+    /// Postgres.
+    ///
+    /// A single connection will be checked out for the duration.
+    ///
+    /// See [`PgConnection::copy_in_row_stream`] for details.
+    pub async fn copy_in_row_stream<S>(&self, statement: &str, rows: S) -> Result<u64>
+    where
+        S: Stream,
+        S::Item: PgCopyRow,
+    {
+        let copy = PgCopyIn::begin_rows(self.acquire().await?, statement).await?;
+        copy.send_row_stream_and_finish(rows).await
+    }
+
     /// Issue a `COPY TO STDOUT` statement and begin streaming data
     /// from Postgres. This is a more efficient way to export data from Postgres but
     /// arrives in chunks of one of a few data formats (text/CSV/binary).
@@ -100,6 +231,18 @@ impl Pool<Postgres> {
     pub async fn copy_out_raw(&self, statement: &str) -> Result<BoxStream<'static, Result<Bytes>>> {
         pg_begin_copy_out(self.acquire().await?, statement).await
     }
+
+    /// Export the results of `query` with a binary `COPY TO STDOUT`, decoding each row into `T`.
+    ///
+    /// A single connection will be checked out for the duration.
+    ///
+    /// See [`PgConnection::copy_out_rows`] for details.
+    pub async fn copy_out_rows<T>(&self, query: &str) -> Result<BoxStream<'static, Result<T>>>
+    where
+        T: for<'r> FromRow<'r, PgRow> + Send + 'static,
+    {
+        pg_begin_copy_out_rows(self.acquire().await?, query).await
+    }
 }
 
 /// A connection in streaming `COPY FROM STDIN` mode.
@@ -113,6 +256,10 @@ impl Pool<Postgres> {
 pub struct PgCopyIn<C: DerefMut<Target = PgConnection>> {
     conn: Option<C>,
     response: CopyResponse,
+    // the binary format has a header before the first row and a trailer after the last one
+    binary_header_sent: bool,
+    // the types of the columns being copied, if they were looked up before the `COPY` started
+    column_types: Option<Arc<[PgTypeInfo]>>,
 }
 
 impl<C: DerefMut<Target = PgConnection>> PgCopyIn<C> {
@@ -122,6 +269,8 @@ impl<C: DerefMut<Target = PgConnection>> PgCopyIn<C> {
             Ok(response) => Ok(PgCopyIn {
                 conn: Some(conn),
                 response,
+                binary_header_sent: false,
+                column_types: None,
             }),
             Err(e) => {
                 conn.stream
@@ -135,6 +284,40 @@ impl<C: DerefMut<Target = PgConnection>> PgCopyIn<C> {
         }
     }
 
+    // Like `begin`, but first looks up the types of the columns being copied: some values, like
+    // `Json<T>`, are encoded differently depending on the type of their column, and the
+    // connection can not be asked for it once the `COPY` has started
+    async fn begin_rows(mut conn: C, statement: &str) -> Result<Self> {
+        let column_types = match copy_target(statement) {
+            Some((table, columns)) => {
+                let columns = match columns {
+                    Some(columns) => columns.to_owned(),
+                    None => copied_columns(conn.deref_mut(), table).await?,
+                };
+
+                let query = format!("SELECT {} FROM {}", columns, table);
+                let statement = conn.deref_mut().prepare(&query).await?;
+                let types: Arc<[PgTypeInfo]> = statement
+                    .metadata
+                    .columns
+                    .iter()
+                    .map(|column| column.type_info.clone())
+                    .collect();
+
+                Some(types)
+            }
+
+            None => None,
+        };
+
+        let mut copy = Self::begin(conn, statement).await?;
+
+        let num_columns = copy.num_columns();
+        copy.column_types = column_types.filter(|types| types.len() == num_columns);
+
+        Ok(copy)
+    }
+
     async fn start_copy(mut conn: C, statement: &str) -> Result<C> {
         conn.wait_until_ready().await?;
         conn.stream.send(Query(statement)).await?;
@@ -180,6 +363,178 @@ impl<C: DerefMut<Target = PgConnection>> PgCopyIn<C> {
         Ok(self)
     }
 
+    /// Send rows in the binary `COPY` format, encoding each field with its [`Encode`]
+    /// implementation.
+    ///
+    /// The `COPY` statement must use `FORMAT binary` and each row must have one field per
+    /// column being copied, or an error is returned. Rows are [`PgCopyRow`]s: tuples, or
+    /// structs deriving `PgCopyRow`.
+    ///
+    /// The binary format does not include the types of the fields: Postgres decodes each field
+    /// as the type of its column, and fails the `COPY` if the encoded value does not match it.
+    ///
+    /// A few types, like [`Json`][crate::types::Json], are encoded differently depending on the
+    /// type of their column. Rows with such fields can only be sent when the `COPY` was started
+    /// by [`PgConnection::copy_in_rows`] or [`PgConnection::copy_in_row_stream`], which look up
+    /// the column types of a `COPY table [(columns)] FROM STDIN` statement before starting it;
+    /// otherwise an error is returned.
+    ///
+    /// This can be called several times, but cannot be mixed with raw data sent with
+    /// [`send`][Self::send] or [`read_from`][Self::read_from].
+    ///
+    /// ### Note
+    /// You must still call either [Self::finish] or [Self::abort] to complete the process.
+    pub async fn send_rows<I>(&mut self, rows: I) -> Result<&mut Self>
+    where
+        I: IntoIterator,
+        I::Item: PgCopyRow,
+    {
+        self.start_binary().await?;
+
+        let mut buf = Vec::new();
+
+        for row in rows {
+            self.encode_row(&row, &mut buf)?;
+
+            if buf.len() >= BINARY_CHUNK_SIZE {
+                self.send(std::mem::take(&mut buf)).await?;
+            }
+        }
+
+        if !buf.is_empty() {
+            self.send(buf).await?;
+        }
+
+        Ok(self)
+    }
+
+    /// Send the rows produced by `rows` in the binary `COPY` format.
+    ///
+    /// See [`send_rows`][Self::send_rows] for details.
+    pub async fn send_row_stream<S>(&mut self, rows: S) -> Result<&mut Self>
+    where
+        S: Stream,
+        S::Item: PgCopyRow,
+    {
+        self.start_binary().await?;
+
+        pin_mut!(rows);
+
+        let mut buf = Vec::new();
+
+        while let Some(row) = rows.next().await {
+            self.encode_row(&row, &mut buf)?;
+
+            if buf.len() >= BINARY_CHUNK_SIZE {
+                self.send(std::mem::take(&mut buf)).await?;
+            }
+        }
+
+        if !buf.is_empty() {
+            self.send(buf).await?;
+        }
+
+        Ok(self)
+    }
+
+    async fn start_binary(&mut self) -> Result<()> {
+        if self.is_textual() || (0..self.num_columns()).any(|i| self.column_is_textual(i)) {
+            return Err(err_protocol!(
+                "rows can only be sent to a `COPY` in binary format, add `WITH (FORMAT binary)`"
+            ));
+        }
+
+        if !self.binary_header_sent {
+            let mut header = Vec::with_capacity(BINARY_SIGNATURE.len() + 8);
+            header.extend_from_slice(BINARY_SIGNATURE);
+            // flags, then the length of the header extension area
+            header.put_i32(0);
+            header.put_i32(0);
+
+            self.send(header).await?;
+            self.binary_header_sent = true;
+        }
+
+        Ok(())
+    }
+
+    fn encode_row(&self, row: &impl PgCopyRow, buf: &mut Vec<u8>) -> Result<()> {
+        let mut encoder = PgCopyRowEncoder {
+            arguments: PgArguments::default(),
+            column_types: self.column_types.clone(),
+            error: None,
+        };
+
+        row.encode_row(&mut encoder);
+
+        if let Some(error) = encoder.error {
+            return Err(error);
+        }
+
+        let mut arguments = encoder.arguments;
+        let num_columns = self.num_columns();
+
+        if arguments.types.len() != num_columns {
+            return Err(err_protocol!(
+                "`COPY` expects rows of {} columns but got a row of {} fields",
+                num_columns,
+                arguments.types.len()
+            ));
+        }
+
+        if arguments.has_patches() {
+            let types = self.column_types.as_deref().ok_or_else(|| {
+                err_protocol!(
+                    "the types of the columns being copied are unknown, \
+                     use `copy_in_rows` with a `COPY table (columns) FROM STDIN` statement"
+                )
+            })?;
+
+            arguments.apply_value_patches(types);
+        }
+
+        let conn: &PgConnection = self.conn.as_deref().expect("encode_row: conn taken");
+        arguments.apply_cached_type_holes(conn)?;
+
+        #[allow(clippy::cast_possible_truncation)]
+        buf.put_i16(num_columns as i16);
+        buf.extend_from_slice(&arguments.buffer);
+
+        Ok(())
+    }
+
+    async fn send_rows_and_finish<I>(mut self, rows: I) -> Result<u64>
+    where
+        I: IntoIterator,
+        I::Item: PgCopyRow,
+    {
+        let sent = self.send_rows(rows).await.map(|_| ());
+
+        match sent {
+            Ok(()) => self.finish().await,
+            Err(e) => {
+                self.abort(e.to_string()).await?;
+                Err(e)
+            }
+        }
+    }
+
+    async fn send_row_stream_and_finish<S>(mut self, rows: S) -> Result<u64>
+    where
+        S: Stream,
+        S::Item: PgCopyRow,
+    {
+        let sent = self.send_row_stream(rows).await.map(|_| ());
+
+        match sent {
+            Ok(()) => self.finish().await,
+            Err(e) => {
+                self.abort(e.to_string()).await?;
+                Err(e)
+            }
+        }
+    }
+
     /// Copy data directly from `source` to the database without requiring an intermediate buffer.
     ///
     /// `source` will be read to the end.
@@ -289,6 +644,11 @@ impl<C: DerefMut<Target = PgConnection>> PgCopyIn<C> {
             .take()
             .expect("CopyWriter::finish: conn taken illegally");
 
+        if self.binary_header_sent {
+            // the trailer is a field count of -1
+            conn.stream.write(CopyData(&(-1_i16).to_be_bytes()[..]));
+        }
+
         conn.stream.send(CopyDone).await?;
         let cc: CommandComplete = conn
             .stream
@@ -313,6 +673,112 @@ impl<C: DerefMut<Target = PgConnection>> Drop for PgCopyIn<C> {
     }
 }
 
+/// A row of data for a binary `COPY FROM STDIN`, sent with [`PgCopyIn::send_rows`] or
+/// [`PgConnection::copy_in_rows`].
+///
+/// This is implemented for tuples of up to 16 values implementing [`Encode`] and [`Type`],
+/// and can be derived for structs with `#[derive(PgCopyRow)]`, which encodes their fields in
+/// the order they are declared. Every field is encoded: the derive does not accept
+/// `#[sqlx(...)]` attributes on fields.
+pub trait PgCopyRow {
+    /// Encode the fields of this row, in the order of the columns of the `COPY` statement.
+    fn encode_row(&self, row: &mut PgCopyRowEncoder);
+}
+
+/// Encodes the fields of a [`PgCopyRow`].
+pub struct PgCopyRowEncoder {
+    arguments: PgArguments,
+    // the types of the columns being copied, if known, to check the fields against
+    column_types: Option<Arc<[PgTypeInfo]>>,
+    // the first field that could not be encoded, reported once the row is complete
+    error: Option<Error>,
+}
+
+impl PgCopyRowEncoder {
+    /// Encode the next field of the row.
+    ///
+    /// If the type of the column is known and `T` is not compatible with it, sending the row
+    /// fails: unlike a bind parameter, binary `COPY` data is never converted to the type of
+    /// its column.
+    pub fn encode<'q, T>(&mut self, value: T) -> &mut Self
+    where
+        T: Encode<'q, Postgres> + Type<Postgres>,
+    {
+        let index = self.arguments.types.len();
+
+        if self.error.is_none() {
+            if let Some(ty) = self
+                .column_types
+                .as_deref()
+                .and_then(|types| types.get(index))
+            {
+                if !T::compatible(ty) {
+                    self.error = Some(err_protocol!(
+                        "field {} of the `COPY` row can not be encoded: {}",
+                        index,
+                        mismatched_types::<Postgres, T>(ty)
+                    ));
+                }
+            }
+        }
+
+        self.arguments.add(value);
+        self
+    }
+}
+
+impl<R: PgCopyRow + ?Sized> PgCopyRow for &'_ R {
+    fn encode_row(&self, row: &mut PgCopyRowEncoder) {
+        (**self).encode_row(row)
+    }
+}
+
+macro_rules! impl_copy_row_for_tuple {
+    ($( $idx:tt : $T:ident ),+) => {
+        impl<$($T,)+> PgCopyRow for ($($T,)+)
+        where
+            $($T: for<'q> Encode<'q, Postgres> + Type<Postgres>,)+
+        {
+            fn encode_row(&self, row: &mut PgCopyRowEncoder) {
+                $(row.encode(&self.$idx);)+
+            }
+        }
+    };
+}
+
+impl_copy_row_for_tuple!(0: T1);
+impl_copy_row_for_tuple!(0: T1, 1: T2);
+impl_copy_row_for_tuple!(0: T1, 1: T2, 2: T3);
+impl_copy_row_for_tuple!(0: T1, 1: T2, 2: T3, 3: T4);
+impl_copy_row_for_tuple!(0: T1, 1: T2, 2: T3, 3: T4, 4: T5);
+impl_copy_row_for_tuple!(0: T1, 1: T2, 2: T3, 3: T4, 4: T5, 5: T6);
+impl_copy_row_for_tuple!(0: T1, 1: T2, 2: T3, 3: T4, 4: T5, 5: T6, 6: T7);
+impl_copy_row_for_tuple!(0: T1, 1: T2, 2: T3, 3: T4, 4: T5, 5: T6, 6: T7, 7: T8);
+impl_copy_row_for_tuple!(0: T1, 1: T2, 2: T3, 3: T4, 4: T5, 5: T6, 6: T7, 7: T8, 8: T9);
+impl_copy_row_for_tuple!(0: T1, 1: T2, 2: T3, 3: T4, 4: T5, 5: T6, 6: T7, 7: T8, 8: T9, 9: T10);
+impl_copy_row_for_tuple!(
+    0: T1, 1: T2, 2: T3, 3: T4, 4: T5, 5: T6, 6: T7, 7: T8, 8: T9, 9: T10, 10: T11
+);
+impl_copy_row_for_tuple!(
+    0: T1, 1: T2, 2: T3, 3: T4, 4: T5, 5: T6, 6: T7, 7: T8, 8: T9, 9: T10, 10: T11, 11: T12
+);
+impl_copy_row_for_tuple!(
+    0: T1, 1: T2, 2: T3, 3: T4, 4: T5, 5: T6, 6: T7, 7: T8, 8: T9, 9: T10, 10: T11, 11: T12,
+    12: T13
+);
+impl_copy_row_for_tuple!(
+    0: T1, 1: T2, 2: T3, 3: T4, 4: T5, 5: T6, 6: T7, 7: T8, 8: T9, 9: T10, 10: T11, 11: T12,
+    12: T13, 13: T14
+);
+impl_copy_row_for_tuple!(
+    0: T1, 1: T2, 2: T3, 3: T4, 4: T5, 5: T6, 6: T7, 7: T8, 8: T9, 9: T10, 10: T11, 11: T12,
+    12: T13, 13: T14, 14: T15
+);
+impl_copy_row_for_tuple!(
+    0: T1, 1: T2, 2: T3, 3: T4, 4: T5, 5: T6, 6: T7, 7: T8, 8: T9, 9: T10, 10: T11, 11: T12,
+    12: T13, 13: T14, 14: T15, 15: T16
+);
+
 async fn pg_begin_copy_out<'c, C: DerefMut<Target = PgConnection> + Send + 'c>(
     mut conn: C,
     statement: &str,
@@ -320,6 +786,10 @@ async fn pg_begin_copy_out<'c, C: DerefMut<Target = PgConnection> + Send + 'c>(
     conn.wait_until_ready().await?;
     conn.stream.send(Query(statement)).await?;
 
+    // the query ends with `ReadyForQuery` even if it fails or its data is not read to the end,
+    // in which case the connection waits for it the next time it is used
+    conn.pending_ready_for_query_count += 1;
+
     let _: CopyResponse = conn
         .stream
         .recv_expect(MessageFormat::CopyOutResponse)
@@ -334,6 +804,7 @@ async fn pg_begin_copy_out<'c, C: DerefMut<Target = PgConnection> + Send + 'c>(
                     let _ = msg.decode::<CopyDone>()?;
                     conn.stream.recv_expect::<CommandComplete>(MessageFormat::CommandComplete).await?;
                     conn.stream.recv_expect::<()>(MessageFormat::ReadyForQuery).await?;
+                    conn.pending_ready_for_query_count -= 1;
                     return Ok(())
                 },
                 _ => return Err(err_protocol!("unexpected message format during copy out: {:?}", msg.format))
@@ -343,3 +814,309 @@ async fn pg_begin_copy_out<'c, C: DerefMut<Target = PgConnection> + Send + 'c>(
 
     Ok(Box::pin(stream))
 }
+
+async fn pg_begin_copy_out_rows<'c, C, T>(
+    mut conn: C,
+    query: &str,
+) -> Result<BoxStream<'c, Result<T>>>
+where
+    C: DerefMut<Target = PgConnection> + Send + 'c,
+    T: for<'r> FromRow<'r, PgRow> + Send + 'c,
+{
+    // the binary format does not say what the columns are, the query does
+    let metadata = Arc::clone(&conn.deref_mut().prepare(query).await?.metadata);
+
+    let statement = format!("COPY ({}) TO STDOUT (FORMAT binary)", query);
+    let mut chunks = pg_begin_copy_out(conn, &statement).await?;
+
+    let stream: TryAsyncStream<'c, T> = try_stream! {
+        let mut buf = BytesMut::new();
+        let mut header_read = false;
+        let mut trailer_read = false;
+
+        while let Some(chunk) = chunks.try_next().await? {
+            if trailer_read {
+                return Err(err_protocol!("unexpected data after the end of binary COPY data"));
+            }
+
+            buf.extend_from_slice(&chunk);
+
+            if !header_read {
+                header_read = take_binary_header(&mut buf)?;
+
+                if !header_read {
+                    continue;
+                }
+            }
+
+            loop {
+                let data = match take_binary_tuple(&mut buf)? {
+                    Some(BinaryTuple::Row(data)) => data,
+                    Some(BinaryTuple::End) => {
+                        trailer_read = true;
+                        break;
+                    }
+                    None => break,
+                };
+
+                let row = PgRow {
+                    data: DataRow::decode(data)?,
+                    format: PgValueFormat::Binary,
+                    metadata: Arc::clone(&metadata),
+                };
+
+                if row.data.values.len() != metadata.columns.len() {
+                    return Err(err_protocol!(
+                        "expected {} columns in binary COPY data but got {}",
+                        metadata.columns.len(),
+                        row.data.values.len()
+                    ));
+                }
+
+                let value = T::from_row(&row)?;
+                r#yield!(value);
+            }
+        }
+
+        if !trailer_read {
+            return Err(err_protocol!("binary COPY data ended without a trailer"));
+        }
+
+        Ok(())
+    };
+
+    Ok(Box::pin(stream))
+}
+
+// Split `COPY table [(columns)] FROM ...` into the table and the list of columns being copied,
+// so their types can be looked up. Returns `None` for any other statement.
+fn copy_target(statement: &str) -> Option<(&str, Option<&str>)> {
+    let rest = strip_keyword(statement.trim_start(), "COPY")?;
+    let (table, rest) = take_qualified_name(rest.trim_start())?;
+    let rest = rest.trim_start();
+
+    let (columns, rest) = match rest.strip_prefix('(') {
+        Some(list) => {
+            let end = closing_paren(list)?;
+            (Some(list[..end].trim()), &list[end + 1..])
+        }
+        None => (None, rest),
+    };
+
+    strip_keyword(rest.trim_start(), "FROM")?;
+
+    Some((table, columns))
+}
+
+// The columns copied by `COPY table FROM` without a column list: unlike `SELECT *`, this leaves
+// out generated columns
+async fn copied_columns(conn: &mut PgConnection, table: &str) -> Result<String> {
+    // generated columns were added in Postgres 12
+    let not_generated = if !matches!(conn.server_version_num(), Some(v) if v < 120000) {
+        "AND attgenerated = ''"
+    } else {
+        ""
+    };
+
+    let sql = format!(
+        "SELECT attname::text FROM pg_catalog.pg_attribute \
+         WHERE attrelid = $1::regclass AND attnum > 0 AND NOT attisdropped {} \
+         ORDER BY attnum",
+        not_generated
+    );
+
+    let names: Vec<String> = query_scalar(&sql).bind(table).fetch_all(conn).await?;
+
+    Ok(names
+        .iter()
+        .map(|name| quote_identifier(name))
+        .collect::<Vec<_>>()
+        .join(", "))
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
+}
+
+fn strip_keyword<'a>(s: &'a str, keyword: &str) -> Option<&'a str> {
+    let head = s.get(..keyword.len())?;
+    let rest = &s[keyword.len()..];
+
+    if head.eq_ignore_ascii_case(keyword) && !rest.starts_with(is_identifier_char) {
+        Some(rest)
+    } else {
+        None
+    }
+}
+
+// Split a name that may be quoted and qualified by its schema off the start of `s`
+fn take_qualified_name(s: &str) -> Option<(&str, &str)> {
+    let mut end = 0;
+
+    loop {
+        let part = &s[end..];
+        let len = if part.starts_with('"') {
+            quoted_len(part)?
+        } else {
+            part.find(|c| !is_identifier_char(c)).unwrap_or(part.len())
+        };
+
+        if len == 0 {
+            return None;
+        }
+
+        end += len;
+
+        if s[end..].starts_with('.') {
+            end += 1;
+        } else {
+            return Some((&s[..end], &s[end..]));
+        }
+    }
+}
+
+// The length of the quoted identifier at the start of `s`, in which quotes are doubled
+fn quoted_len(s: &str) -> Option<usize> {
+    let mut len = 1;
+
+    loop {
+        len += s[len..].find('"')? + 1;
+
+        if s[len..].starts_with('"') {
+            len += 1;
+        } else {
+            return Some(len);
+        }
+    }
+}
+
+// The position of the `)` closing a column list, skipping over quoted column names
+fn closing_paren(s: &str) -> Option<usize> {
+    let mut i = 0;
+
+    while let Some(c) = s[i..].chars().next() {
+        match c {
+            ')' => return Some(i),
+            '"' => i += quoted_len(&s[i..])?,
+            _ => i += c.len_utf8(),
+        }
+    }
+
+    None
+}
+
+enum BinaryTuple {
+    Row(Bytes),
+    End,
+}
+
+// Remove the header of binary `COPY` data from `buf`, returning `false` if it is incomplete.
+fn take_binary_header(buf: &mut BytesMut) -> Result<bool> {
+    let fixed_len = BINARY_SIGNATURE.len() + 8;
+
+    if buf.len() < fixed_len {
+        return Ok(false);
+    }
+
+    if &buf[..BINARY_SIGNATURE.len()] != BINARY_SIGNATURE {
+        return Err(err_protocol!("invalid signature in binary COPY data"));
+    }
+
+    // flags, then the length of the header extension area
+    let extension_len = BigEndian::read_u32(&buf[(fixed_len - 4)..]) as usize;
+
+    if buf.len() < fixed_len + extension_len {
+        return Ok(false);
+    }
+
+    buf.advance(fixed_len + extension_len);
+
+    Ok(true)
+}
+
+// Remove the next tuple of binary `COPY` data from `buf`, in the same layout as a `DataRow`,
+// returning `None` if it is incomplete.
+fn take_binary_tuple(buf: &mut BytesMut) -> Result<Option<BinaryTuple>> {
+    if buf.len() < 2 {
+        return Ok(None);
+    }
+
+    let num_fields = BigEndian::read_i16(buf);
+
+    if num_fields == -1 {
+        buf.advance(2);
+        return Ok(Some(BinaryTuple::End));
+    }
+
+    let num_fields = u16::try_from(num_fields)
+        .map_err(|_| err_protocol!("invalid field count in binary COPY data: {}", num_fields))?;
+
+    let mut len = 2;
+
+    for _ in 0..num_fields {
+        if buf.len() < len + 4 {
+            return Ok(None);
+        }
+
+        // -1 is NULL, with no data following
+        let field_len = BigEndian::read_i32(&buf[len..]);
+        len += 4 + usize::try_from(field_len).unwrap_or(0);
+    }
+
+    if buf.len() < len {
+        return Ok(None);
+    }
+
+    Ok(Some(BinaryTuple::Row(buf.split_to(len).freeze())))
+}
+
+#[test]
+fn test_take_binary_copy_data() {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(BINARY_SIGNATURE);
+    buf.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 0]);
+
+    // a row of two fields: the int4 `1` and NULL
+    buf.extend_from_slice(b"\x00\x02\x00\x00\x00\x04\x00\x00\x00\x01");
+
+    assert!(!take_binary_header(&mut BytesMut::from(&buf[..10])).unwrap());
+    assert!(take_binary_header(&mut buf).unwrap());
+
+    assert!(take_binary_tuple(&mut buf).unwrap().is_none());
+
+    buf.extend_from_slice(b"\xff\xff\xff\xff\xff\xff");
+
+    match take_binary_tuple(&mut buf).unwrap() {
+        Some(BinaryTuple::Row(data)) => {
+            let row = DataRow::decode(data).unwrap();
+            assert_eq!(row.get(0), Some(&[0, 0, 0, 1][..]));
+            assert_eq!(row.get(1), None);
+        }
+        _ => panic!("expected a row"),
+    }
+
+    assert!(matches!(
+        take_binary_tuple(&mut buf).unwrap(),
+        Some(BinaryTuple::End)
+    ));
+    assert!(buf.is_empty());
+}
+
+#[test]
+fn test_copy_target() {
+    assert_eq!(
+        copy_target("COPY fruits (id, name) FROM STDIN WITH (FORMAT binary)"),
+        Some(("fruits", Some("id, name")))
+    );
+    assert_eq!(
+        copy_target(r#"copy public."my ""fruits""" ("a)", b) from stdin"#),
+        Some((r#"public."my ""fruits""""#, Some(r#""a)", b"#)))
+    );
+    assert_eq!(
+        copy_target("COPY fruits FROM STDIN (FORMAT binary)"),
+        Some(("fruits", None))
+    );
+    assert_eq!(copy_target("COPY (SELECT 1) TO STDOUT"), None);
+    assert_eq!(copy_target("COPY fruits TO STDOUT"), None);
+    assert_eq!(copy_target("COPYfruits FROM STDIN"), None);
+}
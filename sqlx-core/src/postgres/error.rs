@@ -4,7 +4,7 @@ use std::fmt::{self, Debug, Display, Formatter};
 use atoi::atoi;
 use smallvec::alloc::borrow::Cow;
 
-use crate::error::DatabaseError;
+use crate::error::{DatabaseError, ErrorKind};
 use crate::postgres::message::{Notice, PgSeverity};
 
 /// An error returned from the PostgreSQL database.
@@ -203,7 +203,39 @@ impl DatabaseError for PgDatabaseError {
         .contains(&self.code())
     }
 
+    fn is_retryable_conflict(&self) -> bool {
+        // serialization_failure, deadlock_detected
+        ["40001", "40P01"].contains(&self.code())
+    }
+
+    fn kind(&self) -> ErrorKind {
+        // https://www.postgresql.org/docs/current/errcodes-appendix.html
+        match self.code() {
+            "23505" => ErrorKind::UniqueViolation,
+            "23503" => ErrorKind::ForeignKeyViolation,
+            "23502" => ErrorKind::NotNullViolation,
+            "23514" => ErrorKind::CheckViolation,
+            "40001" => ErrorKind::SerializationFailure,
+            "40P01" => ErrorKind::Deadlock,
+            // lock_not_available
+            "55P03" => ErrorKind::LockTimeout,
+            "57014" => ErrorKind::QueryCanceled,
+            // admin_shutdown, crash_shutdown, class 08: connection exception
+            "57P01" | "57P02" => ErrorKind::ConnectionLost,
+            code if code.starts_with("08") => ErrorKind::ConnectionLost,
+            _ => ErrorKind::Other,
+        }
+    }
+
     fn constraint(&self) -> Option<&str> {
         self.constraint()
     }
+
+    fn table(&self) -> Option<&str> {
+        self.table()
+    }
+
+    fn column(&self) -> Option<&str> {
+        self.column()
+    }
 }
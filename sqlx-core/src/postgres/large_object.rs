@@ -0,0 +1,453 @@
+use std::cmp;
+use std::fmt::{self, Debug, Formatter};
+use std::io::{self, SeekFrom};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use bytes::{Buf, Bytes};
+use futures_core::future::BoxFuture;
+use futures_util::{ready, FutureExt};
+use sqlx_rt::{AsyncRead, AsyncSeek, AsyncWrite, ReadBuf};
+
+use crate::error::Error;
+use crate::executor::Executor;
+use crate::postgres::types::Oid;
+use crate::postgres::{PgConnection, Postgres};
+use crate::query::query;
+use crate::query_scalar::query_scalar;
+use crate::transaction::Transaction;
+
+// https://www.postgresql.org/docs/current/lo-interfaces.html
+
+// https://github.com/postgres/postgres/blob/REL_15_0/src/include/libpq/libpq-fs.h
+const INV_WRITE: i32 = 0x0002_0000;
+const INV_READ: i32 = 0x0004_0000;
+
+// `whence` values of `lo_lseek64`, the same as for `lseek`
+const SEEK_SET: i32 = 0;
+const SEEK_END: i32 = 2;
+
+// reads and writes are sent in calls of `loread` / `lowrite` of at most this size
+const CHUNK_SIZE: usize = 256 * 1024;
+
+/// The mode a large object is opened with by [`PgLargeObject::open`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PgLargeObjectMode {
+    /// Open the large object for reading only (`INV_READ`).
+    ///
+    /// Reads see the contents of the large object as of the snapshot of the transaction that
+    /// was active when it was opened, regardless of later writes.
+    Read,
+
+    /// Open the large object for writing (`INV_WRITE`).
+    ///
+    /// The large object can also be read, and reads see the committed writes of other
+    /// transactions as well as the writes made through this handle.
+    Write,
+
+    /// Open the large object for reading and writing (`INV_READ | INV_WRITE`), which behaves
+    /// the same as [`Write`][Self::Write].
+    ReadWrite,
+}
+
+impl PgLargeObjectMode {
+    fn flags(self) -> i32 {
+        match self {
+            PgLargeObjectMode::Read => INV_READ,
+            PgLargeObjectMode::Write => INV_WRITE,
+            PgLargeObjectMode::ReadWrite => INV_READ | INV_WRITE,
+        }
+    }
+}
+
+/// A handle to an open [large object], stored in `pg_largeobject` and read and written in
+/// chunks, for streaming files to and from the database without holding them in memory.
+///
+/// Implements [`AsyncRead`], [`AsyncWrite`] and [`AsyncSeek`], on top of the `loread`,
+/// `lowrite` and `lo_lseek64` server-side functions.
+///
+/// A large object can only be opened inside a transaction, and its descriptor is closed at
+/// the end of the transaction if it was not [closed][Self::close] before. The handle borrows
+/// the transaction for as long as it is open.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use sqlx_core_oldapi::error::Error;
+/// # use sqlx_core_oldapi::connection::Connection;
+/// # use sqlx_core_oldapi::postgres::{PgConnection, PgLargeObject, PgLargeObjectMode};
+/// # async fn _f(conn: &mut PgConnection) -> Result<(), Error> {
+/// use sqlx_rt::AsyncReadExt;
+///
+/// let mut tx = conn.begin().await?;
+///
+/// let mut file = sqlx_rt::fs::File::open("scan.pdf").await?;
+/// let mut object = PgLargeObject::create(&mut tx).await?;
+/// let oid = object.oid();
+///
+/// sqlx_rt::tokio::io::copy(&mut file, &mut object).await?;
+/// object.close().await?;
+///
+/// let mut object = PgLargeObject::open(&mut tx, oid, PgLargeObjectMode::Read).await?;
+/// let mut header = [0; 4];
+/// object.read_exact(&mut header).await?;
+/// object.close().await?;
+///
+/// tx.commit().await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [large object]: https://www.postgresql.org/docs/current/largeobjects.html
+pub struct PgLargeObject<'c> {
+    oid: Oid,
+    fd: i32,
+
+    // position in the large object of the next byte returned by `poll_read`
+    position: u64,
+
+    // bytes returned by `loread` that were not yet returned by `poll_read`,
+    // the position of the descriptor on the server is ahead of `position` by their length
+    read_buf: Bytes,
+
+    // taken by the pending operation until it completes
+    conn: Option<&'c mut PgConnection>,
+    pending: Option<BoxFuture<'c, (&'c mut PgConnection, Result<Output, Error>)>>,
+}
+
+enum Output {
+    Read(Vec<u8>),
+    Write(usize),
+    Seek(u64),
+}
+
+impl<'c> PgLargeObject<'c> {
+    /// Create a new empty large object with `lo_create`, and open it for reading and
+    /// writing.
+    pub async fn create(tx: &'c mut Transaction<'_, Postgres>) -> Result<PgLargeObject<'c>, Error> {
+        let oid: Oid = query_scalar("SELECT lo_create(0)")
+            .fetch_one(&mut **tx)
+            .await?;
+
+        Self::open(tx, oid, PgLargeObjectMode::ReadWrite).await
+    }
+
+    /// Open the large object `oid` with `lo_open`.
+    pub async fn open(
+        tx: &'c mut Transaction<'_, Postgres>,
+        oid: Oid,
+        mode: PgLargeObjectMode,
+    ) -> Result<PgLargeObject<'c>, Error> {
+        let conn: &'c mut PgConnection = tx;
+        let fd: i32 = query_scalar("SELECT lo_open($1, $2)")
+            .bind(oid)
+            .bind(mode.flags())
+            .fetch_one(&mut *conn)
+            .await?;
+
+        Ok(PgLargeObject {
+            oid,
+            fd,
+            position: 0,
+            read_buf: Bytes::new(),
+            conn: Some(conn),
+            pending: None,
+        })
+    }
+
+    /// Delete the large object `oid` with `lo_unlink`.
+    pub async fn unlink(conn: &mut PgConnection, oid: Oid) -> Result<(), Error> {
+        query("SELECT lo_unlink($1)")
+            .bind(oid)
+            .execute(&mut *conn)
+            .await?;
+
+        Ok(())
+    }
+
+    /// The OID of the large object.
+    pub fn oid(&self) -> Oid {
+        self.oid
+    }
+
+    /// Close the descriptor of the large object with `lo_close`, after waiting for a pending
+    /// read, write or seek to complete.
+    pub async fn close(mut self) -> Result<(), Error> {
+        if let Some(pending) = self.pending.take() {
+            let (conn, res) = pending.await;
+            self.conn = Some(conn);
+            res?;
+        }
+
+        let conn = self
+            .conn
+            .take()
+            .expect("BUG: connection taken without a pending operation");
+
+        conn.execute(query("SELECT lo_close($1)").bind(self.fd))
+            .await?;
+
+        Ok(())
+    }
+
+    fn start<F>(&mut self, op: F)
+    where
+        F: for<'a> FnOnce(&'a mut PgConnection) -> BoxFuture<'a, Result<Output, Error>> + Send + 'c,
+    {
+        let conn = self
+            .conn
+            .take()
+            .expect("BUG: operation started while another one is pending");
+
+        self.pending = Some(Box::pin(async move {
+            let res = op(&mut *conn).await;
+            (conn, res)
+        }));
+    }
+
+    // drive the pending operation, if any, to completion
+    fn poll_pending(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<Option<Output>>> {
+        let pending = match &mut self.pending {
+            Some(pending) => pending,
+            None => return Poll::Ready(Ok(None)),
+        };
+
+        let (conn, res) = ready!(pending.poll_unpin(cx));
+
+        self.pending = None;
+        self.conn = Some(conn);
+
+        let output = res.map_err(into_io_error)?;
+
+        match output {
+            Output::Write(written) => self.position += written as u64,
+            Output::Seek(position) => self.position = position,
+            Output::Read(_) => {}
+        }
+
+        Poll::Ready(Ok(Some(output)))
+    }
+
+    // the position to move the descriptor back to before writing,
+    // if it was moved ahead by reading bytes that were not consumed
+    fn take_read_buf(&mut self) -> Option<u64> {
+        if self.read_buf.is_empty() {
+            return None;
+        }
+
+        self.read_buf = Bytes::new();
+
+        Some(self.position)
+    }
+}
+
+async fn lo_seek(conn: &mut PgConnection, fd: i32, offset: i64, whence: i32) -> Result<u64, Error> {
+    let position: i64 = query_scalar("SELECT lo_lseek64($1, $2, $3)")
+        .bind(fd)
+        .bind(offset)
+        .bind(whence)
+        .fetch_one(&mut *conn)
+        .await?;
+
+    Ok(position as u64)
+}
+
+async fn lo_read(conn: &mut PgConnection, fd: i32, len: usize) -> Result<Output, Error> {
+    let data: Vec<u8> = query_scalar("SELECT loread($1, $2)")
+        .bind(fd)
+        .bind(len as i32)
+        .fetch_one(&mut *conn)
+        .await?;
+
+    Ok(Output::Read(data))
+}
+
+async fn lo_write(
+    conn: &mut PgConnection,
+    fd: i32,
+    seek_to: Option<u64>,
+    data: Vec<u8>,
+) -> Result<Output, Error> {
+    if let Some(position) = seek_to {
+        lo_seek(&mut *conn, fd, position as i64, SEEK_SET).await?;
+    }
+
+    let written: i32 = query_scalar("SELECT lowrite($1, $2)")
+        .bind(fd)
+        .bind(data)
+        .fetch_one(&mut *conn)
+        .await?;
+
+    Ok(Output::Write(written as usize))
+}
+
+fn into_io_error(error: Error) -> io::Error {
+    match error {
+        Error::Io(error) => error,
+        error => io::Error::other(error),
+    }
+}
+
+impl AsyncRead for PgLargeObject<'_> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+
+        loop {
+            if buf.remaining() == 0 {
+                return Poll::Ready(Ok(()));
+            }
+
+            if !this.read_buf.is_empty() {
+                let len = cmp::min(buf.remaining(), this.read_buf.len());
+
+                buf.put_slice(&this.read_buf[..len]);
+                this.read_buf.advance(len);
+                this.position += len as u64;
+
+                return Poll::Ready(Ok(()));
+            }
+
+            match ready!(this.poll_pending(cx))? {
+                // the end of the large object
+                Some(Output::Read(data)) if data.is_empty() => return Poll::Ready(Ok(())),
+
+                Some(Output::Read(data)) => {
+                    this.read_buf = Bytes::from(data);
+                }
+
+                // an abandoned write or seek, that had to complete before reading
+                Some(Output::Write(_)) | Some(Output::Seek(_)) => {}
+
+                None => {
+                    let fd = this.fd;
+                    let len = cmp::min(buf.remaining(), CHUNK_SIZE);
+
+                    this.start(move |conn| Box::pin(lo_read(conn, fd, len)));
+                }
+            }
+        }
+    }
+}
+
+impl AsyncWrite for PgLargeObject<'_> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+
+        loop {
+            match ready!(this.poll_pending(cx))? {
+                Some(Output::Write(written)) => return Poll::Ready(Ok(written)),
+
+                // an abandoned read, whose bytes can still be returned by `poll_read`
+                Some(Output::Read(data)) => {
+                    this.read_buf = Bytes::from(data);
+                }
+
+                Some(Output::Seek(_)) => {}
+
+                None => {
+                    if buf.is_empty() {
+                        return Poll::Ready(Ok(0));
+                    }
+
+                    let fd = this.fd;
+                    let seek_to = this.take_read_buf();
+                    let data = buf[..cmp::min(buf.len(), CHUNK_SIZE)].to_vec();
+
+                    this.start(move |conn| Box::pin(lo_write(conn, fd, seek_to, data)));
+                }
+            }
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+
+        // writes are not buffered, so there is only the pending operation to wait for
+        if let Some(Output::Read(data)) = ready!(this.poll_pending(cx))? {
+            this.read_buf = Bytes::from(data);
+        }
+
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.poll_flush(cx)
+    }
+}
+
+impl AsyncSeek for PgLargeObject<'_> {
+    fn start_seek(self: Pin<&mut Self>, position: SeekFrom) -> io::Result<()> {
+        let this = self.get_mut();
+
+        if this.pending.is_some() {
+            return Err(io::Error::other(
+                "other large object operation is pending, call poll_complete before start_seek",
+            ));
+        }
+
+        let (offset, whence) = match position {
+            SeekFrom::Start(offset) => (i64::try_from(offset).ok(), SEEK_SET),
+
+            // relative to the position of the reader, not of the descriptor on the server
+            SeekFrom::Current(offset) => (
+                i64::try_from(this.position)
+                    .ok()
+                    .and_then(|position| position.checked_add(offset))
+                    .filter(|position| *position >= 0),
+                SEEK_SET,
+            ),
+
+            SeekFrom::End(offset) => (Some(offset), SEEK_END),
+        };
+
+        let offset = offset.ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )
+        })?;
+
+        let fd = this.fd;
+
+        this.read_buf = Bytes::new();
+        this.start(move |conn| {
+            Box::pin(async move { Ok(Output::Seek(lo_seek(conn, fd, offset, whence).await?)) })
+        });
+
+        Ok(())
+    }
+
+    fn poll_complete(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<u64>> {
+        let this = self.get_mut();
+
+        match ready!(this.poll_pending(cx))? {
+            Some(Output::Seek(position)) => Poll::Ready(Ok(position)),
+
+            Some(Output::Read(data)) => {
+                this.read_buf = Bytes::from(data);
+
+                Poll::Ready(Ok(this.position))
+            }
+
+            Some(Output::Write(_)) | None => Poll::Ready(Ok(this.position)),
+        }
+    }
+}
+
+impl Debug for PgLargeObject<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PgLargeObject")
+            .field("oid", &self.oid)
+            .field("fd", &self.fd)
+            .field("position", &self.position)
+            .finish()
+    }
+}
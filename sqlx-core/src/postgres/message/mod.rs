@@ -6,6 +6,7 @@ use crate::io::Decode;
 mod authentication;
 mod backend_key_data;
 mod bind;
+mod cancel_request;
 mod close;
 mod command_complete;
 mod copy;
@@ -20,6 +21,7 @@ mod parse;
 mod password;
 mod query;
 mod ready_for_query;
+mod replication;
 mod response;
 mod row_description;
 mod sasl;
@@ -31,6 +33,7 @@ mod terminate;
 pub use authentication::{Authentication, AuthenticationSasl};
 pub use backend_key_data::BackendKeyData;
 pub use bind::Bind;
+pub use cancel_request::CancelRequest;
 pub use close::Close;
 pub use command_complete::CommandComplete;
 pub use copy::{CopyData, CopyDone, CopyFail, CopyResponse};
@@ -46,6 +49,7 @@ pub use parse::Parse;
 pub use password::Password;
 pub use query::Query;
 pub use ready_for_query::{ReadyForQuery, TransactionStatus};
+pub use replication::{ReplicationMessage, StandbyStatusUpdate};
 pub use response::{Notice, PgSeverity};
 pub use row_description::RowDescription;
 pub use sasl::{SaslInitialResponse, SaslResponse};
@@ -64,6 +68,7 @@ pub enum MessageFormat {
     CommandComplete,
     CopyData,
     CopyDone,
+    CopyBothResponse,
     CopyInResponse,
     CopyOutResponse,
     DataRow,
@@ -109,6 +114,7 @@ impl MessageFormat {
             b'c' => MessageFormat::CopyDone,
             b'G' => MessageFormat::CopyInResponse,
             b'H' => MessageFormat::CopyOutResponse,
+            b'W' => MessageFormat::CopyBothResponse,
             b'D' => MessageFormat::DataRow,
             b'E' => MessageFormat::ErrorResponse,
             b'I' => MessageFormat::EmptyQueryResponse,
@@ -0,0 +1,150 @@
+use bytes::{Buf, BufMut, Bytes};
+
+use crate::error::Error;
+use crate::io::{Decode, Encode};
+
+// https://www.postgresql.org/docs/current/protocol-replication.html#PROTOCOL-REPLICATION-START-REPLICATION
+
+/// A message sent by the server in a `CopyData` message while streaming replication data.
+#[derive(Debug)]
+pub enum ReplicationMessage {
+    /// A chunk of WAL data, in the format of the output plugin for logical replication.
+    XLogData {
+        /// The starting point of the WAL data in this message.
+        wal_start: u64,
+
+        /// The current end of WAL on the server.
+        wal_end: u64,
+
+        /// The server's clock at the time of transmission, in microseconds since
+        /// midnight on 2000-01-01.
+        send_time: i64,
+
+        data: Bytes,
+    },
+
+    PrimaryKeepalive {
+        /// The current end of WAL on the server.
+        wal_end: u64,
+
+        /// The server's clock at the time of transmission, in microseconds since
+        /// midnight on 2000-01-01.
+        send_time: i64,
+
+        /// The client should reply to this message as soon as possible, to avoid a
+        /// timeout disconnect.
+        reply_requested: bool,
+    },
+}
+
+impl Decode<'_> for ReplicationMessage {
+    fn decode_with(mut buf: Bytes, _: ()) -> Result<Self, Error> {
+        if buf.is_empty() {
+            return Err(err_protocol!("empty replication message"));
+        }
+
+        match buf.get_u8() {
+            b'w' => Ok(ReplicationMessage::XLogData {
+                wal_start: buf.get_u64(),
+                wal_end: buf.get_u64(),
+                send_time: buf.get_i64(),
+                data: buf,
+            }),
+
+            b'k' => Ok(ReplicationMessage::PrimaryKeepalive {
+                wal_end: buf.get_u64(),
+                send_time: buf.get_i64(),
+                reply_requested: buf.get_u8() != 0,
+            }),
+
+            tag => Err(err_protocol!(
+                "unknown replication message type: {:?}",
+                tag as char
+            )),
+        }
+    }
+}
+
+/// Reports the replication progress of the client to the server, in a `CopyData` message.
+pub struct StandbyStatusUpdate {
+    /// The location of the last WAL byte + 1 received by the client.
+    pub written: u64,
+
+    /// The location of the last WAL byte + 1 flushed by the client.
+    pub flushed: u64,
+
+    /// The location of the last WAL byte + 1 applied by the client.
+    pub applied: u64,
+
+    /// The client's clock at the time of transmission, in microseconds since
+    /// midnight on 2000-01-01.
+    pub client_time: i64,
+
+    /// Ask the server to reply to this message immediately.
+    pub reply_requested: bool,
+}
+
+impl Encode<'_> for StandbyStatusUpdate {
+    fn encode_with(&self, buf: &mut Vec<u8>, _: ()) {
+        buf.push(b'd');
+        buf.put_u32(4 + 1 + 8 * 4 + 1);
+
+        buf.push(b'r');
+        buf.put_u64(self.written);
+        buf.put_u64(self.flushed);
+        buf.put_u64(self.applied);
+        buf.put_i64(self.client_time);
+        buf.push(self.reply_requested as u8);
+    }
+}
+
+#[test]
+fn test_decode_xlog_data() {
+    const DATA: &[u8] = b"w\0\0\0\0\x01\x00\x00\x10\0\0\0\0\x01\x00\x00\x20\0\0\0\0\0\0\0\x05hello";
+
+    let message = ReplicationMessage::decode(Bytes::from_static(DATA)).unwrap();
+
+    assert!(matches!(
+        message,
+        ReplicationMessage::XLogData {
+            wal_start: 0x0100_0010,
+            wal_end: 0x0100_0020,
+            send_time: 5,
+            ref data,
+        } if &data[..] == b"hello"
+    ));
+}
+
+#[test]
+fn test_decode_primary_keepalive() {
+    const DATA: &[u8] = b"k\0\0\0\0\x01\x00\x00\x20\0\0\0\0\0\0\0\x05\x01";
+
+    let message = ReplicationMessage::decode(Bytes::from_static(DATA)).unwrap();
+
+    assert!(matches!(
+        message,
+        ReplicationMessage::PrimaryKeepalive {
+            wal_end: 0x0100_0020,
+            send_time: 5,
+            reply_requested: true,
+        }
+    ));
+}
+
+#[test]
+fn test_encode_standby_status_update() {
+    let mut buf = Vec::new();
+    StandbyStatusUpdate {
+        written: 3,
+        flushed: 2,
+        applied: 1,
+        client_time: 5,
+        reply_requested: false,
+    }
+    .encode(&mut buf);
+
+    assert_eq!(
+        buf,
+        b"d\0\0\0\x26r\0\0\0\0\0\0\0\x03\0\0\0\0\0\0\0\x02\0\0\0\0\0\0\0\x01\0\0\0\0\0\0\0\x05\0"
+    );
+}
@@ -10,10 +10,14 @@ mod copy;
 mod database;
 mod error;
 mod io;
+mod large_object;
 mod listener;
 mod message;
+mod notice;
 mod options;
 mod query_result;
+mod quote;
+mod replication;
 mod row;
 mod statement;
 mod transaction;
@@ -30,14 +34,24 @@ mod testing;
 pub use advisory_lock::{PgAdvisoryLock, PgAdvisoryLockGuard, PgAdvisoryLockKey};
 pub use arguments::{PgArgumentBuffer, PgArguments};
 pub use column::PgColumn;
-pub use connection::PgConnection;
-pub use copy::PgCopyIn;
+pub use connection::{
+    PgCancelToken, PgConnection, PgCursor, PgPipeline, PgPipelineResult, PgPortal,
+};
+pub use copy::{PgCopyIn, PgCopyRow, PgCopyRowEncoder};
 pub use database::Postgres;
 pub use error::{PgDatabaseError, PgErrorPosition};
+pub use large_object::{PgLargeObject, PgLargeObjectMode};
 pub use listener::{PgListener, PgNotification};
 pub use message::PgSeverity;
-pub use options::{PgConnectOptions, PgSslMode};
+pub use notice::PgNotice;
+pub use options::{PgConnectOptions, PgSslMode, PgTargetSessionAttrs};
 pub use query_result::PgQueryResult;
+pub use replication::{
+    PgLsn, PgOutputBegin, PgOutputColumn, PgOutputCommit, PgOutputDelete, PgOutputInsert,
+    PgOutputMessage, PgOutputOrigin, PgOutputRelation, PgOutputTruncate, PgOutputType,
+    PgOutputUpdate, PgOutputValue, PgReplicaIdentity, PgReplicationMessage, PgReplicationProgress,
+    PgReplicationSlot, PgReplicationStream,
+};
 pub use row::PgRow;
 pub use statement::PgStatement;
 pub use transaction::PgTransactionManager;
@@ -0,0 +1,84 @@
+use std::fmt::{self, Debug, Display, Formatter};
+use std::sync::Arc;
+
+use crate::postgres::message::{Notice, PgSeverity};
+
+/// A notice sent by the server, such as a warning or the message of a `RAISE NOTICE`.
+///
+/// Received by the handler set with
+/// [`PgConnectOptions::notice_handler`][crate::postgres::PgConnectOptions::notice_handler].
+#[derive(Clone)]
+pub struct PgNotice(pub(crate) Notice);
+
+// Notice message fields are documented:
+// https://www.postgresql.org/docs/current/protocol-error-fields.html
+
+impl PgNotice {
+    #[inline]
+    pub fn severity(&self) -> PgSeverity {
+        self.0.severity()
+    }
+
+    /// The [SQLSTATE](https://www.postgresql.org/docs/current/errcodes-appendix.html) code for
+    /// this notice.
+    #[inline]
+    pub fn code(&self) -> &str {
+        self.0.code()
+    }
+
+    /// The primary human-readable message. This should be accurate but terse (typically
+    /// one line).
+    #[inline]
+    pub fn message(&self) -> &str {
+        self.0.message()
+    }
+
+    /// An optional secondary message carrying more detail. Might run to multiple lines.
+    #[inline]
+    pub fn detail(&self) -> Option<&str> {
+        self.0.get(b'D')
+    }
+
+    /// An optional suggestion of what to do about the notice. Might run to multiple lines.
+    #[inline]
+    pub fn hint(&self) -> Option<&str> {
+        self.0.get(b'H')
+    }
+
+    /// An indication of the context in which the notice was raised, such as a call stack
+    /// traceback of active procedural language functions. The trace is one entry per line,
+    /// most recent first.
+    pub fn r#where(&self) -> Option<&str> {
+        self.0.get(b'W')
+    }
+}
+
+impl Debug for PgNotice {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PgNotice")
+            .field("severity", &self.severity())
+            .field("code", &self.code())
+            .field("message", &self.message())
+            .field("detail", &self.detail())
+            .field("hint", &self.hint())
+            .field("where", &self.r#where())
+            .finish()
+    }
+}
+
+impl Display for PgNotice {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+/// The function set with
+/// [`PgConnectOptions::notice_handler`][crate::postgres::PgConnectOptions::notice_handler].
+#[derive(Clone)]
+pub(crate) struct PgNoticeHandler(pub(crate) Arc<dyn Fn(&PgNotice) + Send + Sync + 'static>);
+
+impl Debug for PgNoticeHandler {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("PgNoticeHandler")
+    }
+}
@@ -0,0 +1,105 @@
+use std::env::var_os;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::PathBuf;
+
+/// try to load the parameters of a service from the various service file locations
+///
+/// as with libpq, the per-user file is searched first, and the system-wide file only if the
+/// service is not found in it
+pub fn load_service(name: &str) -> Option<Vec<(String, String)>> {
+    #[cfg(not(target_os = "windows"))]
+    let default_file = dirs::home_dir().map(|path| path.join(".pg_service.conf"));
+    #[cfg(target_os = "windows")]
+    let default_file =
+        dirs::data_dir().map(|path| path.join("postgresql").join(".pg_service.conf"));
+
+    let user_file = var_os("PGSERVICEFILE").map(PathBuf::from).or(default_file);
+    if let Some(parameters) = user_file.and_then(|file| load_service_from_file(file, name)) {
+        return Some(parameters);
+    }
+
+    let system_file = PathBuf::from(var_os("PGSYSCONFDIR")?).join("pg_service.conf");
+    load_service_from_file(system_file, name)
+}
+
+/// try to extract the parameters of a service from a service file
+fn load_service_from_file(path: PathBuf, name: &str) -> Option<Vec<(String, String)>> {
+    let file = File::open(&path).ok()?;
+
+    let reader = BufReader::new(file);
+    load_service_from_reader(reader, name)
+}
+
+fn load_service_from_reader(reader: impl BufRead, name: &str) -> Option<Vec<(String, String)>> {
+    let mut parameters = None;
+
+    for line in reader.lines() {
+        let line = line.ok()?;
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(section) = line.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+            if parameters.is_some() {
+                // end of the service we were reading
+                break;
+            }
+
+            if section == name {
+                parameters = Some(Vec::new());
+            }
+        } else if let Some(parameters) = &mut parameters {
+            match line.split_once('=') {
+                Some((key, value)) => {
+                    parameters.push((key.trim().to_owned(), value.trim().to_owned()));
+                }
+
+                None => log::warn!("Malformed line in service file: {}", line),
+            }
+        }
+    }
+
+    parameters
+}
+
+#[cfg(test)]
+mod tests {
+    use super::load_service_from_reader;
+
+    #[test]
+    fn test_load_service_from_reader() {
+        let file = b"\
+            # a comment\n\
+            [other]\n\
+            host=otherhost\n\
+            \n\
+            [mydb]\r\n\
+            host = dbhost\n\
+            # a comment in a section\n\
+            port=5433\n\
+            malformed line\n\
+            dbname=my=db\n\
+            [last]\n\
+            host=lasthost
+        ";
+
+        assert_eq!(
+            load_service_from_reader(&mut &file[..], "mydb"),
+            Some(vec![
+                ("host".to_owned(), "dbhost".to_owned()),
+                ("port".to_owned(), "5433".to_owned()),
+                ("dbname".to_owned(), "my=db".to_owned()),
+            ])
+        );
+        assert_eq!(
+            load_service_from_reader(&mut &file[..], "last"),
+            Some(vec![("host".to_owned(), "lasthost".to_owned())])
+        );
+
+        // doesn't exist
+        assert_eq!(load_service_from_reader(&mut &file[..], "missing"), None);
+    }
+}
@@ -0,0 +1,15 @@
+// Quoting of names and values interpolated into statements that can not take bind parameters
+
+pub(super) fn quote_identifier(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+pub(super) fn quote_literal(literal: &str) -> String {
+    format!("'{}'", literal.replace('\'', "''"))
+}
+
+#[test]
+fn test_quote() {
+    assert_eq!(quote_identifier("my \"slot\""), "\"my \"\"slot\"\"\"");
+    assert_eq!(quote_literal("it's"), "'it''s'");
+}
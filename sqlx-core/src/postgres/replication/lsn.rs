@@ -0,0 +1,76 @@
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use crate::error::Error;
+
+/// A position in the write-ahead log of the server, a [`pg_lsn`] in SQL.
+///
+/// Displayed and parsed in the textual format of Postgres, two hexadecimal numbers separated
+/// by a slash, such as `16/B374D848`.
+///
+/// [`pg_lsn`]: https://www.postgresql.org/docs/current/datatype-pg-lsn.html
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PgLsn(u64);
+
+impl PgLsn {
+    /// Create a log sequence number from its 64-bit value.
+    pub const fn from_u64(lsn: u64) -> Self {
+        Self(lsn)
+    }
+
+    /// The 64-bit value of this log sequence number.
+    pub const fn to_u64(self) -> u64 {
+        self.0
+    }
+}
+
+impl From<u64> for PgLsn {
+    fn from(lsn: u64) -> Self {
+        Self(lsn)
+    }
+}
+
+impl From<PgLsn> for u64 {
+    fn from(lsn: PgLsn) -> Self {
+        lsn.0
+    }
+}
+
+impl Display for PgLsn {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:X}/{:X}", self.0 >> 32, self.0 & 0xFFFF_FFFF)
+    }
+}
+
+impl FromStr for PgLsn {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let parse = |part: &str| u32::from_str_radix(part, 16).ok();
+
+        let lsn = s
+            .split_once('/')
+            .and_then(|(high, low)| Some((u64::from(parse(high)?) << 32) | u64::from(parse(low)?)))
+            .ok_or_else(|| err_protocol!("invalid log sequence number: {:?}", s))?;
+
+        Ok(Self(lsn))
+    }
+}
+
+#[test]
+fn test_display_lsn() {
+    assert_eq!(PgLsn::from_u64(0).to_string(), "0/0");
+    assert_eq!(PgLsn::from_u64(0x16_B374_D848).to_string(), "16/B374D848");
+}
+
+#[test]
+fn test_parse_lsn() {
+    assert_eq!("0/0".parse::<PgLsn>().unwrap(), PgLsn::from_u64(0));
+    assert_eq!(
+        "16/b374d848".parse::<PgLsn>().unwrap(),
+        PgLsn::from_u64(0x16_B374_D848)
+    );
+
+    assert!("16B374D848".parse::<PgLsn>().is_err());
+    assert!("1/2/3".parse::<PgLsn>().is_err());
+}
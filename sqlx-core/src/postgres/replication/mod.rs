@@ -0,0 +1,201 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::error::Error;
+use crate::executor::Executor;
+use crate::postgres::message::{CopyResponse, MessageFormat, Query};
+use crate::postgres::quote::{quote_identifier, quote_literal};
+use crate::postgres::{PgConnection, PgRow};
+use crate::row::Row;
+
+mod lsn;
+mod pgoutput;
+mod stream;
+
+pub use lsn::PgLsn;
+pub use pgoutput::{
+    PgOutputBegin, PgOutputColumn, PgOutputCommit, PgOutputDelete, PgOutputInsert, PgOutputMessage,
+    PgOutputOrigin, PgOutputRelation, PgOutputTruncate, PgOutputType, PgOutputUpdate,
+    PgOutputValue, PgReplicaIdentity,
+};
+pub use stream::{PgReplicationMessage, PgReplicationProgress, PgReplicationStream};
+
+// https://www.postgresql.org/docs/current/protocol-replication.html
+// https://www.postgresql.org/docs/current/protocol-logical-replication.html
+
+/// A logical replication slot created by [`PgConnection::create_replication_slot`].
+#[derive(Debug, Clone)]
+pub struct PgReplicationSlot {
+    name: String,
+    consistent_point: PgLsn,
+    snapshot_name: Option<String>,
+}
+
+impl PgReplicationSlot {
+    /// The name of the slot.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The position from which the slot streams changes: the changes of transactions
+    /// committed after it.
+    pub fn consistent_point(&self) -> PgLsn {
+        self.consistent_point
+    }
+
+    /// The name of the snapshot exported when the slot was created, which shows the
+    /// database as it was at the [consistent point][Self::consistent_point].
+    ///
+    /// Another connection can use it with
+    /// [`begin_with_snapshot`][PgConnection::begin_with_snapshot] to copy the initial
+    /// contents of the tables before streaming the changes made since. It stays valid until
+    /// the replication connection runs another command.
+    pub fn snapshot_name(&self) -> Option<&str> {
+        self.snapshot_name.as_deref()
+    }
+}
+
+impl PgConnection {
+    /// Create a logical replication slot using the `pgoutput` plugin, which records the
+    /// changes to the database from now on until they are consumed by
+    /// [`start_replication`][Self::start_replication].
+    ///
+    /// A temporary slot is dropped when this connection is closed, otherwise the slot
+    /// persists until [`drop_replication_slot`][Self::drop_replication_slot] is called. Beware
+    /// that the server keeps the write-ahead log needed by a slot, so a slot that is never
+    /// consumed fills up the disk of the server.
+    ///
+    /// The connection must have been opened with
+    /// [`PgConnectOptions::replication`][crate::postgres::PgConnectOptions::replication].
+    pub async fn create_replication_slot(
+        &mut self,
+        name: &str,
+        temporary: bool,
+    ) -> Result<PgReplicationSlot, Error> {
+        let command = format!(
+            "CREATE_REPLICATION_SLOT {}{} LOGICAL pgoutput",
+            quote_identifier(name),
+            if temporary { " TEMPORARY" } else { "" }
+        );
+
+        let row: PgRow = self.fetch_one(&*command).await?;
+
+        Ok(PgReplicationSlot {
+            name: row.try_get("slot_name")?,
+            consistent_point: row.try_get::<&str, _>("consistent_point")?.parse()?,
+            snapshot_name: row.try_get("snapshot_name")?,
+        })
+    }
+
+    /// Drop a replication slot, so the server no longer keeps the write-ahead log for it.
+    pub async fn drop_replication_slot(&mut self, name: &str) -> Result<(), Error> {
+        let command = format!("DROP_REPLICATION_SLOT {}", quote_identifier(name));
+
+        self.execute(&*command).await?;
+
+        Ok(())
+    }
+
+    /// Start streaming the changes recorded by a logical replication slot, decoded from the
+    /// messages of the `pgoutput` plugin.
+    ///
+    /// Only changes to the tables of the given publications, created with
+    /// `CREATE PUBLICATION`, are streamed. Streaming resumes after the last position
+    /// acknowledged to the server for this slot, or from `start` if it is later.
+    ///
+    /// The connection must have been opened with
+    /// [`PgConnectOptions::replication`][crate::postgres::PgConnectOptions::replication], and
+    /// it cannot be used for anything else until the returned stream is
+    /// [stopped][PgReplicationStream::stop] or dropped.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use sqlx_core_oldapi::connection::Connection;
+    /// # use sqlx_core_oldapi::error::Error;
+    /// # use sqlx_core_oldapi::postgres::{PgConnectOptions, PgConnection, PgLsn, PgOutputMessage};
+    /// # async fn _f() -> Result<(), Error> {
+    /// let options = "postgres://localhost/mydb"
+    ///     .parse::<PgConnectOptions>()?
+    ///     .replication(true);
+    ///
+    /// let mut conn = PgConnection::connect_with(&options).await?;
+    /// let mut stream = conn
+    ///     .start_replication("my_slot", PgLsn::default(), &["my_publication"])
+    ///     .await?;
+    ///
+    /// while let Some(message) = stream.recv().await? {
+    ///     if let PgOutputMessage::Commit(commit) = message.data {
+    ///         // the transaction has been processed, let the server discard it
+    ///         stream.progress().acknowledge(commit.end_lsn);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn start_replication(
+        &mut self,
+        slot: &str,
+        start: PgLsn,
+        publications: &[&str],
+    ) -> Result<PgReplicationStream<'_>, Error> {
+        let publication_names = publications
+            .iter()
+            .map(|publication| quote_identifier(publication))
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let command = format!(
+            "START_REPLICATION SLOT {} LOGICAL {} (\"proto_version\" '1', \"publication_names\" {})",
+            quote_identifier(slot),
+            start,
+            quote_literal(&publication_names)
+        );
+
+        self.wait_until_ready().await?;
+        self.stream.send(Query(&command)).await?;
+
+        // the stream ends with `ReadyForQuery` once it is stopped, or if starting it fails
+        self.pending_ready_for_query_count += 1;
+
+        let _: CopyResponse = self
+            .stream
+            .recv_expect(MessageFormat::CopyBothResponse)
+            .await?;
+
+        Ok(PgReplicationStream::new(self, start))
+    }
+}
+
+// the replication protocol counts time in microseconds since 2000-01-01
+const PG_EPOCH_UNIX_SECS: u64 = 946_684_800;
+
+fn pg_time(micros: i64) -> SystemTime {
+    let pg_epoch = UNIX_EPOCH + Duration::from_secs(PG_EPOCH_UNIX_SECS);
+    let offset = Duration::from_micros(micros.unsigned_abs());
+
+    if micros < 0 {
+        pg_epoch - offset
+    } else {
+        pg_epoch + offset
+    }
+}
+
+fn pg_time_micros(time: SystemTime) -> i64 {
+    let pg_epoch = UNIX_EPOCH + Duration::from_secs(PG_EPOCH_UNIX_SECS);
+
+    match time.duration_since(pg_epoch) {
+        Ok(since) => i64::try_from(since.as_micros()).unwrap_or(i64::MAX),
+        Err(before) => i64::try_from(before.duration().as_micros())
+            .map(|micros| -micros)
+            .unwrap_or(i64::MIN),
+    }
+}
+
+#[test]
+fn test_pg_time() {
+    let time = UNIX_EPOCH + Duration::from_micros(1_600_000_000_123_456);
+
+    assert_eq!(pg_time(pg_time_micros(time)), time);
+    assert_eq!(pg_time(0), UNIX_EPOCH + Duration::from_secs(946_684_800));
+    assert_eq!(pg_time_micros(UNIX_EPOCH), -946_684_800_000_000);
+}
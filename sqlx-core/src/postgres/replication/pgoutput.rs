@@ -0,0 +1,497 @@
+use std::cmp;
+use std::str::from_utf8;
+use std::time::SystemTime;
+
+use bytes::{Buf, Bytes};
+
+use crate::error::Error;
+use crate::io::{BufExt, Decode};
+use crate::postgres::replication::{pg_time, PgLsn};
+use crate::postgres::types::Oid;
+
+// https://www.postgresql.org/docs/current/protocol-logicalrep-message-formats.html
+
+/// A message of the `pgoutput` logical decoding plugin, version 1.
+///
+/// The changes of a transaction are sent between its [`Begin`][Self::Begin] and
+/// [`Commit`][Self::Commit] messages. Each change refers to its table by the ID of a
+/// [`Relation`][Self::Relation] message sent earlier in the stream, which describes the columns
+/// of the table. A `Relation` message is sent again whenever the table changes.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum PgOutputMessage {
+    Begin(PgOutputBegin),
+    Commit(PgOutputCommit),
+    Origin(PgOutputOrigin),
+    Relation(PgOutputRelation),
+    Type(PgOutputType),
+    Insert(PgOutputInsert),
+    Update(PgOutputUpdate),
+    Delete(PgOutputDelete),
+    Truncate(PgOutputTruncate),
+}
+
+/// The start of a transaction.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct PgOutputBegin {
+    /// The position of the commit record of the transaction.
+    pub final_lsn: PgLsn,
+
+    /// When the transaction was committed.
+    pub commit_time: SystemTime,
+
+    /// The ID of the transaction.
+    pub xid: u32,
+}
+
+/// The end of a transaction.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct PgOutputCommit {
+    /// The position of the commit record of the transaction.
+    pub commit_lsn: PgLsn,
+
+    /// The end of the transaction in the log. Once the transaction has been processed,
+    /// acknowledging this position lets the server discard it.
+    pub end_lsn: PgLsn,
+
+    /// When the transaction was committed.
+    pub commit_time: SystemTime,
+}
+
+/// The replication origin of the current transaction, when it was itself replicated
+/// from another server.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct PgOutputOrigin {
+    /// The position of the commit record of the transaction on the origin server.
+    pub commit_lsn: PgLsn,
+
+    pub name: String,
+}
+
+/// The description of a table, sent before the first change to it and whenever it changes.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct PgOutputRelation {
+    /// The ID that later changes use to refer to this table.
+    pub id: Oid,
+
+    pub namespace: String,
+
+    pub name: String,
+
+    /// Which columns of the old row are sent with updates and deletes.
+    pub replica_identity: PgReplicaIdentity,
+
+    pub columns: Vec<PgOutputColumn>,
+}
+
+/// The `REPLICA IDENTITY` setting of a table.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PgReplicaIdentity {
+    /// The columns of the primary key, if any.
+    Default,
+
+    /// No columns.
+    Nothing,
+
+    /// All the columns.
+    Full,
+
+    /// The columns of a specific index.
+    Index,
+}
+
+/// A column of a [`PgOutputRelation`].
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct PgOutputColumn {
+    /// Whether the column is part of the replica identity of the table.
+    pub is_key: bool,
+
+    pub name: String,
+
+    pub type_oid: Oid,
+
+    pub type_modifier: i32,
+}
+
+/// The description of a user-defined type used by a column, sent before the
+/// [`PgOutputRelation`] that refers to it.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct PgOutputType {
+    pub id: Oid,
+
+    pub namespace: String,
+
+    pub name: String,
+}
+
+/// A row inserted into a table.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct PgOutputInsert {
+    /// The ID of the [`PgOutputRelation`] of the table.
+    pub relation_id: Oid,
+
+    pub new: Vec<PgOutputValue>,
+}
+
+/// A row updated in a table.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct PgOutputUpdate {
+    /// The ID of the [`PgOutputRelation`] of the table.
+    pub relation_id: Oid,
+
+    /// The old values of the replica identity columns, if the update changed them. The other
+    /// columns are null.
+    pub key: Option<Vec<PgOutputValue>>,
+
+    /// The old values of all the columns, if the replica identity of the table is
+    /// [`Full`][PgReplicaIdentity::Full].
+    pub old: Option<Vec<PgOutputValue>>,
+
+    pub new: Vec<PgOutputValue>,
+}
+
+/// A row deleted from a table.
+///
+/// Either `key` or `old` is set, depending on the replica identity of the table.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct PgOutputDelete {
+    /// The ID of the [`PgOutputRelation`] of the table.
+    pub relation_id: Oid,
+
+    /// The values of the replica identity columns. The other columns are null.
+    pub key: Option<Vec<PgOutputValue>>,
+
+    /// The values of all the columns, if the replica identity of the table is
+    /// [`Full`][PgReplicaIdentity::Full].
+    pub old: Option<Vec<PgOutputValue>>,
+}
+
+/// Tables truncated together.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct PgOutputTruncate {
+    /// The IDs of the [`PgOutputRelation`]s of the tables.
+    pub relation_ids: Vec<Oid>,
+
+    pub cascade: bool,
+
+    pub restart_identity: bool,
+}
+
+/// The value of a column in a row of a change.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PgOutputValue {
+    Null,
+
+    /// A large value stored out of line that was not changed by an update, so it is not sent.
+    UnchangedToast,
+
+    /// The value in the text format of its type.
+    Text(Bytes),
+}
+
+impl PgOutputValue {
+    /// The value in the text format of its type, or `None` if it is null or was not sent.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            PgOutputValue::Text(text) => from_utf8(text).ok(),
+            _ => None,
+        }
+    }
+}
+
+impl Decode<'_> for PgOutputMessage {
+    fn decode_with(mut buf: Bytes, _: ()) -> Result<Self, Error> {
+        if buf.is_empty() {
+            return Err(err_protocol!("empty pgoutput message"));
+        }
+
+        Ok(match buf.get_u8() {
+            b'B' => PgOutputMessage::Begin(PgOutputBegin {
+                final_lsn: PgLsn::from_u64(buf.get_u64()),
+                commit_time: pg_time(buf.get_i64()),
+                xid: buf.get_u32(),
+            }),
+
+            b'C' => {
+                // flags, currently unused
+                buf.advance(1);
+
+                PgOutputMessage::Commit(PgOutputCommit {
+                    commit_lsn: PgLsn::from_u64(buf.get_u64()),
+                    end_lsn: PgLsn::from_u64(buf.get_u64()),
+                    commit_time: pg_time(buf.get_i64()),
+                })
+            }
+
+            b'O' => PgOutputMessage::Origin(PgOutputOrigin {
+                commit_lsn: PgLsn::from_u64(buf.get_u64()),
+                name: buf.get_str_nul()?,
+            }),
+
+            b'R' => {
+                let id = Oid(buf.get_u32());
+                let namespace = buf.get_str_nul()?;
+                let name = buf.get_str_nul()?;
+
+                let replica_identity = match buf.get_u8() {
+                    b'd' => PgReplicaIdentity::Default,
+                    b'n' => PgReplicaIdentity::Nothing,
+                    b'f' => PgReplicaIdentity::Full,
+                    b'i' => PgReplicaIdentity::Index,
+                    other => {
+                        return Err(err_protocol!(
+                            "unknown replica identity: {:?}",
+                            other as char
+                        ))
+                    }
+                };
+
+                let num_columns = buf.get_i16();
+                let mut columns = Vec::with_capacity(cmp::max(num_columns, 0) as usize);
+
+                for _ in 0..num_columns {
+                    columns.push(PgOutputColumn {
+                        is_key: buf.get_u8() & 1 != 0,
+                        name: buf.get_str_nul()?,
+                        type_oid: Oid(buf.get_u32()),
+                        type_modifier: buf.get_i32(),
+                    });
+                }
+
+                PgOutputMessage::Relation(PgOutputRelation {
+                    id,
+                    namespace,
+                    name,
+                    replica_identity,
+                    columns,
+                })
+            }
+
+            b'Y' => PgOutputMessage::Type(PgOutputType {
+                id: Oid(buf.get_u32()),
+                namespace: buf.get_str_nul()?,
+                name: buf.get_str_nul()?,
+            }),
+
+            b'I' => {
+                let relation_id = Oid(buf.get_u32());
+                expect_tuple_tag(&mut buf, b'N')?;
+
+                PgOutputMessage::Insert(PgOutputInsert {
+                    relation_id,
+                    new: decode_tuple(&mut buf)?,
+                })
+            }
+
+            b'U' => {
+                let relation_id = Oid(buf.get_u32());
+                let mut key = None;
+                let mut old = None;
+
+                match buf.first() {
+                    Some(b'K') => {
+                        buf.advance(1);
+                        key = Some(decode_tuple(&mut buf)?);
+                    }
+
+                    Some(b'O') => {
+                        buf.advance(1);
+                        old = Some(decode_tuple(&mut buf)?);
+                    }
+
+                    _ => {}
+                }
+
+                expect_tuple_tag(&mut buf, b'N')?;
+
+                PgOutputMessage::Update(PgOutputUpdate {
+                    relation_id,
+                    key,
+                    old,
+                    new: decode_tuple(&mut buf)?,
+                })
+            }
+
+            b'D' => {
+                let relation_id = Oid(buf.get_u32());
+                let mut key = None;
+                let mut old = None;
+
+                match buf.get_u8() {
+                    b'K' => key = Some(decode_tuple(&mut buf)?),
+                    b'O' => old = Some(decode_tuple(&mut buf)?),
+                    other => {
+                        return Err(err_protocol!(
+                            "expected 'K' or 'O' in pgoutput delete, got {:?}",
+                            other as char
+                        ))
+                    }
+                }
+
+                PgOutputMessage::Delete(PgOutputDelete {
+                    relation_id,
+                    key,
+                    old,
+                })
+            }
+
+            b'T' => {
+                let num_relations = buf.get_u32();
+                let options = buf.get_u8();
+
+                let relation_ids = (0..num_relations).map(|_| Oid(buf.get_u32())).collect();
+
+                PgOutputMessage::Truncate(PgOutputTruncate {
+                    relation_ids,
+                    cascade: options & 1 != 0,
+                    restart_identity: options & 2 != 0,
+                })
+            }
+
+            other => {
+                return Err(err_protocol!(
+                    "unsupported pgoutput message type: {:?}",
+                    other as char
+                ))
+            }
+        })
+    }
+}
+
+fn expect_tuple_tag(buf: &mut Bytes, tag: u8) -> Result<(), Error> {
+    match buf.get_u8() {
+        other if other == tag => Ok(()),
+        other => Err(err_protocol!(
+            "expected {:?} in pgoutput message, got {:?}",
+            tag as char,
+            other as char
+        )),
+    }
+}
+
+fn decode_tuple(buf: &mut Bytes) -> Result<Vec<PgOutputValue>, Error> {
+    let num_columns = buf.get_i16();
+    let mut values = Vec::with_capacity(cmp::max(num_columns, 0) as usize);
+
+    for _ in 0..num_columns {
+        values.push(match buf.get_u8() {
+            b'n' => PgOutputValue::Null,
+            b'u' => PgOutputValue::UnchangedToast,
+            b't' => {
+                let len = buf.get_u32() as usize;
+                PgOutputValue::Text(buf.get_bytes(len))
+            }
+
+            other => {
+                return Err(err_protocol!(
+                    "unsupported pgoutput column value: {:?}",
+                    other as char
+                ))
+            }
+        });
+    }
+
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_decodes_relation() {
+        const DATA: &[u8] = b"R\0\0\x40\x01public\0users\0d\0\x02\x01id\0\0\0\0\x17\xff\xff\xff\xff\0name\0\0\0\0\x19\xff\xff\xff\xff";
+
+        let message = PgOutputMessage::decode(Bytes::from_static(DATA)).unwrap();
+
+        assert_eq!(
+            message,
+            PgOutputMessage::Relation(PgOutputRelation {
+                id: Oid(0x4001),
+                namespace: "public".into(),
+                name: "users".into(),
+                replica_identity: PgReplicaIdentity::Default,
+                columns: vec![
+                    PgOutputColumn {
+                        is_key: true,
+                        name: "id".into(),
+                        type_oid: Oid(23),
+                        type_modifier: -1,
+                    },
+                    PgOutputColumn {
+                        is_key: false,
+                        name: "name".into(),
+                        type_oid: Oid(25),
+                        type_modifier: -1,
+                    },
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn it_decodes_update_with_key() {
+        const DATA: &[u8] = b"U\0\0\x40\x01K\0\x02t\0\0\0\x011nN\0\x02t\0\0\0\x012u";
+
+        let message = PgOutputMessage::decode(Bytes::from_static(DATA)).unwrap();
+
+        let update = match message {
+            PgOutputMessage::Update(update) => update,
+            other => panic!("unexpected message: {:?}", other),
+        };
+
+        assert_eq!(update.relation_id, Oid(0x4001));
+        assert_eq!(
+            update.key,
+            Some(vec![
+                PgOutputValue::Text(Bytes::from_static(b"1")),
+                PgOutputValue::Null
+            ])
+        );
+        assert_eq!(update.old, None);
+        assert_eq!(update.new[0].as_str(), Some("2"));
+        assert_eq!(update.new[1], PgOutputValue::UnchangedToast);
+    }
+
+    #[test]
+    fn it_decodes_commit() {
+        const DATA: &[u8] = b"C\0\0\0\0\0\0\0\0\x10\0\0\0\0\0\0\0\x20\0\0\0\0\0\x0f\x42\x40";
+
+        let message = PgOutputMessage::decode(Bytes::from_static(DATA)).unwrap();
+
+        assert_eq!(
+            message,
+            PgOutputMessage::Commit(PgOutputCommit {
+                commit_lsn: PgLsn::from_u64(0x10),
+                end_lsn: PgLsn::from_u64(0x20),
+                commit_time: pg_time(1_000_000),
+            })
+        );
+    }
+
+    #[test]
+    fn it_decodes_truncate() {
+        const DATA: &[u8] = b"T\0\0\0\x02\x01\0\0\x40\x01\0\0\x40\x02";
+
+        let message = PgOutputMessage::decode(Bytes::from_static(DATA)).unwrap();
+
+        assert_eq!(
+            message,
+            PgOutputMessage::Truncate(PgOutputTruncate {
+                relation_ids: vec![Oid(0x4001), Oid(0x4002)],
+                cascade: true,
+                restart_identity: false,
+            })
+        );
+    }
+}
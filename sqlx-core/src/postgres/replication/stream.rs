@@ -0,0 +1,289 @@
+use std::cmp;
+use std::fmt::{self, Debug, Formatter};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+
+use futures_core::stream::Stream;
+
+use crate::error::Error;
+use crate::io::Decode;
+use crate::postgres::message::{
+    CopyData, CopyDone, MessageFormat, ReplicationMessage, StandbyStatusUpdate,
+};
+use crate::postgres::replication::{pg_time, pg_time_micros, PgLsn, PgOutputMessage};
+use crate::postgres::PgConnection;
+
+// how often the progress is reported to the server while messages are received,
+// the same default as `pg_recvlogical`
+const STATUS_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The changes streamed from a logical replication slot, returned by
+/// [`PgConnection::start_replication`].
+///
+/// The server keeps the changes of the slot until they are acknowledged with
+/// [`PgReplicationProgress::acknowledge`], so that they are streamed again if the client
+/// reconnects before it processed them. The acknowledged position is reported to the server
+/// periodically and whenever it asks for it.
+///
+/// The stream has to be [stopped][Self::stop] before the connection can be used again. If it
+/// is dropped instead, it is stopped the next time the connection is used.
+pub struct PgReplicationStream<'c> {
+    conn: &'c mut PgConnection,
+    progress: PgReplicationProgress,
+    received: PgLsn,
+    last_status: Instant,
+    done: bool,
+}
+
+/// A message received from a logical replication slot.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct PgReplicationMessage {
+    /// The position of the message in the write-ahead log.
+    pub wal_start: PgLsn,
+
+    /// The current end of the write-ahead log on the server.
+    pub wal_end: PgLsn,
+
+    /// When the server sent the message.
+    pub server_time: SystemTime,
+
+    pub data: PgOutputMessage,
+}
+
+/// A handle for acknowledging the changes processed from a [`PgReplicationStream`].
+///
+/// Returned by [`PgReplicationStream::progress`]. It is cheap to clone, and keeps working
+/// after the stream was turned into a [`Stream`] with
+/// [`into_stream`][PgReplicationStream::into_stream].
+#[derive(Clone, Default)]
+pub struct PgReplicationProgress(Arc<AtomicU64>);
+
+impl PgReplicationProgress {
+    /// Acknowledge that all changes up to `lsn` have been processed and do not need to be
+    /// streamed again.
+    ///
+    /// This is usually the [`end_lsn`][crate::postgres::PgOutputCommit::end_lsn] of the last
+    /// transaction processed. Acknowledging an earlier position than before has no effect.
+    pub fn acknowledge(&self, lsn: PgLsn) {
+        self.0.fetch_max(lsn.to_u64(), Ordering::AcqRel);
+    }
+
+    /// The last position acknowledged.
+    pub fn acknowledged(&self) -> PgLsn {
+        PgLsn::from_u64(self.0.load(Ordering::Acquire))
+    }
+}
+
+impl<'c> PgReplicationStream<'c> {
+    pub(super) fn new(conn: &'c mut PgConnection, start: PgLsn) -> Self {
+        Self {
+            conn,
+            progress: PgReplicationProgress::default(),
+            received: start,
+            last_status: Instant::now(),
+            done: false,
+        }
+    }
+
+    /// Returns a handle for acknowledging the changes processed.
+    pub fn progress(&self) -> PgReplicationProgress {
+        self.progress.clone()
+    }
+
+    /// Receive the next message from the slot, waiting for new changes to be committed if
+    /// there are none.
+    ///
+    /// Keepalive messages from the server are answered here. Returns `None` if the server
+    /// ended the stream.
+    pub async fn recv(&mut self) -> Result<Option<PgReplicationMessage>, Error> {
+        while !self.done {
+            if self.last_status.elapsed() >= STATUS_INTERVAL {
+                self.send_status_update(false).await?;
+            }
+
+            let message = match self.conn.stream.recv().await {
+                Ok(message) => message,
+
+                // the server ends the stream after an error
+                Err(error) => {
+                    self.done = true;
+                    return Err(error);
+                }
+            };
+
+            match message.format {
+                MessageFormat::CopyData => {
+                    let CopyData(data) = message.decode()?;
+
+                    match ReplicationMessage::decode(data)? {
+                        ReplicationMessage::XLogData {
+                            wal_start,
+                            wal_end,
+                            send_time,
+                            data,
+                        } => {
+                            self.received = cmp::max(self.received, PgLsn::from_u64(wal_start));
+
+                            return Ok(Some(PgReplicationMessage {
+                                wal_start: PgLsn::from_u64(wal_start),
+                                wal_end: PgLsn::from_u64(wal_end),
+                                server_time: pg_time(send_time),
+                                data: PgOutputMessage::decode(data)?,
+                            }));
+                        }
+
+                        ReplicationMessage::PrimaryKeepalive {
+                            wal_end,
+                            reply_requested,
+                            ..
+                        } => {
+                            self.received = cmp::max(self.received, PgLsn::from_u64(wal_end));
+
+                            if reply_requested {
+                                self.send_status_update(false).await?;
+                            }
+                        }
+                    }
+                }
+
+                MessageFormat::CopyDone => {
+                    // the server ended the stream, end our side of it too
+                    self.conn.stream.send(CopyDone).await?;
+                    self.finish().await?;
+                }
+
+                _ => {
+                    return Err(err_protocol!(
+                        "unexpected message during replication: {:?}",
+                        message.format
+                    ));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Report the acknowledged position to the server now, instead of waiting for the next
+    /// periodic update.
+    ///
+    /// If `reply_requested` is set, the server replies with a keepalive message.
+    pub async fn send_status_update(&mut self, reply_requested: bool) -> Result<(), Error> {
+        let acknowledged = self.progress.acknowledged().to_u64();
+
+        self.conn
+            .stream
+            .send(StandbyStatusUpdate {
+                written: cmp::max(self.received.to_u64(), acknowledged),
+                flushed: acknowledged,
+                applied: acknowledged,
+                client_time: pg_time_micros(SystemTime::now()),
+                reply_requested,
+            })
+            .await?;
+
+        self.last_status = Instant::now();
+
+        Ok(())
+    }
+
+    /// Stop streaming, reporting the acknowledged position to the server, and make the
+    /// connection usable again.
+    pub async fn stop(mut self) -> Result<(), Error> {
+        if self.done {
+            return Ok(());
+        }
+
+        self.send_status_update(false).await?;
+        self.conn.stream.send(CopyDone).await?;
+
+        // messages already sent by the server are discarded, they are streamed again
+        // the next time as they were not acknowledged
+        loop {
+            let message = self.conn.stream.recv().await?;
+
+            match message.format {
+                MessageFormat::CopyData => {}
+
+                MessageFormat::CopyDone => {
+                    return self.finish().await;
+                }
+
+                _ => {
+                    return Err(err_protocol!(
+                        "unexpected message while stopping replication: {:?}",
+                        message.format
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Turn this into a [`Stream`] of the messages received by [`recv`][Self::recv].
+    ///
+    /// Use the [`progress`][Self::progress] handle taken beforehand to acknowledge the
+    /// changes processed.
+    pub fn into_stream(mut self) -> impl Stream<Item = Result<PgReplicationMessage, Error>> + 'c {
+        Box::pin(try_stream! {
+            while let Some(message) = self.recv().await? {
+                r#yield!(message);
+            }
+
+            Ok(())
+        })
+    }
+
+    // once both sides ended the copy, the server completes it and then the
+    // `START_REPLICATION` command
+    async fn finish(&mut self) -> Result<(), Error> {
+        self.done = true;
+
+        loop {
+            let message = self.conn.stream.recv().await?;
+
+            match message.format {
+                MessageFormat::CommandComplete => {}
+
+                MessageFormat::ReadyForQuery => {
+                    return self.conn.handle_ready_for_query(message);
+                }
+
+                _ => {
+                    return Err(err_protocol!(
+                        "unexpected message after replication: {:?}",
+                        message.format
+                    ));
+                }
+            }
+        }
+    }
+}
+
+impl Drop for PgReplicationStream<'_> {
+    fn drop(&mut self) {
+        if !self.done {
+            // end the copy the next time the connection is used,
+            // which then waits for the server to end it too
+            self.conn.stream.write(CopyDone);
+        }
+    }
+}
+
+impl Debug for PgReplicationStream<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PgReplicationStream")
+            .field("received", &self.received)
+            .field("acknowledged", &self.progress.acknowledged())
+            .finish()
+    }
+}
+
+impl Debug for PgReplicationProgress {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("PgReplicationProgress")
+            .field(&self.acknowledged())
+            .finish()
+    }
+}
@@ -1,11 +1,17 @@
+use std::borrow::Cow;
+
 use futures_core::future::BoxFuture;
 
 use crate::error::Error;
 use crate::executor::Executor;
+use crate::pool::Pool;
+use crate::postgres::quote::quote_literal;
 use crate::postgres::{PgConnection, Postgres};
+use crate::query_scalar::query_scalar;
 use crate::transaction::{
-    begin_ansi_transaction_sql, commit_ansi_transaction_sql, rollback_ansi_transaction_sql,
-    TransactionManager,
+    begin_ansi_transaction_sql, check_savepoint_options, commit_ansi_transaction_sql,
+    rollback_ansi_transaction_sql, savepoint_ansi_sql, IsolationLevel, SavepointAction,
+    Transaction, TransactionManager, TransactionOptions,
 };
 
 /// Implementation of [`TransactionManager`] for PostgreSQL.
@@ -15,8 +21,17 @@ impl TransactionManager for PgTransactionManager {
     type Database = Postgres;
 
     fn begin(conn: &mut PgConnection) -> BoxFuture<'_, Result<(), Error>> {
+        Self::begin_with(conn, TransactionOptions::default())
+    }
+
+    fn begin_with(
+        conn: &mut PgConnection,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, Result<(), Error>> {
         Box::pin(async move {
-            conn.execute(&*begin_ansi_transaction_sql(conn.transaction_depth))
+            check_savepoint_options(conn.transaction_depth, &options)?;
+
+            conn.execute(&*begin_transaction_sql(conn.transaction_depth, &options))
                 .await?;
 
             conn.transaction_depth += 1;
@@ -58,4 +73,136 @@ impl TransactionManager for PgTransactionManager {
             conn.transaction_depth -= 1;
         }
     }
+
+    fn savepoint<'c>(
+        conn: &'c mut PgConnection,
+        name: &'c str,
+        action: SavepointAction,
+    ) -> BoxFuture<'c, Result<(), Error>> {
+        Box::pin(async move {
+            conn.execute(&*savepoint_ansi_sql(name, action)).await?;
+
+            Ok(())
+        })
+    }
+}
+
+fn begin_transaction_sql(depth: usize, options: &TransactionOptions) -> Cow<'static, str> {
+    if options.is_default() {
+        return begin_ansi_transaction_sql(depth);
+    }
+
+    let mut sql = String::from("BEGIN");
+
+    if let Some(isolation) = options.isolation {
+        sql.push_str(" ISOLATION LEVEL ");
+        sql.push_str(isolation.as_str());
+    }
+
+    if options.read_only {
+        sql.push_str(" READ ONLY");
+    }
+
+    if options.deferrable {
+        sql.push_str(" DEFERRABLE");
+    }
+
+    Cow::Owned(sql)
+}
+
+// https://www.postgresql.org/docs/current/functions-admin.html#FUNCTIONS-SNAPSHOT-SYNCHRONIZATION
+
+impl Transaction<'_, Postgres> {
+    /// Export the snapshot of this transaction with `pg_export_snapshot()`, returning its
+    /// identifier.
+    ///
+    /// Other connections can begin transactions that see the same contents of the database
+    /// with [`PgConnection::begin_with_snapshot`] or [`Pool::begin_with_snapshot`], for as long
+    /// as this transaction is open.
+    ///
+    /// This transaction should be `REPEATABLE READ` or `SERIALIZABLE` for its own queries to
+    /// keep seeing the exported snapshot as well.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use sqlx_core_oldapi::error::Error;
+    /// # use sqlx_core_oldapi::postgres::PgPool;
+    /// # use sqlx_core_oldapi::transaction::{IsolationLevel, TransactionOptions};
+    /// # async fn _f(pool: &PgPool) -> Result<(), Error> {
+    /// let options = TransactionOptions::new().isolation(IsolationLevel::RepeatableRead);
+    ///
+    /// let mut tx = pool.begin_with(options).await?;
+    /// let snapshot = tx.export_snapshot().await?;
+    ///
+    /// let mut readers = Vec::new();
+    ///
+    /// for _ in 0..4 {
+    ///     readers.push(pool.begin_with_snapshot(&snapshot).await?);
+    /// }
+    ///
+    /// // ... read from `tx` and `readers` in parallel ...
+    ///
+    /// tx.commit().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn export_snapshot(&mut self) -> Result<String, Error> {
+        query_scalar("SELECT pg_export_snapshot()")
+            .fetch_one(&mut **self)
+            .await
+    }
+}
+
+impl PgConnection {
+    /// Begin a new `REPEATABLE READ` transaction that sees the snapshot `snapshot`, exported by
+    /// another transaction with [`Transaction::export_snapshot`], using
+    /// `SET TRANSACTION SNAPSHOT`.
+    ///
+    /// The transaction exporting the snapshot must still be open. Unlike
+    /// [`begin`][crate::connection::Connection::begin], this cannot be used to establish a
+    /// savepoint.
+    pub async fn begin_with_snapshot(
+        &mut self,
+        snapshot: &str,
+    ) -> Result<Transaction<'_, Postgres>, Error> {
+        let tx = Transaction::begin_with(self, snapshot_transaction_options()).await?;
+
+        set_transaction_snapshot(tx, snapshot).await
+    }
+}
+
+impl Pool<Postgres> {
+    /// Retrieves a connection and immediately begins a new `REPEATABLE READ` transaction that
+    /// sees the snapshot `snapshot`, exported by another transaction with
+    /// [`Transaction::export_snapshot`].
+    ///
+    /// See [`PgConnection::begin_with_snapshot`] for details.
+    pub async fn begin_with_snapshot(
+        &self,
+        snapshot: &str,
+    ) -> Result<Transaction<'static, Postgres>, Error> {
+        let tx = self.begin_with(snapshot_transaction_options()).await?;
+
+        set_transaction_snapshot(tx, snapshot).await
+    }
+}
+
+fn snapshot_transaction_options() -> TransactionOptions {
+    // importing a snapshot requires `REPEATABLE READ` or `SERIALIZABLE`
+    TransactionOptions::new().isolation(IsolationLevel::RepeatableRead)
+}
+
+async fn set_transaction_snapshot<'c>(
+    mut tx: Transaction<'c, Postgres>,
+    snapshot: &str,
+) -> Result<Transaction<'c, Postgres>, Error> {
+    // the transaction is rolled back when dropped if the snapshot cannot be imported
+    tx.execute(&*format!(
+        "SET TRANSACTION SNAPSHOT {}",
+        quote_literal(snapshot)
+    ))
+    .await?;
+
+    Ok(tx)
 }
@@ -113,10 +113,26 @@ pub enum PgType {
     DateRangeArray,
     Int8Range,
     Int8RangeArray,
+    Int4MultiRange,
+    Int4MultiRangeArray,
+    NumMultiRange,
+    NumMultiRangeArray,
+    TsMultiRange,
+    TsMultiRangeArray,
+    TstzMultiRange,
+    TstzMultiRangeArray,
+    DateMultiRange,
+    DateMultiRangeArray,
+    Int8MultiRange,
+    Int8MultiRangeArray,
     Jsonpath,
     JsonpathArray,
     Money,
     MoneyArray,
+    TsVector,
+    TsVectorArray,
+    TsQuery,
+    TsQueryArray,
 
     // https://www.postgresql.org/docs/9.3/datatype-pseudo.html
     Void,
@@ -152,6 +168,7 @@ pub enum PgTypeKind {
     Array(PgTypeInfo),
     Enum(Arc<[String]>),
     Range(PgTypeInfo),
+    MultiRange(PgTypeInfo),
 }
 
 impl PgTypeInfo {
@@ -317,6 +334,10 @@ impl PgType {
             3807 => PgType::JsonbArray,
             3904 => PgType::Int4Range,
             3905 => PgType::Int4RangeArray,
+            3614 => PgType::TsVector,
+            3615 => PgType::TsQuery,
+            3643 => PgType::TsVectorArray,
+            3645 => PgType::TsQueryArray,
             3906 => PgType::NumRange,
             3907 => PgType::NumRangeArray,
             3908 => PgType::TsRange,
@@ -327,6 +348,18 @@ impl PgType {
             3913 => PgType::DateRangeArray,
             3926 => PgType::Int8Range,
             3927 => PgType::Int8RangeArray,
+            4451 => PgType::Int4MultiRange,
+            6150 => PgType::Int4MultiRangeArray,
+            4532 => PgType::NumMultiRange,
+            6151 => PgType::NumMultiRangeArray,
+            4533 => PgType::TsMultiRange,
+            6152 => PgType::TsMultiRangeArray,
+            4534 => PgType::TstzMultiRange,
+            6153 => PgType::TstzMultiRangeArray,
+            4535 => PgType::DateMultiRange,
+            6155 => PgType::DateMultiRangeArray,
+            4536 => PgType::Int8MultiRange,
+            6157 => PgType::Int8MultiRangeArray,
             4072 => PgType::Jsonpath,
             4073 => PgType::JsonpathArray,
 
@@ -374,6 +407,10 @@ impl PgType {
             PgType::Macaddr8Array => Oid(775),
             PgType::Money => Oid(790),
             PgType::MoneyArray => Oid(791),
+            PgType::TsVector => Oid(3614),
+            PgType::TsVectorArray => Oid(3643),
+            PgType::TsQuery => Oid(3615),
+            PgType::TsQueryArray => Oid(3645),
             PgType::Macaddr => Oid(829),
             PgType::Inet => Oid(869),
             PgType::BoolArray => Oid(1000),
@@ -435,6 +472,18 @@ impl PgType {
             PgType::DateRangeArray => Oid(3913),
             PgType::Int8Range => Oid(3926),
             PgType::Int8RangeArray => Oid(3927),
+            PgType::Int4MultiRange => Oid(4451),
+            PgType::Int4MultiRangeArray => Oid(6150),
+            PgType::NumMultiRange => Oid(4532),
+            PgType::NumMultiRangeArray => Oid(6151),
+            PgType::TsMultiRange => Oid(4533),
+            PgType::TsMultiRangeArray => Oid(6152),
+            PgType::TstzMultiRange => Oid(4534),
+            PgType::TstzMultiRangeArray => Oid(6153),
+            PgType::DateMultiRange => Oid(4535),
+            PgType::DateMultiRangeArray => Oid(6155),
+            PgType::Int8MultiRange => Oid(4536),
+            PgType::Int8MultiRangeArray => Oid(6157),
             PgType::Jsonpath => Oid(4072),
             PgType::JsonpathArray => Oid(4073),
             PgType::Custom(ty) => ty.oid,
@@ -535,10 +584,26 @@ impl PgType {
             PgType::DateRangeArray => "DATERANGE[]",
             PgType::Int8Range => "INT8RANGE",
             PgType::Int8RangeArray => "INT8RANGE[]",
+            PgType::Int4MultiRange => "INT4MULTIRANGE",
+            PgType::Int4MultiRangeArray => "INT4MULTIRANGE[]",
+            PgType::NumMultiRange => "NUMMULTIRANGE",
+            PgType::NumMultiRangeArray => "NUMMULTIRANGE[]",
+            PgType::TsMultiRange => "TSMULTIRANGE",
+            PgType::TsMultiRangeArray => "TSMULTIRANGE[]",
+            PgType::TstzMultiRange => "TSTZMULTIRANGE",
+            PgType::TstzMultiRangeArray => "TSTZMULTIRANGE[]",
+            PgType::DateMultiRange => "DATEMULTIRANGE",
+            PgType::DateMultiRangeArray => "DATEMULTIRANGE[]",
+            PgType::Int8MultiRange => "INT8MULTIRANGE",
+            PgType::Int8MultiRangeArray => "INT8MULTIRANGE[]",
             PgType::Jsonpath => "JSONPATH",
             PgType::JsonpathArray => "JSONPATH[]",
             PgType::Money => "MONEY",
             PgType::MoneyArray => "MONEY[]",
+            PgType::TsVector => "TSVECTOR",
+            PgType::TsVectorArray => "TSVECTOR[]",
+            PgType::TsQuery => "TSQUERY",
+            PgType::TsQueryArray => "TSQUERY[]",
             PgType::Void => "VOID",
             PgType::Custom(ty) => &ty.name,
             PgType::DeclareWithOid(_) => "?",
@@ -635,10 +700,26 @@ impl PgType {
             PgType::DateRangeArray => "_daterange",
             PgType::Int8Range => "int8range",
             PgType::Int8RangeArray => "_int8range",
+            PgType::Int4MultiRange => "int4multirange",
+            PgType::Int4MultiRangeArray => "_int4multirange",
+            PgType::NumMultiRange => "nummultirange",
+            PgType::NumMultiRangeArray => "_nummultirange",
+            PgType::TsMultiRange => "tsmultirange",
+            PgType::TsMultiRangeArray => "_tsmultirange",
+            PgType::TstzMultiRange => "tstzmultirange",
+            PgType::TstzMultiRangeArray => "_tstzmultirange",
+            PgType::DateMultiRange => "datemultirange",
+            PgType::DateMultiRangeArray => "_datemultirange",
+            PgType::Int8MultiRange => "int8multirange",
+            PgType::Int8MultiRangeArray => "_int8multirange",
             PgType::Jsonpath => "jsonpath",
             PgType::JsonpathArray => "_jsonpath",
             PgType::Money => "money",
             PgType::MoneyArray => "_money",
+            PgType::TsVector => "tsvector",
+            PgType::TsVectorArray => "_tsvector",
+            PgType::TsQuery => "tsquery",
+            PgType::TsQueryArray => "_tsquery",
             PgType::Void => "void",
             PgType::Custom(ty) => &ty.name,
             PgType::DeclareWithOid(_) => "?",
@@ -735,10 +816,26 @@ impl PgType {
             PgType::DateRangeArray => &PgTypeKind::Array(PgTypeInfo(PgType::DateRange)),
             PgType::Int8Range => &PgTypeKind::Range(PgTypeInfo::INT8),
             PgType::Int8RangeArray => &PgTypeKind::Array(PgTypeInfo(PgType::Int8Range)),
+            PgType::Int4MultiRange => &PgTypeKind::MultiRange(PgTypeInfo(PgType::Int4Range)),
+            PgType::Int4MultiRangeArray => &PgTypeKind::Array(PgTypeInfo(PgType::Int4MultiRange)),
+            PgType::NumMultiRange => &PgTypeKind::MultiRange(PgTypeInfo(PgType::NumRange)),
+            PgType::NumMultiRangeArray => &PgTypeKind::Array(PgTypeInfo(PgType::NumMultiRange)),
+            PgType::TsMultiRange => &PgTypeKind::MultiRange(PgTypeInfo(PgType::TsRange)),
+            PgType::TsMultiRangeArray => &PgTypeKind::Array(PgTypeInfo(PgType::TsMultiRange)),
+            PgType::TstzMultiRange => &PgTypeKind::MultiRange(PgTypeInfo(PgType::TstzRange)),
+            PgType::TstzMultiRangeArray => &PgTypeKind::Array(PgTypeInfo(PgType::TstzMultiRange)),
+            PgType::DateMultiRange => &PgTypeKind::MultiRange(PgTypeInfo(PgType::DateRange)),
+            PgType::DateMultiRangeArray => &PgTypeKind::Array(PgTypeInfo(PgType::DateMultiRange)),
+            PgType::Int8MultiRange => &PgTypeKind::MultiRange(PgTypeInfo(PgType::Int8Range)),
+            PgType::Int8MultiRangeArray => &PgTypeKind::Array(PgTypeInfo(PgType::Int8MultiRange)),
             PgType::Jsonpath => &PgTypeKind::Simple,
             PgType::JsonpathArray => &PgTypeKind::Array(PgTypeInfo(PgType::Jsonpath)),
             PgType::Money => &PgTypeKind::Simple,
             PgType::MoneyArray => &PgTypeKind::Array(PgTypeInfo(PgType::Money)),
+            PgType::TsVector => &PgTypeKind::Simple,
+            PgType::TsVectorArray => &PgTypeKind::Array(PgTypeInfo(PgType::TsVector)),
+            PgType::TsQuery => &PgTypeKind::Simple,
+            PgType::TsQueryArray => &PgTypeKind::Array(PgTypeInfo(PgType::TsQuery)),
 
             PgType::Void => &PgTypeKind::Pseudo,
 
@@ -804,6 +901,10 @@ impl PgType {
             PgType::Macaddr8Array => Some(Cow::Owned(PgTypeInfo(PgType::Macaddr8))),
             PgType::Money => None,
             PgType::MoneyArray => Some(Cow::Owned(PgTypeInfo(PgType::Money))),
+            PgType::TsVector => None,
+            PgType::TsVectorArray => Some(Cow::Owned(PgTypeInfo(PgType::TsVector))),
+            PgType::TsQuery => None,
+            PgType::TsQueryArray => Some(Cow::Owned(PgTypeInfo(PgType::TsQuery))),
             PgType::Macaddr => None,
             PgType::MacaddrArray => Some(Cow::Owned(PgTypeInfo(PgType::Macaddr))),
             PgType::Inet => None,
@@ -848,6 +949,18 @@ impl PgType {
             PgType::DateRangeArray => Some(Cow::Owned(PgTypeInfo(PgType::DateRange))),
             PgType::Int8Range => None,
             PgType::Int8RangeArray => Some(Cow::Owned(PgTypeInfo(PgType::Int8Range))),
+            PgType::Int4MultiRange => None,
+            PgType::Int4MultiRangeArray => Some(Cow::Owned(PgTypeInfo(PgType::Int4MultiRange))),
+            PgType::NumMultiRange => None,
+            PgType::NumMultiRangeArray => Some(Cow::Owned(PgTypeInfo(PgType::NumMultiRange))),
+            PgType::TsMultiRange => None,
+            PgType::TsMultiRangeArray => Some(Cow::Owned(PgTypeInfo(PgType::TsMultiRange))),
+            PgType::TstzMultiRange => None,
+            PgType::TstzMultiRangeArray => Some(Cow::Owned(PgTypeInfo(PgType::TstzMultiRange))),
+            PgType::DateMultiRange => None,
+            PgType::DateMultiRangeArray => Some(Cow::Owned(PgTypeInfo(PgType::DateMultiRange))),
+            PgType::Int8MultiRange => None,
+            PgType::Int8MultiRangeArray => Some(Cow::Owned(PgTypeInfo(PgType::Int8MultiRange))),
             PgType::Jsonpath => None,
             PgType::JsonpathArray => Some(Cow::Owned(PgTypeInfo(PgType::Jsonpath))),
             // There is no `UnknownArray`
@@ -862,6 +975,7 @@ impl PgType {
                 PgTypeKind::Array(ref elem_type_info) => Some(Cow::Borrowed(elem_type_info)),
                 PgTypeKind::Enum(_) => None,
                 PgTypeKind::Range(_) => None,
+                PgTypeKind::MultiRange(_) => None,
             },
             PgType::DeclareWithOid(oid) => {
                 unreachable!("(bug) use of unresolved type declaration [oid={}]", oid.0);
@@ -1102,6 +1216,40 @@ impl PgTypeInfo {
     pub(crate) const INT8_RANGE: Self = Self(PgType::Int8Range);
     pub(crate) const INT8_RANGE_ARRAY: Self = Self(PgType::Int8RangeArray);
 
+    //
+    // multirange types
+    // https://www.postgresql.org/docs/current/rangetypes.html
+    //
+
+    pub(crate) const INT4_MULTI_RANGE: Self = Self(PgType::Int4MultiRange);
+    pub(crate) const INT4_MULTI_RANGE_ARRAY: Self = Self(PgType::Int4MultiRangeArray);
+
+    pub(crate) const NUM_MULTI_RANGE: Self = Self(PgType::NumMultiRange);
+    pub(crate) const NUM_MULTI_RANGE_ARRAY: Self = Self(PgType::NumMultiRangeArray);
+
+    pub(crate) const TS_MULTI_RANGE: Self = Self(PgType::TsMultiRange);
+    pub(crate) const TS_MULTI_RANGE_ARRAY: Self = Self(PgType::TsMultiRangeArray);
+
+    pub(crate) const TSTZ_MULTI_RANGE: Self = Self(PgType::TstzMultiRange);
+    pub(crate) const TSTZ_MULTI_RANGE_ARRAY: Self = Self(PgType::TstzMultiRangeArray);
+
+    pub(crate) const DATE_MULTI_RANGE: Self = Self(PgType::DateMultiRange);
+    pub(crate) const DATE_MULTI_RANGE_ARRAY: Self = Self(PgType::DateMultiRangeArray);
+
+    pub(crate) const INT8_MULTI_RANGE: Self = Self(PgType::Int8MultiRange);
+    pub(crate) const INT8_MULTI_RANGE_ARRAY: Self = Self(PgType::Int8MultiRangeArray);
+
+    //
+    // text search types
+    // https://www.postgresql.org/docs/current/datatype-textsearch.html
+    //
+
+    pub(crate) const TS_VECTOR: Self = Self(PgType::TsVector);
+    pub(crate) const TS_VECTOR_ARRAY: Self = Self(PgType::TsVectorArray);
+
+    pub(crate) const TS_QUERY: Self = Self(PgType::TsQuery);
+    pub(crate) const TS_QUERY_ARRAY: Self = Self(PgType::TsQueryArray);
+
     //
     // pseudo types
     // https://www.postgresql.org/docs/9.3/datatype-pseudo.html
@@ -0,0 +1,498 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::postgres::{
+    PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres,
+};
+use crate::types::Type;
+
+// https://www.postgresql.org/docs/current/datatype-geometric.html
+// https://github.com/postgres/postgres/blob/master/src/backend/utils/adt/geo_ops.c
+
+/// A point on a plane, the Postgres `POINT` type.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct PgPoint {
+    pub x: f64,
+    pub y: f64,
+}
+
+/// An infinite line, the Postgres `LINE` type.
+///
+/// The line is represented by the linear equation `a*x + b*y + c = 0`, where `a` and `b` are
+/// not both zero.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct PgLine {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+}
+
+/// A finite line segment, the Postgres `LSEG` type.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct PgLSeg {
+    pub start: PgPoint,
+    pub end: PgPoint,
+}
+
+/// A rectangular box, the Postgres `BOX` type.
+///
+/// The server reorders the coordinates of the corners so that `upper_right` is above and to
+/// the right of `lower_left`.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct PgBox {
+    pub upper_right: PgPoint,
+    pub lower_left: PgPoint,
+}
+
+/// A list of connected points, the Postgres `PATH` type.
+///
+/// A closed path connects the last point back to the first one.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PgPath {
+    pub closed: bool,
+    pub points: Vec<PgPoint>,
+}
+
+/// A polygon given by the list of its vertices, the Postgres `POLYGON` type.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PgPolygon {
+    pub points: Vec<PgPoint>,
+}
+
+/// A circle, the Postgres `CIRCLE` type.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct PgCircle {
+    pub center: PgPoint,
+    pub radius: f64,
+}
+
+impl PgPoint {
+    pub fn new(x: f64, y: f64) -> Self {
+        Self { x, y }
+    }
+}
+
+macro_rules! impl_type {
+    ($ty:ty, $type_info:ident, $array_type_info:ident) => {
+        impl Type<Postgres> for $ty {
+            fn type_info() -> PgTypeInfo {
+                PgTypeInfo::$type_info
+            }
+        }
+
+        impl PgHasArrayType for $ty {
+            fn array_type_info() -> PgTypeInfo {
+                PgTypeInfo::$array_type_info
+            }
+        }
+
+        impl Encode<'_, Postgres> for $ty {
+            fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+                self.encode_binary(buf);
+
+                IsNull::No
+            }
+        }
+
+        impl Decode<'_, Postgres> for $ty {
+            fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+                match value.format() {
+                    PgValueFormat::Binary => {
+                        let mut buf = value.as_bytes()?;
+                        let decoded = Self::decode_binary(&mut buf)?;
+
+                        if !buf.is_empty() {
+                            return Err(
+                                concat!("trailing bytes in a ", stringify!($ty), " value").into()
+                            );
+                        }
+
+                        Ok(decoded)
+                    }
+
+                    PgValueFormat::Text => Self::decode_text(value.as_str()?),
+                }
+            }
+        }
+    };
+}
+
+impl_type!(PgPoint, POINT, POINT_ARRAY);
+impl_type!(PgLine, LINE, LINE_ARRAY);
+impl_type!(PgLSeg, LSEG, LSEG_ARRAY);
+impl_type!(PgBox, BOX, BOX_ARRAY);
+impl_type!(PgPath, PATH, PATH_ARRAY);
+impl_type!(PgPolygon, POLYGON, POLYGON_ARRAY);
+impl_type!(PgCircle, CIRCLE, CIRCLE_ARRAY);
+
+impl PgPoint {
+    fn encode_binary(&self, buf: &mut Vec<u8>) {
+        buf.extend(&self.x.to_be_bytes());
+        buf.extend(&self.y.to_be_bytes());
+    }
+
+    fn decode_binary(buf: &mut &[u8]) -> Result<Self, BoxDynError> {
+        Ok(Self {
+            x: read_f64(buf)?,
+            y: read_f64(buf)?,
+        })
+    }
+
+    // `(x,y)`
+    fn decode_text(s: &str) -> Result<Self, BoxDynError> {
+        let [x, y] = parse_numbers(s, "POINT")?;
+
+        Ok(Self { x, y })
+    }
+}
+
+impl PgLine {
+    fn encode_binary(&self, buf: &mut Vec<u8>) {
+        buf.extend(&self.a.to_be_bytes());
+        buf.extend(&self.b.to_be_bytes());
+        buf.extend(&self.c.to_be_bytes());
+    }
+
+    fn decode_binary(buf: &mut &[u8]) -> Result<Self, BoxDynError> {
+        Ok(Self {
+            a: read_f64(buf)?,
+            b: read_f64(buf)?,
+            c: read_f64(buf)?,
+        })
+    }
+
+    // `{a,b,c}`
+    fn decode_text(s: &str) -> Result<Self, BoxDynError> {
+        let [a, b, c] = parse_numbers(s, "LINE")?;
+
+        Ok(Self { a, b, c })
+    }
+}
+
+impl PgLSeg {
+    fn encode_binary(&self, buf: &mut Vec<u8>) {
+        self.start.encode_binary(buf);
+        self.end.encode_binary(buf);
+    }
+
+    fn decode_binary(buf: &mut &[u8]) -> Result<Self, BoxDynError> {
+        Ok(Self {
+            start: PgPoint::decode_binary(buf)?,
+            end: PgPoint::decode_binary(buf)?,
+        })
+    }
+
+    // `[(x1,y1),(x2,y2)]`
+    fn decode_text(s: &str) -> Result<Self, BoxDynError> {
+        let [x1, y1, x2, y2] = parse_numbers(s, "LSEG")?;
+
+        Ok(Self {
+            start: PgPoint::new(x1, y1),
+            end: PgPoint::new(x2, y2),
+        })
+    }
+}
+
+impl PgBox {
+    fn encode_binary(&self, buf: &mut Vec<u8>) {
+        self.upper_right.encode_binary(buf);
+        self.lower_left.encode_binary(buf);
+    }
+
+    fn decode_binary(buf: &mut &[u8]) -> Result<Self, BoxDynError> {
+        Ok(Self {
+            upper_right: PgPoint::decode_binary(buf)?,
+            lower_left: PgPoint::decode_binary(buf)?,
+        })
+    }
+
+    // `(x1,y1),(x2,y2)`
+    fn decode_text(s: &str) -> Result<Self, BoxDynError> {
+        let [x1, y1, x2, y2] = parse_numbers(s, "BOX")?;
+
+        Ok(Self {
+            upper_right: PgPoint::new(x1, y1),
+            lower_left: PgPoint::new(x2, y2),
+        })
+    }
+}
+
+impl PgPath {
+    fn encode_binary(&self, buf: &mut Vec<u8>) {
+        buf.push(u8::from(self.closed));
+        encode_points(&self.points, buf);
+    }
+
+    fn decode_binary(buf: &mut &[u8]) -> Result<Self, BoxDynError> {
+        let (&closed, rest) = buf
+            .split_first()
+            .ok_or("unexpected end of a PgPath value")?;
+        *buf = rest;
+
+        Ok(Self {
+            closed: closed != 0,
+            points: decode_points(buf)?,
+        })
+    }
+
+    // `[(x1,y1),...]` when open, `((x1,y1),...)` when closed
+    fn decode_text(s: &str) -> Result<Self, BoxDynError> {
+        Ok(Self {
+            closed: !s.trim_start().starts_with('['),
+            points: parse_points(s, "PATH")?,
+        })
+    }
+}
+
+impl PgPolygon {
+    fn encode_binary(&self, buf: &mut Vec<u8>) {
+        encode_points(&self.points, buf);
+    }
+
+    fn decode_binary(buf: &mut &[u8]) -> Result<Self, BoxDynError> {
+        Ok(Self {
+            points: decode_points(buf)?,
+        })
+    }
+
+    // `((x1,y1),...)`
+    fn decode_text(s: &str) -> Result<Self, BoxDynError> {
+        Ok(Self {
+            points: parse_points(s, "POLYGON")?,
+        })
+    }
+}
+
+impl PgCircle {
+    fn encode_binary(&self, buf: &mut Vec<u8>) {
+        self.center.encode_binary(buf);
+        buf.extend(&self.radius.to_be_bytes());
+    }
+
+    fn decode_binary(buf: &mut &[u8]) -> Result<Self, BoxDynError> {
+        Ok(Self {
+            center: PgPoint::decode_binary(buf)?,
+            radius: read_f64(buf)?,
+        })
+    }
+
+    // `<(x,y),r>`
+    fn decode_text(s: &str) -> Result<Self, BoxDynError> {
+        let [x, y, radius] = parse_numbers(s, "CIRCLE")?;
+
+        Ok(Self {
+            center: PgPoint::new(x, y),
+            radius,
+        })
+    }
+}
+
+impl Display for PgPoint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "({},{})", self.x, self.y)
+    }
+}
+
+impl Display for PgLine {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{{{},{},{}}}", self.a, self.b, self.c)
+    }
+}
+
+impl Display for PgLSeg {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "[{},{}]", self.start, self.end)
+    }
+}
+
+impl Display for PgBox {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.upper_right, self.lower_left)
+    }
+}
+
+impl Display for PgPath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (open, close) = if self.closed { ('(', ')') } else { ('[', ']') };
+
+        write!(f, "{}", open)?;
+        write_points(&self.points, f)?;
+        write!(f, "{}", close)
+    }
+}
+
+impl Display for PgPolygon {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "(")?;
+        write_points(&self.points, f)?;
+        write!(f, ")")
+    }
+}
+
+impl Display for PgCircle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "<{},{}>", self.center, self.radius)
+    }
+}
+
+fn write_points(points: &[PgPoint], f: &mut Formatter<'_>) -> fmt::Result {
+    for (i, point) in points.iter().enumerate() {
+        if i > 0 {
+            write!(f, ",")?;
+        }
+
+        write!(f, "{}", point)?;
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+fn encode_points(points: &[PgPoint], buf: &mut Vec<u8>) {
+    buf.extend(&(points.len() as i32).to_be_bytes());
+
+    for point in points {
+        point.encode_binary(buf);
+    }
+}
+
+fn decode_points(buf: &mut &[u8]) -> Result<Vec<PgPoint>, BoxDynError> {
+    if buf.len() < 4 {
+        return Err("unexpected end of a geometric value".into());
+    }
+
+    let (count, rest) = buf.split_at(4);
+    *buf = rest;
+
+    let count = i32::from_be_bytes([count[0], count[1], count[2], count[3]]);
+    let count = usize::try_from(count).map_err(|_| "negative number of points")?;
+
+    // each point takes 16 bytes, checked before allocating
+    if buf.len() < count.saturating_mul(16) {
+        return Err("unexpected end of a geometric value".into());
+    }
+
+    (0..count).map(|_| PgPoint::decode_binary(buf)).collect()
+}
+
+fn read_f64(buf: &mut &[u8]) -> Result<f64, BoxDynError> {
+    if buf.len() < 8 {
+        return Err("unexpected end of a geometric value".into());
+    }
+
+    let (value, rest) = buf.split_at(8);
+    *buf = rest;
+
+    let mut bytes = [0; 8];
+    bytes.copy_from_slice(value);
+
+    Ok(f64::from_be_bytes(bytes))
+}
+
+// the text formats are numbers separated by commas, grouped with various brackets which
+// only matter for paths
+fn parse_all_numbers(s: &str, type_name: &str) -> Result<Vec<f64>, BoxDynError> {
+    s.split(|c: char| c == ',' || "()[]{}<>".contains(c))
+        .map(str::trim)
+        .filter(|number| !number.is_empty())
+        .map(|number| {
+            number
+                .parse()
+                .map_err(|_| format!("invalid number {:?} in a {} value", number, type_name).into())
+        })
+        .collect()
+}
+
+fn parse_numbers<const N: usize>(s: &str, type_name: &str) -> Result<[f64; N], BoxDynError> {
+    let numbers = parse_all_numbers(s, type_name)?;
+
+    <[f64; N]>::try_from(numbers).map_err(|numbers| {
+        format!(
+            "expected {} numbers in a {} value, found {}",
+            N,
+            type_name,
+            numbers.len()
+        )
+        .into()
+    })
+}
+
+fn parse_points(s: &str, type_name: &str) -> Result<Vec<PgPoint>, BoxDynError> {
+    let numbers = parse_all_numbers(s, type_name)?;
+
+    if numbers.len() % 2 != 0 {
+        return Err(format!("odd number of coordinates in a {} value", type_name).into());
+    }
+
+    Ok(numbers
+        .chunks_exact(2)
+        .map(|point| PgPoint::new(point[0], point[1]))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_text() {
+        assert_eq!(
+            PgPoint::decode_text("(1.5,-2)").unwrap(),
+            PgPoint::new(1.5, -2.0)
+        );
+        assert_eq!(
+            PgLine::decode_text("{1,-1,0}").unwrap(),
+            PgLine {
+                a: 1.0,
+                b: -1.0,
+                c: 0.0
+            }
+        );
+        assert_eq!(
+            PgBox::decode_text("(3,4),(1,2)").unwrap(),
+            PgBox {
+                upper_right: PgPoint::new(3.0, 4.0),
+                lower_left: PgPoint::new(1.0, 2.0),
+            }
+        );
+        assert_eq!(
+            PgPath::decode_text("[(0,0),(1,1e+20)]").unwrap(),
+            PgPath {
+                closed: false,
+                points: vec![PgPoint::new(0.0, 0.0), PgPoint::new(1.0, 1e20)],
+            }
+        );
+        assert!(PgPath::decode_text("((0,0),(1,1))").unwrap().closed);
+        assert_eq!(
+            PgCircle::decode_text("<(1,2),Infinity>").unwrap(),
+            PgCircle {
+                center: PgPoint::new(1.0, 2.0),
+                radius: f64::INFINITY,
+            }
+        );
+
+        assert!(PgPoint::decode_text("(1,2,3)").is_err());
+        assert!(PgPolygon::decode_text("((0,0),(1))").is_err());
+        assert!(PgLSeg::decode_text("[(0,0),(1,x)]").is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        let path = PgPath {
+            closed: true,
+            points: vec![PgPoint::new(0.0, 0.0), PgPoint::new(1.5, -1.0)],
+        };
+
+        assert_eq!(path.to_string(), "((0,0),(1.5,-1))");
+        assert_eq!(PgPath::decode_text(&path.to_string()).unwrap(), path);
+
+        let circle = PgCircle {
+            center: PgPoint::new(1.0, 2.0),
+            radius: 3.0,
+        };
+
+        assert_eq!(circle.to_string(), "<(1,2),3>");
+    }
+}
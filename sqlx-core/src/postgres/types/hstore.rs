@@ -0,0 +1,298 @@
+use std::collections::btree_map::{self, BTreeMap};
+use std::iter::Peekable;
+use std::ops::{Deref, DerefMut};
+use std::str::{from_utf8, Chars};
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::postgres::{
+    PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres,
+};
+use crate::types::Type;
+
+/// Key-value pairs stored in an `hstore` column in Postgres.
+///
+/// See https://www.postgresql.org/docs/current/hstore.html
+///
+/// Keys are unique and always have a value, which can be `NULL`. The pairs are kept sorted by
+/// key, as Postgres does not preserve their order.
+///
+/// ### Note: Extension Required
+/// The `hstore` extension is not enabled by default in Postgres. You will need to do so explicitly:
+///
+/// ```ignore
+/// CREATE EXTENSION IF NOT EXISTS "hstore";
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// # use sqlx_core_oldapi::postgres::types::PgHStore;
+/// let mut flags = PgHStore::default();
+///
+/// flags.insert("dark_mode".to_owned(), Some("on".to_owned()));
+/// flags.insert("beta".to_owned(), None);
+///
+/// assert_eq!(flags.get("dark_mode"), Some(&Some("on".to_owned())));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PgHStore(pub BTreeMap<String, Option<String>>);
+
+impl Deref for PgHStore {
+    type Target = BTreeMap<String, Option<String>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for PgHStore {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl From<BTreeMap<String, Option<String>>> for PgHStore {
+    fn from(map: BTreeMap<String, Option<String>>) -> Self {
+        Self(map)
+    }
+}
+
+impl From<PgHStore> for BTreeMap<String, Option<String>> {
+    fn from(hstore: PgHStore) -> Self {
+        hstore.0
+    }
+}
+
+impl<K, V> FromIterator<(K, Option<V>)> for PgHStore
+where
+    K: Into<String>,
+    V: Into<String>,
+{
+    fn from_iter<I: IntoIterator<Item = (K, Option<V>)>>(iter: I) -> Self {
+        Self(
+            iter.into_iter()
+                .map(|(key, value)| (key.into(), value.map(Into::into)))
+                .collect(),
+        )
+    }
+}
+
+impl IntoIterator for PgHStore {
+    type Item = (String, Option<String>);
+    type IntoIter = btree_map::IntoIter<String, Option<String>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl Type<Postgres> for PgHStore {
+    fn type_info() -> PgTypeInfo {
+        // Since `hstore` is enabled by an extension, it does not have a stable OID.
+        PgTypeInfo::with_name("hstore")
+    }
+}
+
+impl PgHasArrayType for PgHStore {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::with_name("_hstore")
+    }
+}
+
+// https://github.com/postgres/postgres/blob/master/contrib/hstore/hstore_io.c (`hstore_send`)
+
+impl Encode<'_, Postgres> for PgHStore {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+        buf.extend(&(self.0.len() as i32).to_be_bytes());
+
+        for (key, value) in &self.0 {
+            buf.extend(&(key.len() as i32).to_be_bytes());
+            buf.extend(key.as_bytes());
+
+            match value {
+                Some(value) => {
+                    buf.extend(&(value.len() as i32).to_be_bytes());
+                    buf.extend(value.as_bytes());
+                }
+
+                None => buf.extend(&(-1_i32).to_be_bytes()),
+            }
+        }
+
+        IsNull::No
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for PgHStore {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        match value.format() {
+            PgValueFormat::Binary => decode_binary(value.as_bytes()?),
+            PgValueFormat::Text => decode_text(value.as_str()?),
+        }
+    }
+}
+
+fn decode_binary(mut buf: &[u8]) -> Result<PgHStore, BoxDynError> {
+    let count = read_length(&mut buf)?.ok_or("negative number of pairs in an HSTORE value")?;
+
+    let mut map = BTreeMap::new();
+
+    for _ in 0..count {
+        let key = read_string(&mut buf)?.ok_or("NULL key in an HSTORE value")?;
+        let value = read_string(&mut buf)?;
+
+        map.insert(key, value);
+    }
+
+    Ok(PgHStore(map))
+}
+
+// a negative length denotes a NULL value
+fn read_length(buf: &mut &[u8]) -> Result<Option<usize>, BoxDynError> {
+    if buf.len() < 4 {
+        return Err("unexpected end of an HSTORE value".into());
+    }
+
+    let (length, rest) = buf.split_at(4);
+    *buf = rest;
+
+    let length = i32::from_be_bytes([length[0], length[1], length[2], length[3]]);
+
+    Ok(usize::try_from(length).ok())
+}
+
+fn read_string(buf: &mut &[u8]) -> Result<Option<String>, BoxDynError> {
+    let length = match read_length(buf)? {
+        Some(length) => length,
+        None => return Ok(None),
+    };
+
+    if buf.len() < length {
+        return Err("unexpected end of an HSTORE value".into());
+    }
+
+    let (string, rest) = buf.split_at(length);
+    *buf = rest;
+
+    Ok(Some(from_utf8(string)?.to_owned()))
+}
+
+// the text format is a list of `"key"=>"value"` pairs separated by commas, where a value can be
+// the unquoted `NULL`, and quotes and backslashes inside quoted strings are escaped with a
+// backslash
+fn decode_text(s: &str) -> Result<PgHStore, BoxDynError> {
+    let mut chars = s.chars().peekable();
+    let mut map = BTreeMap::new();
+
+    loop {
+        skip_whitespace(&mut chars);
+
+        if chars.peek().is_none() {
+            return Ok(PgHStore(map));
+        }
+
+        let key = read_token(&mut chars)?;
+
+        skip_whitespace(&mut chars);
+
+        if chars.next() != Some('=') || chars.next() != Some('>') {
+            return Err(format!("expected `=>` after key {:?} in an HSTORE value", key.0).into());
+        }
+
+        skip_whitespace(&mut chars);
+
+        let value = match read_token(&mut chars)? {
+            (value, false) if value.eq_ignore_ascii_case("null") => None,
+            (value, _) => Some(value),
+        };
+
+        // the first value of a key is kept, as Postgres does
+        map.entry(key.0).or_insert(value);
+
+        skip_whitespace(&mut chars);
+
+        match chars.next() {
+            Some(',') | None => {}
+            Some(c) => {
+                return Err(format!("unexpected {:?} after a pair in an HSTORE value", c).into())
+            }
+        }
+    }
+}
+
+fn skip_whitespace(chars: &mut Peekable<Chars<'_>>) {
+    while chars.next_if(|c| c.is_whitespace()).is_some() {}
+}
+
+// returns the token and whether it was quoted
+fn read_token(chars: &mut Peekable<Chars<'_>>) -> Result<(String, bool), BoxDynError> {
+    let mut token = String::new();
+
+    if chars.next_if_eq(&'"').is_some() {
+        loop {
+            match chars.next() {
+                Some('"') => return Ok((token, true)),
+                Some('\\') => token.extend(chars.next()),
+                Some(c) => token.push(c),
+                None => return Err("unterminated quoted string in an HSTORE value".into()),
+            }
+        }
+    }
+
+    while let Some(c) = chars.next_if(|&c| !c.is_whitespace() && c != ',' && c != '=') {
+        match c {
+            '\\' => token.extend(chars.next()),
+            c => token.push(c),
+        }
+    }
+
+    if token.is_empty() {
+        return Err("expected a key or value in an HSTORE value".into());
+    }
+
+    Ok((token, false))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_text() {
+        let hstore = decode_text(r#""a"=>"1", "b"=>NULL, "c\"d"=>"e\\f", "g"=>"NULL""#).unwrap();
+
+        assert_eq!(
+            hstore,
+            PgHStore::from_iter([
+                ("a", Some("1")),
+                ("b", None),
+                ("c\"d", Some("e\\f")),
+                ("g", Some("NULL")),
+            ])
+        );
+
+        assert_eq!(
+            decode_text("a => 1,b=>null").unwrap(),
+            PgHStore::from_iter([("a", Some("1")), ("b", None::<&str>)])
+        );
+        assert_eq!(decode_text("").unwrap(), PgHStore::default());
+
+        assert!(decode_text(r#""a"=>"#).is_err());
+        assert!(decode_text(r#""a"=>"1"#).is_err());
+        assert!(decode_text(r#""a" "1""#).is_err());
+    }
+
+    #[test]
+    fn test_encode_decode_binary() {
+        let hstore = PgHStore::from_iter([("a", Some("1")), ("b", None), ("", Some(""))]);
+
+        let mut buf = PgArgumentBuffer::default();
+        let _ = Encode::<Postgres>::encode_by_ref(&hstore, &mut buf);
+
+        assert_eq!(decode_binary(&buf).unwrap(), hstore);
+        assert!(decode_binary(&buf[..buf.len() - 1]).is_err());
+    }
+}
@@ -15,7 +15,18 @@
 //! | `&[u8]`, `Vec<u8>`                    | BYTEA                                                |
 //! | [`PgInterval`]                        | INTERVAL                                             |
 //! | [`PgRange<T>`](PgRange)               | INT8RANGE, INT4RANGE, TSRANGE, TSTZRANGE, DATERANGE, NUMRANGE |
+//! | [`PgMultiRange<T>`](PgMultiRange)     | INT8MULTIRANGE, INT4MULTIRANGE, TSMULTIRANGE, TSTZMULTIRANGE, DATEMULTIRANGE, NUMMULTIRANGE |
 //! | [`PgMoney`]                           | MONEY                                                |
+//! | [`PgHStore`]                          | HSTORE                                               |
+//! | [`PgPoint`]                           | POINT                                                |
+//! | [`PgLine`]                            | LINE                                                 |
+//! | [`PgLSeg`]                            | LSEG                                                 |
+//! | [`PgBox`]                             | BOX                                                  |
+//! | [`PgPath`]                            | PATH                                                 |
+//! | [`PgPolygon`]                         | POLYGON                                              |
+//! | [`PgCircle`]                          | CIRCLE                                               |
+//! | [`PgTsVector`]                        | TSVECTOR                                             |
+//! | [`PgTsQuery`]                         | TSQUERY                                              |
 //!
 //!
 //! ### [`bigdecimal`](https://crates.io/crates/bigdecimal)
@@ -173,15 +184,20 @@ mod array;
 mod bool;
 mod bytes;
 mod float;
+mod geometry;
+mod hstore;
 mod int;
 mod interval;
 mod lquery;
 mod ltree;
 mod money;
+mod multirange;
 mod oid;
 mod range;
 mod record;
 mod str;
+mod tsquery;
+mod tsvector;
 mod tuple;
 mod void;
 
@@ -221,6 +237,8 @@ mod mac_address;
 mod bit_vec;
 
 pub use array::PgHasArrayType;
+pub use geometry::{PgBox, PgCircle, PgLSeg, PgLine, PgPath, PgPoint, PgPolygon};
+pub use hstore::PgHStore;
 pub use interval::PgInterval;
 pub use lquery::PgLQuery;
 pub use lquery::PgLQueryLevel;
@@ -230,8 +248,11 @@ pub use ltree::PgLTree;
 pub use ltree::PgLTreeLabel;
 pub use ltree::PgLTreeParseError;
 pub use money::PgMoney;
+pub use multirange::PgMultiRange;
 pub use oid::Oid;
 pub use range::PgRange;
+pub use tsquery::{PgTsQuery, PgTsQueryNode};
+pub use tsvector::{PgTsLexeme, PgTsPosition, PgTsVector, PgTsWeight};
 
 #[cfg(any(feature = "chrono", feature = "time"))]
 pub use time_tz::PgTimeTz;
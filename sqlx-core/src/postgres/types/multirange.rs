@@ -0,0 +1,432 @@
+use std::fmt::{self, Display, Formatter};
+
+use bytes::Buf;
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::postgres::type_info::PgTypeKind;
+use crate::postgres::types::range::range_compatible;
+use crate::postgres::types::PgRange;
+use crate::postgres::{
+    PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres,
+};
+use crate::types::Type;
+
+/// A set of non-overlapping ranges, the Postgres multirange types (`INT4MULTIRANGE`,
+/// `DATEMULTIRANGE`, ...), available since Postgres 14.
+///
+/// Postgres merges the ranges that overlap or touch and sorts them, so the ranges read back
+/// from the database can differ from the ones that were written.
+///
+/// # Example
+///
+/// ```rust
+/// # use sqlx_core_oldapi::postgres::types::PgMultiRange;
+/// // {[1,3),[5,8)}
+/// let multirange: PgMultiRange<i32> = [1..3, 5..8].into_iter().collect();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PgMultiRange<T> {
+    pub ranges: Vec<PgRange<T>>,
+}
+
+impl<T> Default for PgMultiRange<T> {
+    fn default() -> Self {
+        Self { ranges: Vec::new() }
+    }
+}
+
+impl<T> From<Vec<PgRange<T>>> for PgMultiRange<T> {
+    fn from(ranges: Vec<PgRange<T>>) -> Self {
+        Self { ranges }
+    }
+}
+
+impl<T, R> FromIterator<R> for PgMultiRange<T>
+where
+    R: Into<PgRange<T>>,
+{
+    fn from_iter<I: IntoIterator<Item = R>>(iter: I) -> Self {
+        Self {
+            ranges: iter.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl<T> IntoIterator for PgMultiRange<T> {
+    type Item = PgRange<T>;
+    type IntoIter = std::vec::IntoIter<PgRange<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ranges.into_iter()
+    }
+}
+
+impl Type<Postgres> for PgMultiRange<i32> {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::INT4_MULTI_RANGE
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        multirange_compatible::<i32>(ty)
+    }
+}
+
+impl Type<Postgres> for PgMultiRange<i64> {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::INT8_MULTI_RANGE
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        multirange_compatible::<i64>(ty)
+    }
+}
+
+#[cfg(feature = "bigdecimal")]
+impl Type<Postgres> for PgMultiRange<bigdecimal::BigDecimal> {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::NUM_MULTI_RANGE
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        multirange_compatible::<bigdecimal::BigDecimal>(ty)
+    }
+}
+
+#[cfg(feature = "decimal")]
+impl Type<Postgres> for PgMultiRange<rust_decimal::Decimal> {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::NUM_MULTI_RANGE
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        multirange_compatible::<rust_decimal::Decimal>(ty)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl Type<Postgres> for PgMultiRange<chrono::NaiveDate> {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::DATE_MULTI_RANGE
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        multirange_compatible::<chrono::NaiveDate>(ty)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl Type<Postgres> for PgMultiRange<chrono::NaiveDateTime> {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::TS_MULTI_RANGE
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        multirange_compatible::<chrono::NaiveDateTime>(ty)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<Tz: chrono::TimeZone> Type<Postgres> for PgMultiRange<chrono::DateTime<Tz>> {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::TSTZ_MULTI_RANGE
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        multirange_compatible::<chrono::DateTime<Tz>>(ty)
+    }
+}
+
+#[cfg(feature = "time")]
+impl Type<Postgres> for PgMultiRange<time::Date> {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::DATE_MULTI_RANGE
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        multirange_compatible::<time::Date>(ty)
+    }
+}
+
+#[cfg(feature = "time")]
+impl Type<Postgres> for PgMultiRange<time::PrimitiveDateTime> {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::TS_MULTI_RANGE
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        multirange_compatible::<time::PrimitiveDateTime>(ty)
+    }
+}
+
+#[cfg(feature = "time")]
+impl Type<Postgres> for PgMultiRange<time::OffsetDateTime> {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::TSTZ_MULTI_RANGE
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        multirange_compatible::<time::OffsetDateTime>(ty)
+    }
+}
+
+impl PgHasArrayType for PgMultiRange<i32> {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::INT4_MULTI_RANGE_ARRAY
+    }
+}
+
+impl PgHasArrayType for PgMultiRange<i64> {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::INT8_MULTI_RANGE_ARRAY
+    }
+}
+
+#[cfg(feature = "bigdecimal")]
+impl PgHasArrayType for PgMultiRange<bigdecimal::BigDecimal> {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::NUM_MULTI_RANGE_ARRAY
+    }
+}
+
+#[cfg(feature = "decimal")]
+impl PgHasArrayType for PgMultiRange<rust_decimal::Decimal> {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::NUM_MULTI_RANGE_ARRAY
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl PgHasArrayType for PgMultiRange<chrono::NaiveDate> {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::DATE_MULTI_RANGE_ARRAY
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl PgHasArrayType for PgMultiRange<chrono::NaiveDateTime> {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::TS_MULTI_RANGE_ARRAY
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<Tz: chrono::TimeZone> PgHasArrayType for PgMultiRange<chrono::DateTime<Tz>> {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::TSTZ_MULTI_RANGE_ARRAY
+    }
+}
+
+#[cfg(feature = "time")]
+impl PgHasArrayType for PgMultiRange<time::Date> {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::DATE_MULTI_RANGE_ARRAY
+    }
+}
+
+#[cfg(feature = "time")]
+impl PgHasArrayType for PgMultiRange<time::PrimitiveDateTime> {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::TS_MULTI_RANGE_ARRAY
+    }
+}
+
+#[cfg(feature = "time")]
+impl PgHasArrayType for PgMultiRange<time::OffsetDateTime> {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::TSTZ_MULTI_RANGE_ARRAY
+    }
+}
+
+impl<'q, T> Encode<'q, Postgres> for PgMultiRange<T>
+where
+    T: Encode<'q, Postgres>,
+{
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+        // https://github.com/postgres/postgres/blob/REL_14_0/src/backend/utils/adt/multirangetypes.c#L326
+
+        let count = i32::try_from(self.ranges.len()).expect("too many ranges in a multirange");
+
+        buf.extend(&count.to_be_bytes());
+
+        for range in &self.ranges {
+            // each range is prefixed with its length
+            buf.encode(range);
+        }
+
+        IsNull::No
+    }
+}
+
+impl<'r, T> Decode<'r, Postgres> for PgMultiRange<T>
+where
+    T: Type<Postgres> + for<'a> Decode<'a, Postgres>,
+{
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        match value.format {
+            PgValueFormat::Binary => {
+                let range_ty = if let PgTypeKind::MultiRange(range) = &value.type_info.0.kind() {
+                    range.clone()
+                } else {
+                    return Err(
+                        format!("unexpected non-multirange type {}", value.type_info).into(),
+                    );
+                };
+
+                let mut buf = value.as_bytes()?;
+
+                if buf.len() < 4 {
+                    return Err("unexpected end of a multirange value".into());
+                }
+
+                let count = buf.get_u32();
+                let mut ranges = Vec::new();
+
+                for _ in 0..count {
+                    if buf.len() < 4 {
+                        return Err("unexpected end of a multirange value".into());
+                    }
+
+                    ranges.push(PgRange::decode(PgValueRef::get(
+                        &mut buf,
+                        value.format,
+                        range_ty.clone(),
+                    ))?);
+                }
+
+                Ok(PgMultiRange { ranges })
+            }
+
+            PgValueFormat::Text => {
+                // https://github.com/postgres/postgres/blob/REL_14_0/src/backend/utils/adt/multirangetypes.c#L117
+
+                let ranges = split_ranges(value.as_str()?)?
+                    .into_iter()
+                    .map(|range| {
+                        PgRange::decode(PgValueRef {
+                            value: Some(range.as_bytes()),
+                            row: None,
+                            // not used to decode a range in text format, and the type of a
+                            // multirange received in text format may not have been resolved
+                            type_info: value.type_info.clone(),
+                            format: PgValueFormat::Text,
+                        })
+                    })
+                    .collect::<Result<_, _>>()?;
+
+                Ok(PgMultiRange { ranges })
+            }
+        }
+    }
+}
+
+// splits the text representation `{[1,3),[5,8)}` into the representations of its ranges
+fn split_ranges(s: &str) -> Result<Vec<&str>, BoxDynError> {
+    let s = s
+        .trim()
+        .strip_prefix('{')
+        .and_then(|s| s.strip_suffix('}'))
+        .ok_or("expected a multirange literal enclosed in `{` and `}`")?;
+
+    let mut ranges = Vec::new();
+    let mut start = None;
+    let mut in_quotes = false;
+    let mut in_escape = false;
+
+    for (i, ch) in s.char_indices() {
+        match ch {
+            _ if in_escape => in_escape = false,
+            '\\' => in_escape = true,
+            '"' => in_quotes = !in_quotes,
+            _ if in_quotes => {}
+
+            '[' | '(' if start.is_none() => start = Some(i),
+
+            ']' | ')' => {
+                let range_start = start
+                    .take()
+                    .ok_or("unexpected end of a range in a multirange literal")?;
+
+                ranges.push(&s[range_start..=i]);
+            }
+
+            ',' if start.is_none() => {}
+            _ if start.is_some() || ch.is_whitespace() => {}
+
+            _ => {
+                return Err(
+                    format!("unexpected `{}` between the ranges of a multirange", ch).into(),
+                );
+            }
+        }
+    }
+
+    if start.is_some() || in_quotes {
+        return Err("unterminated range in a multirange literal".into());
+    }
+
+    Ok(ranges)
+}
+
+impl<T> Display for PgMultiRange<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("{")?;
+
+        for (i, range) in self.ranges.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+
+            write!(f, "{}", range)?;
+        }
+
+        f.write_str("}")
+    }
+}
+
+fn multirange_compatible<E: Type<Postgres>>(ty: &PgTypeInfo) -> bool {
+    // we require the declared type to be a _multirange_ of ranges
+    // with an element type that is acceptable
+    if let PgTypeKind::MultiRange(range) = &ty.kind() {
+        return range_compatible::<E>(range);
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_ranges() {
+        assert_eq!(split_ranges("{}").unwrap(), Vec::<&str>::new());
+        assert_eq!(
+            split_ranges("{[1,3), [5,)}").unwrap(),
+            vec!["[1,3)", "[5,)"]
+        );
+        assert_eq!(
+            split_ranges(r#"{["2020-01-01 00:00:00","2021-01-01 00:00:00"),("a)\"",)}"#).unwrap(),
+            vec![
+                r#"["2020-01-01 00:00:00","2021-01-01 00:00:00")"#,
+                r#"("a)\"",)"#
+            ]
+        );
+
+        assert!(split_ranges("[1,3)").is_err());
+        assert!(split_ranges("{[1,3}").is_err());
+        assert!(split_ranges("{x}").is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        let multirange: PgMultiRange<i32> = [1..3, 5..8].into_iter().collect();
+
+        assert_eq!(multirange.to_string(), "{[1,3),[5,8)}");
+        assert_eq!(PgMultiRange::<i32>::default().to_string(), "{}");
+    }
+}
@@ -395,14 +395,16 @@ where
 
                 let mut element = String::new();
                 let mut done = false;
-                let mut quoted = false;
+                let mut quoted;
                 let mut in_quotes = false;
                 let mut in_escape = false;
-                let mut prev_ch = '\0';
+                let mut prev_ch;
                 let mut count = 0;
 
                 while !done {
                     element.clear();
+                    quoted = false;
+                    prev_ch = '\0';
 
                     loop {
                         match chars.next() {
@@ -514,7 +516,7 @@ where
     }
 }
 
-fn range_compatible<E: Type<Postgres>>(ty: &PgTypeInfo) -> bool {
+pub(super) fn range_compatible<E: Type<Postgres>>(ty: &PgTypeInfo) -> bool {
     // we require the declared type to be a _range_ with an
     // element type that is acceptable
     if let PgTypeKind::Range(element) = &ty.kind() {
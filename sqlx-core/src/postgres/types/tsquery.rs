@@ -0,0 +1,295 @@
+use std::io;
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::postgres::types::tsvector::{read_cstr, read_u16, read_u32, read_u8};
+use crate::postgres::types::PgTsWeight;
+use crate::postgres::{
+    PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres,
+};
+use crate::types::Type;
+
+// https://www.postgresql.org/docs/current/datatype-textsearch.html#DATATYPE-TSQUERY
+// https://github.com/postgres/postgres/blob/master/src/backend/utils/adt/tsquery.c
+
+const QI_VAL: u8 = 1;
+const QI_OPR: u8 = 2;
+
+const OP_NOT: u8 = 1;
+const OP_AND: u8 = 2;
+const OP_OR: u8 = 3;
+const OP_PHRASE: u8 = 4;
+
+/// A full-text search query, the Postgres `TSQUERY` type.
+///
+/// The query is a tree of lexemes combined with operators, or nothing for a query without
+/// any lexeme (which matches nothing).
+///
+/// Reading a `TSQUERY` value in text format is not supported and will cause an error. Postgres
+/// returns queries without any lexeme but does not accept them as parameters.
+///
+/// # Example
+///
+/// ```rust
+/// # use sqlx_core_oldapi::postgres::types::{PgTsQuery, PgTsQueryNode};
+/// // 'fat' & !'rat'
+/// let query = PgTsQuery::from(PgTsQueryNode::And(
+///     Box::new(PgTsQueryNode::lexeme("fat")),
+///     Box::new(PgTsQueryNode::Not(Box::new(PgTsQueryNode::lexeme("rat")))),
+/// ));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PgTsQuery {
+    pub root: Option<PgTsQueryNode>,
+}
+
+/// An operand or operator of a [`PgTsQuery`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PgTsQueryNode {
+    /// A lexeme to match (`'word':*AB`).
+    Lexeme {
+        word: String,
+
+        /// The weights a matching lexeme of the document must have, or empty for any weight.
+        weights: Vec<PgTsWeight>,
+
+        /// Whether the lexeme also matches the words it is a prefix of.
+        prefix: bool,
+    },
+
+    /// Matches documents that do not match the operand (`!`).
+    Not(Box<PgTsQueryNode>),
+
+    /// Matches documents that match both operands (`&`).
+    And(Box<PgTsQueryNode>, Box<PgTsQueryNode>),
+
+    /// Matches documents that match either operand (`|`).
+    Or(Box<PgTsQueryNode>, Box<PgTsQueryNode>),
+
+    /// Matches documents where the second operand follows the first one at the given distance
+    /// (`<->` for a distance of 1, `<N>` otherwise).
+    Phrase(Box<PgTsQueryNode>, Box<PgTsQueryNode>, u16),
+}
+
+impl PgTsQueryNode {
+    /// A lexeme matching only itself, with any weight.
+    pub fn lexeme(word: impl Into<String>) -> Self {
+        PgTsQueryNode::Lexeme {
+            word: word.into(),
+            weights: Vec::new(),
+            prefix: false,
+        }
+    }
+
+    // the number of items in the binary representation
+    fn len(&self) -> usize {
+        match self {
+            PgTsQueryNode::Lexeme { .. } => 1,
+            PgTsQueryNode::Not(operand) => 1 + operand.len(),
+            PgTsQueryNode::And(left, right)
+            | PgTsQueryNode::Or(left, right)
+            | PgTsQueryNode::Phrase(left, right, _) => 1 + left.len() + right.len(),
+        }
+    }
+
+    // items are in prefix order, with the right operand of an operator before the left one
+    fn encode(&self, buf: &mut Vec<u8>) {
+        let (operator, left, right) = match self {
+            PgTsQueryNode::Lexeme {
+                word,
+                weights,
+                prefix,
+            } => {
+                let weights = weights.iter().fold(0, |mask, weight| {
+                    mask | match weight {
+                        PgTsWeight::A => 1 << 3,
+                        PgTsWeight::B => 1 << 2,
+                        PgTsWeight::C => 1 << 1,
+                        PgTsWeight::D => 1,
+                    }
+                });
+
+                buf.push(QI_VAL);
+                buf.push(weights);
+                buf.push(u8::from(*prefix));
+                buf.extend(word.as_bytes());
+                buf.push(0);
+
+                return;
+            }
+
+            PgTsQueryNode::Not(operand) => {
+                buf.push(QI_OPR);
+                buf.push(OP_NOT);
+                operand.encode(buf);
+
+                return;
+            }
+
+            PgTsQueryNode::And(left, right) => (OP_AND, left, right),
+            PgTsQueryNode::Or(left, right) => (OP_OR, left, right),
+            PgTsQueryNode::Phrase(left, right, _) => (OP_PHRASE, left, right),
+        };
+
+        buf.push(QI_OPR);
+        buf.push(operator);
+
+        if let PgTsQueryNode::Phrase(_, _, distance) = self {
+            buf.extend(&distance.to_be_bytes());
+        }
+
+        right.encode(buf);
+        left.encode(buf);
+    }
+
+    fn decode(buf: &mut &[u8]) -> Result<Self, BoxDynError> {
+        match read_u8(buf)? {
+            QI_VAL => {
+                let mask = read_u8(buf)?;
+                let prefix = read_u8(buf)? != 0;
+                let word = read_cstr(buf)?;
+
+                let weights = [
+                    (1 << 3, PgTsWeight::A),
+                    (1 << 2, PgTsWeight::B),
+                    (1 << 1, PgTsWeight::C),
+                    (1, PgTsWeight::D),
+                ]
+                .into_iter()
+                .filter(|(bit, _)| mask & bit != 0)
+                .map(|(_, weight)| weight)
+                .collect();
+
+                Ok(PgTsQueryNode::Lexeme {
+                    word,
+                    weights,
+                    prefix,
+                })
+            }
+
+            QI_OPR => {
+                let operator = read_u8(buf)?;
+
+                let distance = match operator {
+                    OP_PHRASE => read_u16(buf)?,
+                    _ => 0,
+                };
+
+                if operator == OP_NOT {
+                    return Ok(PgTsQueryNode::Not(Box::new(Self::decode(buf)?)));
+                }
+
+                let right = Box::new(Self::decode(buf)?);
+                let left = Box::new(Self::decode(buf)?);
+
+                match operator {
+                    OP_AND => Ok(PgTsQueryNode::And(left, right)),
+                    OP_OR => Ok(PgTsQueryNode::Or(left, right)),
+                    OP_PHRASE => Ok(PgTsQueryNode::Phrase(left, right, distance)),
+                    _ => Err(format!("unknown operator {} in a TSQUERY value", operator).into()),
+                }
+            }
+
+            ty => Err(format!("unknown item type {} in a TSQUERY value", ty).into()),
+        }
+    }
+}
+
+impl From<PgTsQueryNode> for PgTsQuery {
+    fn from(root: PgTsQueryNode) -> Self {
+        Self { root: Some(root) }
+    }
+}
+
+impl Type<Postgres> for PgTsQuery {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::TS_QUERY
+    }
+}
+
+impl PgHasArrayType for PgTsQuery {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::TS_QUERY_ARRAY
+    }
+}
+
+impl Encode<'_, Postgres> for PgTsQuery {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+        let len = self.root.as_ref().map_or(0, PgTsQueryNode::len);
+
+        buf.extend(&(len as i32).to_be_bytes());
+
+        if let Some(root) = &self.root {
+            root.encode(buf);
+        }
+
+        IsNull::No
+    }
+}
+
+impl Decode<'_, Postgres> for PgTsQuery {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        match value.format() {
+            PgValueFormat::Binary => {
+                let mut buf = value.as_bytes()?;
+
+                let len = read_u32(&mut buf)?;
+
+                let root = if len == 0 {
+                    None
+                } else {
+                    Some(PgTsQueryNode::decode(&mut buf)?)
+                };
+
+                if root.as_ref().map_or(0, PgTsQueryNode::len) != len as usize || !buf.is_empty() {
+                    return Err("malformed TSQUERY value".into());
+                }
+
+                Ok(PgTsQuery { root })
+            }
+
+            PgValueFormat::Text => {
+                let error = io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "Reading a `TSQUERY` value in text format is not supported.",
+                );
+
+                Err(Box::new(error))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_decode_node() {
+        // ('a':*AB | !'b') <2> 'c'
+        let node = PgTsQueryNode::Phrase(
+            Box::new(PgTsQueryNode::Or(
+                Box::new(PgTsQueryNode::Lexeme {
+                    word: "a".to_owned(),
+                    weights: vec![PgTsWeight::A, PgTsWeight::B],
+                    prefix: true,
+                }),
+                Box::new(PgTsQueryNode::Not(Box::new(PgTsQueryNode::lexeme("b")))),
+            )),
+            Box::new(PgTsQueryNode::lexeme("c")),
+            2,
+        );
+
+        let mut buf = Vec::new();
+        node.encode(&mut buf);
+
+        assert_eq!(node.len(), 6);
+        assert_eq!(&buf[..4], &[QI_OPR, OP_PHRASE, 0, 2]);
+        assert_eq!(&buf[4..8], &[QI_VAL, 0, 0, b'c']);
+
+        assert_eq!(PgTsQueryNode::decode(&mut &buf[..]).unwrap(), node);
+        assert!(PgTsQueryNode::decode(&mut &buf[..buf.len() - 1]).is_err());
+    }
+}
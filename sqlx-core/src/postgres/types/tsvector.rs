@@ -0,0 +1,214 @@
+use std::cmp;
+use std::io;
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::postgres::{
+    PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres,
+};
+use crate::types::Type;
+
+// https://www.postgresql.org/docs/current/datatype-textsearch.html
+// https://github.com/postgres/postgres/blob/master/src/backend/utils/adt/tsvector.c
+
+// positions are stored in 14 bits, larger ones are clamped by Postgres
+const MAX_POSITION: u16 = (1 << 14) - 1;
+
+/// A document prepared for full-text search, the Postgres `TSVECTOR` type.
+///
+/// It is a list of distinct lexemes (normalized words), sorted by Postgres, each with the
+/// positions where it appears in the document, if they were kept.
+///
+/// Reading a `TSVECTOR` value in text format is not supported and will cause an error.
+///
+/// # Example
+///
+/// ```rust
+/// # use sqlx_core_oldapi::postgres::types::{PgTsLexeme, PgTsPosition, PgTsVector, PgTsWeight};
+/// // 'fat':2A 'rat':3
+/// let vector = PgTsVector {
+///     lexemes: vec![
+///         PgTsLexeme {
+///             word: "fat".to_owned(),
+///             positions: vec![PgTsPosition { position: 2, weight: PgTsWeight::A }],
+///         },
+///         PgTsLexeme {
+///             word: "rat".to_owned(),
+///             positions: vec![PgTsPosition::new(3)],
+///         },
+///     ],
+/// };
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PgTsVector {
+    pub lexemes: Vec<PgTsLexeme>,
+}
+
+/// A lexeme of a [`PgTsVector`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PgTsLexeme {
+    pub word: String,
+
+    /// The positions of the lexeme in the document, in ascending order. Empty if the
+    /// positions were stripped.
+    pub positions: Vec<PgTsPosition>,
+}
+
+/// A position of a [`PgTsLexeme`] in the document, from 1 to 16383.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PgTsPosition {
+    pub position: u16,
+    pub weight: PgTsWeight,
+}
+
+/// The weight of a lexeme, used to rank the matches of a search: usually `A` for the words
+/// of a title and `D` for those of the body.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PgTsWeight {
+    A,
+    B,
+    C,
+    #[default]
+    D,
+}
+
+impl PgTsPosition {
+    /// A position with the default weight `D`.
+    pub fn new(position: u16) -> Self {
+        Self {
+            position,
+            weight: PgTsWeight::D,
+        }
+    }
+}
+
+impl Type<Postgres> for PgTsVector {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::TS_VECTOR
+    }
+}
+
+impl PgHasArrayType for PgTsVector {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::TS_VECTOR_ARRAY
+    }
+}
+
+impl Encode<'_, Postgres> for PgTsVector {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+        buf.extend(&(self.lexemes.len() as i32).to_be_bytes());
+
+        for lexeme in &self.lexemes {
+            buf.extend(lexeme.word.as_bytes());
+            buf.push(0);
+
+            // Postgres rejects positions that are not strictly ascending
+            let mut positions: Vec<u16> = lexeme
+                .positions
+                .iter()
+                .map(|position| {
+                    let weight = match position.weight {
+                        PgTsWeight::A => 3,
+                        PgTsWeight::B => 2,
+                        PgTsWeight::C => 1,
+                        PgTsWeight::D => 0,
+                    };
+
+                    (weight << 14) | cmp::min(position.position, MAX_POSITION)
+                })
+                .collect();
+
+            positions.sort_by_key(|position| position & MAX_POSITION);
+            positions.dedup_by_key(|position| *position & MAX_POSITION);
+
+            buf.extend(&(positions.len() as u16).to_be_bytes());
+
+            for position in positions {
+                buf.extend(&position.to_be_bytes());
+            }
+        }
+
+        IsNull::No
+    }
+}
+
+impl Decode<'_, Postgres> for PgTsVector {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        match value.format() {
+            PgValueFormat::Binary => {
+                let mut buf = value.as_bytes()?;
+
+                let count = read_u32(&mut buf)?;
+                let mut lexemes = Vec::new();
+
+                for _ in 0..count {
+                    let word = read_cstr(&mut buf)?;
+                    let count = read_u16(&mut buf)?;
+
+                    let positions = (0..count)
+                        .map(|_| {
+                            let position = read_u16(&mut buf)?;
+
+                            Ok(PgTsPosition {
+                                position: position & MAX_POSITION,
+                                weight: match position >> 14 {
+                                    3 => PgTsWeight::A,
+                                    2 => PgTsWeight::B,
+                                    1 => PgTsWeight::C,
+                                    _ => PgTsWeight::D,
+                                },
+                            })
+                        })
+                        .collect::<Result<_, BoxDynError>>()?;
+
+                    lexemes.push(PgTsLexeme { word, positions });
+                }
+
+                Ok(PgTsVector { lexemes })
+            }
+
+            PgValueFormat::Text => {
+                let error = io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "Reading a `TSVECTOR` value in text format is not supported.",
+                );
+
+                Err(Box::new(error))
+            }
+        }
+    }
+}
+
+pub(super) fn read_u8(buf: &mut &[u8]) -> Result<u8, BoxDynError> {
+    let (&value, rest) = buf
+        .split_first()
+        .ok_or("unexpected end of a text search value")?;
+    *buf = rest;
+
+    Ok(value)
+}
+
+pub(super) fn read_u16(buf: &mut &[u8]) -> Result<u16, BoxDynError> {
+    Ok(u16::from_be_bytes([read_u8(buf)?, read_u8(buf)?]))
+}
+
+pub(super) fn read_u32(buf: &mut &[u8]) -> Result<u32, BoxDynError> {
+    Ok(u32::from_be_bytes([
+        read_u8(buf)?,
+        read_u8(buf)?,
+        read_u8(buf)?,
+        read_u8(buf)?,
+    ]))
+}
+
+// lexemes are sent as NUL-terminated strings
+pub(super) fn read_cstr(buf: &mut &[u8]) -> Result<String, BoxDynError> {
+    let end = memchr::memchr(0, buf).ok_or("unterminated lexeme in a text search value")?;
+
+    let word = std::str::from_utf8(&buf[..end])?.to_owned();
+    *buf = &buf[end + 1..];
+
+    Ok(word)
+}
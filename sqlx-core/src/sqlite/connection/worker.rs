@@ -15,9 +15,13 @@ use crate::sqlite::connection::describe::describe;
 use crate::sqlite::connection::establish::EstablishParams;
 use crate::sqlite::connection::ConnectionState;
 use crate::sqlite::connection::{execute, ConnectionHandleRaw};
-use crate::sqlite::{Sqlite, SqliteArguments, SqliteQueryResult, SqliteRow, SqliteStatement};
+use crate::sqlite::{
+    Sqlite, SqliteArguments, SqliteQueryResult, SqliteRow, SqliteStatement,
+    SqliteTransactionBehavior,
+};
 use crate::transaction::{
-    begin_ansi_transaction_sql, commit_ansi_transaction_sql, rollback_ansi_transaction_sql,
+    begin_ansi_transaction_sql, check_savepoint_options, commit_ansi_transaction_sql,
+    rollback_ansi_transaction_sql, TransactionOptions,
 };
 
 // Each SQLite connection has a dedicated thread.
@@ -55,6 +59,7 @@ enum Command {
         tx: flume::Sender<Result<Either<SqliteQueryResult, SqliteRow>, Error>>,
     },
     Begin {
+        options: TransactionOptions,
         tx: rendezvous_oneshot::Sender<Result<(), Error>>,
     },
     Commit {
@@ -158,14 +163,14 @@ impl ConnectionWorker {
 
                             update_cached_statements_size(&conn, &shared.cached_statements_size);
                         }
-                        Command::Begin { tx } => {
+                        Command::Begin { options, tx } => {
                             let depth = conn.transaction_depth;
-                            let res =
-                                conn.handle
-                                    .exec(begin_ansi_transaction_sql(depth))
-                                    .map(|_| {
-                                        conn.transaction_depth += 1;
-                                    });
+                            let res = check_savepoint_options(depth, &options)
+                                .and_then(|_| begin_transaction_sql(depth, &options))
+                                .and_then(|sql| conn.handle.exec(sql))
+                                .map(|_| {
+                                    conn.transaction_depth += 1;
+                                });
                             let res_ok = res.is_ok();
 
                             if tx.blocking_send(res).is_err() && res_ok {
@@ -309,8 +314,8 @@ impl ConnectionWorker {
         Ok(rx)
     }
 
-    pub(crate) async fn begin(&mut self) -> Result<(), Error> {
-        self.oneshot_cmd_with_ack(|tx| Command::Begin { tx })
+    pub(crate) async fn begin(&mut self, options: TransactionOptions) -> Result<(), Error> {
+        self.oneshot_cmd_with_ack(|tx| Command::Begin { options, tx })
             .await?
     }
 
@@ -442,6 +447,28 @@ fn prepare(conn: &mut ConnectionState, query: &str) -> Result<SqliteStatement<'s
     })
 }
 
+// SQLite transactions are always serializable, which satisfies any requested isolation level.
+fn begin_transaction_sql(
+    depth: usize,
+    options: &TransactionOptions,
+) -> Result<Cow<'static, str>, Error> {
+    if options.read_only {
+        return Err(Error::Configuration(
+            "SQLite does not support read-only transactions".into(),
+        ));
+    }
+
+    if depth > 0 {
+        return Ok(begin_ansi_transaction_sql(depth));
+    }
+
+    Ok(match options.sqlite_behavior {
+        SqliteTransactionBehavior::Deferred => begin_ansi_transaction_sql(depth),
+        SqliteTransactionBehavior::Immediate => Cow::Borrowed("BEGIN IMMEDIATE"),
+        SqliteTransactionBehavior::Exclusive => Cow::Borrowed("BEGIN EXCLUSIVE"),
+    })
+}
+
 fn update_cached_statements_size(conn: &ConnectionState, size: &AtomicUsize) {
     size.store(conn.statements.len(), Ordering::Release);
 }
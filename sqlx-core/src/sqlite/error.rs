@@ -4,9 +4,14 @@ use std::fmt::{self, Display, Formatter};
 use std::os::raw::c_int;
 use std::{borrow::Cow, str::from_utf8_unchecked};
 
-use libsqlite3_sys::{sqlite3, sqlite3_errmsg, sqlite3_error_offset, sqlite3_extended_errcode};
+use libsqlite3_sys::{
+    sqlite3, sqlite3_errmsg, sqlite3_error_offset, sqlite3_extended_errcode, SQLITE_BUSY,
+    SQLITE_BUSY_SNAPSHOT, SQLITE_CONSTRAINT_CHECK, SQLITE_CONSTRAINT_FOREIGNKEY,
+    SQLITE_CONSTRAINT_NOTNULL, SQLITE_CONSTRAINT_PRIMARYKEY, SQLITE_CONSTRAINT_UNIQUE,
+    SQLITE_INTERRUPT,
+};
 
-use crate::error::DatabaseError;
+use crate::error::{DatabaseError, ErrorKind};
 
 // Error Codes And Messages
 // https://www.sqlite.org/c3ref/errcode.html
@@ -49,6 +54,23 @@ impl SqliteError {
         }
     }
 
+    /// The `table.column` named by a constraint error, e.g. `NOT NULL constraint failed: t.c`.
+    ///
+    /// Unique constraints over several columns list all of them; only the table is returned then.
+    fn constraint_target(&self) -> Option<(&str, Option<&str>)> {
+        if !matches!(
+            self.code,
+            SQLITE_CONSTRAINT_UNIQUE | SQLITE_CONSTRAINT_PRIMARYKEY | SQLITE_CONSTRAINT_NOTNULL
+        ) {
+            return None;
+        }
+
+        let (_, columns) = self.message.split_once("constraint failed: ")?;
+        let (table, column) = columns.split_once('.')?;
+
+        Some((table, (!column.contains(", ")).then_some(column)))
+    }
+
     /// For errors during extension load, the error message is supplied via a separate pointer
     pub(crate) fn extension(handle: *mut sqlite3, error_msg: &CStr) -> Self {
         let mut err = Self::new(handle);
@@ -90,6 +112,44 @@ impl DatabaseError for SqliteError {
         self.offset
     }
 
+    fn kind(&self) -> ErrorKind {
+        match self.code {
+            SQLITE_CONSTRAINT_UNIQUE | SQLITE_CONSTRAINT_PRIMARYKEY => ErrorKind::UniqueViolation,
+            SQLITE_CONSTRAINT_FOREIGNKEY => ErrorKind::ForeignKeyViolation,
+            SQLITE_CONSTRAINT_NOTNULL => ErrorKind::NotNullViolation,
+            SQLITE_CONSTRAINT_CHECK => ErrorKind::CheckViolation,
+            // a write conflicted with another connection since the read transaction started
+            SQLITE_BUSY_SNAPSHOT => ErrorKind::SerializationFailure,
+            // the database stayed locked for longer than the busy timeout
+            code if code & 0xff == SQLITE_BUSY => ErrorKind::LockTimeout,
+            SQLITE_INTERRUPT => ErrorKind::QueryCanceled,
+            _ => ErrorKind::Other,
+        }
+    }
+
+    fn constraint(&self) -> Option<&str> {
+        match self.code {
+            SQLITE_CONSTRAINT_CHECK => self
+                .message
+                .split_once("constraint failed: ")
+                .map(|(_, name)| name),
+            _ => None,
+        }
+    }
+
+    fn table(&self) -> Option<&str> {
+        self.constraint_target().map(|(table, _)| table)
+    }
+
+    fn column(&self) -> Option<&str> {
+        self.constraint_target().and_then(|(_, column)| column)
+    }
+
+    fn is_retryable_conflict(&self) -> bool {
+        // other `SQLITE_BUSY` errors are lock timeouts, which are not retried
+        self.code == SQLITE_BUSY_SNAPSHOT
+    }
+
     #[doc(hidden)]
     fn as_error(&self) -> &(dyn StdError + Send + Sync + 'static) {
         self
@@ -1,8 +1,28 @@
 use futures_core::future::BoxFuture;
 
 use crate::error::Error;
+use crate::executor::Executor;
 use crate::sqlite::{Sqlite, SqliteConnection};
-use crate::transaction::TransactionManager;
+use crate::transaction::{
+    savepoint_ansi_sql, SavepointAction, TransactionManager, TransactionOptions,
+};
+
+/// When a SQLite transaction locks the database.
+///
+/// See [`TransactionOptions::sqlite_behavior`] and the [SQLite documentation].
+///
+/// [SQLite documentation]: https://www.sqlite.org/lang_transaction.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SqliteTransactionBehavior {
+    /// Lock the database when it is first read or written (`BEGIN DEFERRED`).
+    #[default]
+    Deferred,
+    /// Lock the database for writing as soon as the transaction begins (`BEGIN IMMEDIATE`).
+    Immediate,
+    /// Lock the database for reading and writing as soon as the transaction begins
+    /// (`BEGIN EXCLUSIVE`).
+    Exclusive,
+}
 
 /// Implementation of [`TransactionManager`] for SQLite.
 pub struct SqliteTransactionManager;
@@ -11,7 +31,14 @@ impl TransactionManager for SqliteTransactionManager {
     type Database = Sqlite;
 
     fn begin(conn: &mut SqliteConnection) -> BoxFuture<'_, Result<(), Error>> {
-        Box::pin(conn.worker.begin())
+        Self::begin_with(conn, TransactionOptions::default())
+    }
+
+    fn begin_with(
+        conn: &mut SqliteConnection,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(conn.worker.begin(options))
     }
 
     fn commit(conn: &mut SqliteConnection) -> BoxFuture<'_, Result<(), Error>> {
@@ -25,4 +52,16 @@ impl TransactionManager for SqliteTransactionManager {
     fn start_rollback(conn: &mut SqliteConnection) {
         conn.worker.start_rollback().ok();
     }
+
+    fn savepoint<'c>(
+        conn: &'c mut SqliteConnection,
+        name: &'c str,
+        action: SavepointAction,
+    ) -> BoxFuture<'c, Result<(), Error>> {
+        Box::pin(async move {
+            conn.execute(&*savepoint_ansi_sql(name, action)).await?;
+
+            Ok(())
+        })
+    }
 }
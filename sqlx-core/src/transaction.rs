@@ -1,8 +1,13 @@
 use std::borrow::Cow;
+use std::cmp;
+use std::collections::hash_map::RandomState;
 use std::fmt::{self, Debug, Formatter};
+use std::hash::{BuildHasher, Hasher};
 use std::ops::{Deref, DerefMut};
+use std::time::Duration;
 
 use futures_core::future::BoxFuture;
+use futures_util::future;
 
 use crate::database::Database;
 use crate::error::Error;
@@ -20,6 +25,26 @@ pub trait TransactionManager {
         conn: &mut <Self::Database as Database>::Connection,
     ) -> BoxFuture<'_, Result<(), Error>>;
 
+    /// Begin a new transaction with the given options, or establish a savepoint within the
+    /// active transaction.
+    ///
+    /// Options other than the default cannot be applied to a savepoint and return an error.
+    ///
+    /// The default implementation calls [`begin`][Self::begin] for the default options, and
+    /// returns an error for any other options.
+    fn begin_with(
+        conn: &mut <Self::Database as Database>::Connection,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, Result<(), Error>> {
+        if options.is_default() {
+            Self::begin(conn)
+        } else {
+            Box::pin(future::err(Error::Configuration(
+                "transaction options are not supported by this database".into(),
+            )))
+        }
+    }
+
     /// Commit the active transaction or release the most recent savepoint.
     fn commit(
         conn: &mut <Self::Database as Database>::Connection,
@@ -32,6 +57,197 @@ pub trait TransactionManager {
 
     /// Starts to abort the active transaction or restore from the most recent snapshot.
     fn start_rollback(conn: &mut <Self::Database as Database>::Connection);
+
+    /// Create, release or roll back to the named savepoint within the active transaction.
+    ///
+    /// The default implementation returns an error, as named savepoints are not supported.
+    fn savepoint<'c>(
+        _conn: &'c mut <Self::Database as Database>::Connection,
+        _name: &'c str,
+        _action: SavepointAction,
+    ) -> BoxFuture<'c, Result<(), Error>> {
+        Box::pin(future::err(Error::Configuration(
+            "named savepoints are not supported by this database".into(),
+        )))
+    }
+}
+
+/// The operation to perform on a named savepoint.
+///
+/// This enum should not be used, except when implementing [`TransactionManager`].
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SavepointAction {
+    Create,
+    Release,
+    RollbackTo,
+}
+
+/// The isolation level of a transaction.
+///
+/// See [`TransactionOptions::isolation`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IsolationLevel {
+    ReadUncommitted,
+    ReadCommitted,
+    RepeatableRead,
+    Serializable,
+}
+
+impl IsolationLevel {
+    /// The SQL name of the isolation level, as used in `SET TRANSACTION ISOLATION LEVEL`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IsolationLevel::ReadUncommitted => "READ UNCOMMITTED",
+            IsolationLevel::ReadCommitted => "READ COMMITTED",
+            IsolationLevel::RepeatableRead => "REPEATABLE READ",
+            IsolationLevel::Serializable => "SERIALIZABLE",
+        }
+    }
+}
+
+/// Options for beginning a new transaction with [`Connection::begin_with`] or
+/// [`Pool::begin_with`].
+///
+/// How the options are applied depends on the database:
+///
+/// * Postgres: `BEGIN ISOLATION LEVEL .. READ ONLY DEFERRABLE`.
+/// * MySQL: `SET TRANSACTION ISOLATION LEVEL ..` followed by `START TRANSACTION READ ONLY`.
+/// * MSSQL: `SET TRANSACTION ISOLATION LEVEL ..` before `BEGIN TRAN`. As the isolation level
+///   applies to the whole session in MSSQL, the previous one is restored when the transaction is
+///   committed or rolled back. Read-only transactions are not supported.
+/// * SQLite: transactions are always serializable, which provides the guarantees of every
+///   isolation level, so the isolation level does not change the `BEGIN` statement. How early
+///   the database is locked is picked with [`sqlite_behavior`][Self::sqlite_behavior] instead.
+///   Read-only transactions are not supported.
+/// * ODBC: transaction options are not supported.
+///
+/// `deferrable` is only supported by Postgres and `sqlite_behavior` by SQLite; they are ignored
+/// elsewhere.
+///
+/// [`Connection::begin_with`]: crate::connection::Connection::begin_with()
+/// [`Pool::begin_with`]: crate::pool::Pool::begin_with()
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct TransactionOptions {
+    pub(crate) isolation: Option<IsolationLevel>,
+    pub(crate) read_only: bool,
+    pub(crate) deferrable: bool,
+    #[cfg(feature = "sqlite")]
+    pub(crate) sqlite_behavior: crate::sqlite::SqliteTransactionBehavior,
+}
+
+impl TransactionOptions {
+    /// Use the default options of the database: its default isolation level, read-write.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the isolation level of the transaction.
+    pub fn isolation(mut self, isolation: IsolationLevel) -> Self {
+        self.isolation = Some(isolation);
+        self
+    }
+
+    /// Make the transaction read-only.
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Make the transaction deferrable (Postgres only).
+    ///
+    /// This only has an effect on `SERIALIZABLE`, `READ ONLY` transactions, which then wait
+    /// until they can run without the possibility of a serialization failure.
+    pub fn deferrable(mut self, deferrable: bool) -> Self {
+        self.deferrable = deferrable;
+        self
+    }
+
+    /// Set when the transaction locks the database (SQLite only).
+    ///
+    /// Use [`Immediate`][crate::sqlite::SqliteTransactionBehavior::Immediate] for transactions
+    /// that will write, so that they wait for other writers when they begin instead of failing
+    /// with `SQLITE_BUSY` when upgrading from a read to a write.
+    #[cfg(feature = "sqlite")]
+    pub fn sqlite_behavior(mut self, behavior: crate::sqlite::SqliteTransactionBehavior) -> Self {
+        self.sqlite_behavior = behavior;
+        self
+    }
+
+    pub(crate) fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// How [`Connection::transaction_with_retry`] retries transactions that failed because of a
+/// conflict with a concurrent transaction.
+///
+/// Retries are delayed with an exponential backoff, starting at
+/// [`initial_backoff`][Self::initial_backoff] and doubling up to
+/// [`max_backoff`][Self::max_backoff]. A random jitter of up to half the delay is subtracted so
+/// that transactions which conflicted with each other do not retry in lockstep.
+///
+/// [`Connection::transaction_with_retry`]: crate::connection::Connection::transaction_with_retry()
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    max_retries: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_secs(1),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Retry up to 3 times, waiting 10 ms before the first retry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum number of times the transaction is retried after the first attempt.
+    ///
+    /// Set to 0 to disable retrying.
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Set the delay before the first retry.
+    pub fn initial_backoff(mut self, backoff: Duration) -> Self {
+        self.initial_backoff = backoff;
+        self
+    }
+
+    /// Set the maximum delay between retries.
+    pub fn max_backoff(mut self, backoff: Duration) -> Self {
+        self.max_backoff = backoff;
+        self
+    }
+
+    pub(crate) fn should_retry(&self, retries: u32) -> bool {
+        retries < self.max_retries
+    }
+
+    /// The delay before retry number `retries + 1`.
+    pub(crate) fn backoff(&self, retries: u32) -> Duration {
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(2u32.saturating_pow(retries));
+        let backoff = cmp::min(backoff, self.max_backoff);
+
+        // `RandomState` is randomly seeded, which is good enough for jitter.
+        let random = RandomState::new().build_hasher().finish();
+        let jitter = backoff.mul_f64((random as f64 / u64::MAX as f64) / 2.0);
+
+        backoff - jitter
+    }
 }
 
 /// An in-progress database transaction or savepoint.
@@ -77,6 +293,22 @@ where
         })
     }
 
+    pub(crate) fn begin_with(
+        conn: impl Into<MaybePoolConnection<'c, DB>>,
+        options: TransactionOptions,
+    ) -> BoxFuture<'c, Result<Self, Error>> {
+        let mut conn = conn.into();
+
+        Box::pin(async move {
+            DB::TransactionManager::begin_with(&mut conn, options).await?;
+
+            Ok(Self {
+                connection: conn,
+                open: true,
+            })
+        })
+    }
+
     /// Commits this transaction or savepoint.
     pub async fn commit(mut self) -> Result<(), Error> {
         DB::TransactionManager::commit(&mut self.connection).await?;
@@ -92,6 +324,143 @@ where
 
         Ok(())
     }
+
+    /// Establishes a savepoint with the given name within this transaction.
+    ///
+    /// Statements executed through the returned [`Savepoint`] run inside this transaction.
+    /// Call [`Savepoint::rollback_to`] to undo everything executed since the savepoint was
+    /// established while keeping the rest of the transaction, or [`Savepoint::release`] to keep
+    /// it. If neither is called, the savepoint stays in effect until the transaction ends.
+    ///
+    /// The name must be a plain SQL identifier: ASCII letters, digits and underscores, not
+    /// starting with a digit. Establishing a savepoint with the name of an existing one hides
+    /// the older one until the newer one is released.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use sqlx_core_oldapi::error::Error;
+    /// use sqlx_core_oldapi::executor::Executor;
+    /// use sqlx_core_oldapi::postgres::Postgres;
+    /// use sqlx_core_oldapi::transaction::Transaction;
+    ///
+    /// # pub async fn _f(tx: &mut Transaction<'_, Postgres>, batches: &[&str]) -> Result<(), Error> {
+    /// for batch in batches {
+    ///     let mut savepoint = tx.savepoint("batch").await?;
+    ///
+    ///     match savepoint.execute(*batch).await {
+    ///         Ok(_) => savepoint.release().await?,
+    ///         // skip the failed batch and continue with the next one
+    ///         Err(_) => savepoint.rollback_to().await?,
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn savepoint<'t>(
+        &'t mut self,
+        name: impl Into<String>,
+    ) -> Result<Savepoint<'t, 'c, DB>, Error> {
+        let name = name.into();
+
+        if !is_valid_savepoint_name(&name) {
+            return Err(Error::Configuration(
+                format!("invalid savepoint name: {:?}", name).into(),
+            ));
+        }
+
+        DB::TransactionManager::savepoint(&mut self.connection, &name, SavepointAction::Create)
+            .await?;
+
+        Ok(Savepoint {
+            transaction: self,
+            name,
+        })
+    }
+}
+
+/// A named savepoint within a [`Transaction`], created with [`Transaction::savepoint`].
+///
+/// Dereferences to the connection, so statements can be executed with `&mut *savepoint`.
+pub struct Savepoint<'t, 'c, DB>
+where
+    DB: Database,
+{
+    transaction: &'t mut Transaction<'c, DB>,
+    name: String,
+}
+
+impl<'t, 'c, DB> Savepoint<'t, 'c, DB>
+where
+    DB: Database,
+{
+    /// The name of this savepoint.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Releases this savepoint, keeping the changes made since it was established.
+    ///
+    /// On MSSQL, which cannot release savepoints, this does nothing.
+    pub async fn release(self) -> Result<(), Error> {
+        DB::TransactionManager::savepoint(
+            &mut self.transaction.connection,
+            &self.name,
+            SavepointAction::Release,
+        )
+        .await
+    }
+
+    /// Undoes all changes made since this savepoint was established. The transaction remains
+    /// active.
+    pub async fn rollback_to(self) -> Result<(), Error> {
+        DB::TransactionManager::savepoint(
+            &mut self.transaction.connection,
+            &self.name,
+            SavepointAction::RollbackTo,
+        )
+        .await
+    }
+}
+
+impl<'t, 'c, DB> Debug for Savepoint<'t, 'c, DB>
+where
+    DB: Database,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Savepoint")
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+impl<'t, 'c, DB> Deref for Savepoint<'t, 'c, DB>
+where
+    DB: Database,
+{
+    type Target = DB::Connection;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.transaction
+    }
+}
+
+impl<'t, 'c, DB> DerefMut for Savepoint<'t, 'c, DB>
+where
+    DB: Database,
+{
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.transaction
+    }
+}
+
+fn is_valid_savepoint_name(name: &str) -> bool {
+    let mut chars = name.chars();
+
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
 }
 
 // NOTE: required due to lack of lazy normalization
@@ -214,6 +583,30 @@ where
     }
 }
 
+/// Savepoints always inherit the characteristics of the enclosing transaction.
+#[allow(dead_code)]
+pub(crate) fn check_savepoint_options(
+    depth: usize,
+    options: &TransactionOptions,
+) -> Result<(), Error> {
+    if depth > 0 && !options.is_default() {
+        return Err(Error::Configuration(
+            "transaction options cannot be applied to a savepoint".into(),
+        ));
+    }
+
+    Ok(())
+}
+
+#[allow(dead_code)]
+pub(crate) fn savepoint_ansi_sql(name: &str, action: SavepointAction) -> String {
+    match action {
+        SavepointAction::Create => format!("SAVEPOINT {}", name),
+        SavepointAction::Release => format!("RELEASE SAVEPOINT {}", name),
+        SavepointAction::RollbackTo => format!("ROLLBACK TO SAVEPOINT {}", name),
+    }
+}
+
 #[allow(dead_code)]
 pub(crate) fn begin_ansi_transaction_sql(depth: usize) -> Cow<'static, str> {
     if depth == 0 {
@@ -243,3 +636,39 @@ pub(crate) fn rollback_ansi_transaction_sql(depth: usize) -> Cow<'static, str> {
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{is_valid_savepoint_name, RetryPolicy};
+    use std::time::Duration;
+
+    #[test]
+    fn savepoint_names_must_be_plain_identifiers() {
+        assert!(is_valid_savepoint_name("batch_1"));
+        assert!(is_valid_savepoint_name("_sp"));
+        assert!(!is_valid_savepoint_name(""));
+        assert!(!is_valid_savepoint_name("1batch"));
+        assert!(!is_valid_savepoint_name("sp; DROP TABLE users"));
+        assert!(!is_valid_savepoint_name("\"sp\""));
+    }
+
+    #[test]
+    fn retry_backoff_grows_exponentially_up_to_max() {
+        let policy = RetryPolicy::new()
+            .initial_backoff(Duration::from_millis(100))
+            .max_backoff(Duration::from_millis(300));
+
+        for (retries, expected) in [(0, 100), (1, 200), (2, 300), (10, 300), (100, 300)] {
+            let backoff = policy.backoff(retries);
+            let expected = Duration::from_millis(expected);
+
+            assert!(backoff <= expected, "{:?} > {:?}", backoff, expected);
+            assert!(
+                backoff >= expected / 2,
+                "{:?} < {:?}",
+                backoff,
+                expected / 2
+            );
+        }
+    }
+}
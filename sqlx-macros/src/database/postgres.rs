@@ -23,6 +23,17 @@ impl_database_ext! {
 
         sqlx::postgres::types::PgLQuery,
 
+        sqlx::postgres::types::PgPoint,
+        sqlx::postgres::types::PgLine,
+        sqlx::postgres::types::PgLSeg,
+        sqlx::postgres::types::PgBox,
+        sqlx::postgres::types::PgPath,
+        sqlx::postgres::types::PgPolygon,
+        sqlx::postgres::types::PgCircle,
+
+        sqlx::postgres::types::PgTsVector,
+        sqlx::postgres::types::PgTsQuery,
+
         #[cfg(feature = "uuid")]
         sqlx::types::Uuid,
 
@@ -87,6 +98,15 @@ impl_database_ext! {
         Vec<f64> | &[f64],
         Vec<sqlx::postgres::types::Oid> | &[sqlx::postgres::types::Oid],
         Vec<sqlx::postgres::types::PgMoney> | &[sqlx::postgres::types::PgMoney],
+        Vec<sqlx::postgres::types::PgPoint> | &[sqlx::postgres::types::PgPoint],
+        Vec<sqlx::postgres::types::PgLine> | &[sqlx::postgres::types::PgLine],
+        Vec<sqlx::postgres::types::PgLSeg> | &[sqlx::postgres::types::PgLSeg],
+        Vec<sqlx::postgres::types::PgBox> | &[sqlx::postgres::types::PgBox],
+        Vec<sqlx::postgres::types::PgPath> | &[sqlx::postgres::types::PgPath],
+        Vec<sqlx::postgres::types::PgPolygon> | &[sqlx::postgres::types::PgPolygon],
+        Vec<sqlx::postgres::types::PgCircle> | &[sqlx::postgres::types::PgCircle],
+        Vec<sqlx::postgres::types::PgTsVector> | &[sqlx::postgres::types::PgTsVector],
+        Vec<sqlx::postgres::types::PgTsQuery> | &[sqlx::postgres::types::PgTsQuery],
 
         #[cfg(feature = "uuid")]
         Vec<sqlx::types::Uuid> | &[sqlx::types::Uuid],
@@ -200,6 +220,77 @@ impl_database_ext! {
         #[cfg(feature = "time")]
         Vec<sqlx::postgres::types::PgRange<sqlx::types::time::OffsetDateTime>> |
             &[sqlx::postgres::types::PgRange<sqlx::types::time::OffsetDateTime>],
+
+        // Multiranges
+
+        sqlx::postgres::types::PgMultiRange<i32>,
+        sqlx::postgres::types::PgMultiRange<i64>,
+
+        #[cfg(feature = "bigdecimal")]
+        sqlx::postgres::types::PgMultiRange<sqlx::types::BigDecimal>,
+
+        #[cfg(feature = "decimal")]
+        sqlx::postgres::types::PgMultiRange<sqlx::types::Decimal>,
+
+        #[cfg(feature = "chrono")]
+        sqlx::postgres::types::PgMultiRange<sqlx::types::chrono::NaiveDate>,
+
+        #[cfg(feature = "chrono")]
+        sqlx::postgres::types::PgMultiRange<sqlx::types::chrono::NaiveDateTime>,
+
+        #[cfg(feature = "chrono")]
+        sqlx::postgres::types::PgMultiRange<sqlx::types::chrono::DateTime<sqlx::types::chrono::Utc>> |
+            sqlx::postgres::types::PgMultiRange<sqlx::types::chrono::DateTime<_>>,
+
+        #[cfg(feature = "time")]
+        sqlx::postgres::types::PgMultiRange<sqlx::types::time::Date>,
+
+        #[cfg(feature = "time")]
+        sqlx::postgres::types::PgMultiRange<sqlx::types::time::PrimitiveDateTime>,
+
+        #[cfg(feature = "time")]
+        sqlx::postgres::types::PgMultiRange<sqlx::types::time::OffsetDateTime>,
+
+        // Multirange arrays
+
+        Vec<sqlx::postgres::types::PgMultiRange<i32>> | &[sqlx::postgres::types::PgMultiRange<i32>],
+        Vec<sqlx::postgres::types::PgMultiRange<i64>> | &[sqlx::postgres::types::PgMultiRange<i64>],
+
+        #[cfg(feature = "bigdecimal")]
+        Vec<sqlx::postgres::types::PgMultiRange<sqlx::types::BigDecimal>> |
+            &[sqlx::postgres::types::PgMultiRange<sqlx::types::BigDecimal>],
+
+        #[cfg(feature = "decimal")]
+        Vec<sqlx::postgres::types::PgMultiRange<sqlx::types::Decimal>> |
+            &[sqlx::postgres::types::PgMultiRange<sqlx::types::Decimal>],
+
+        #[cfg(feature = "chrono")]
+        Vec<sqlx::postgres::types::PgMultiRange<sqlx::types::chrono::NaiveDate>> |
+            &[sqlx::postgres::types::PgMultiRange<sqlx::types::chrono::NaiveDate>],
+
+        #[cfg(feature = "chrono")]
+        Vec<sqlx::postgres::types::PgMultiRange<sqlx::types::chrono::NaiveDateTime>> |
+            &[sqlx::postgres::types::PgMultiRange<sqlx::types::chrono::NaiveDateTime>],
+
+        #[cfg(feature = "chrono")]
+        Vec<sqlx::postgres::types::PgMultiRange<sqlx::types::chrono::DateTime<sqlx::types::chrono::Utc>>> |
+            Vec<sqlx::postgres::types::PgMultiRange<sqlx::types::chrono::DateTime<_>>>,
+
+        #[cfg(feature = "chrono")]
+        &[sqlx::postgres::types::PgMultiRange<sqlx::types::chrono::DateTime<sqlx::types::chrono::Utc>>] |
+            &[sqlx::postgres::types::PgMultiRange<sqlx::types::chrono::DateTime<_>>],
+
+        #[cfg(feature = "time")]
+        Vec<sqlx::postgres::types::PgMultiRange<sqlx::types::time::Date>> |
+            &[sqlx::postgres::types::PgMultiRange<sqlx::types::time::Date>],
+
+        #[cfg(feature = "time")]
+        Vec<sqlx::postgres::types::PgMultiRange<sqlx::types::time::PrimitiveDateTime>> |
+            &[sqlx::postgres::types::PgMultiRange<sqlx::types::time::PrimitiveDateTime>],
+
+        #[cfg(feature = "time")]
+        Vec<sqlx::postgres::types::PgMultiRange<sqlx::types::time::OffsetDateTime>> |
+            &[sqlx::postgres::types::PgMultiRange<sqlx::types::time::OffsetDateTime>],
     },
     ParamChecking::Strong,
     feature-types: info => info.__type_feature_gate(),
@@ -0,0 +1,96 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    parse_quote, punctuated::Punctuated, token::Comma, Data, DataStruct, DeriveInput, Field,
+    Fields, FieldsNamed, FieldsUnnamed, Index, Member, Stmt,
+};
+
+use super::attributes::check_struct_attributes;
+
+pub fn expand_derive_copy_row(input: &DeriveInput) -> syn::Result<TokenStream> {
+    match &input.data {
+        Data::Struct(DataStruct {
+            fields: Fields::Named(FieldsNamed { named: fields, .. }),
+            ..
+        })
+        | Data::Struct(DataStruct {
+            fields:
+                Fields::Unnamed(FieldsUnnamed {
+                    unnamed: fields, ..
+                }),
+            ..
+        }) => expand_derive_copy_row_struct(input, fields),
+
+        Data::Struct(DataStruct {
+            fields: Fields::Unit,
+            ..
+        }) => Err(syn::Error::new_spanned(
+            input,
+            "unit structs are not supported",
+        )),
+
+        Data::Enum(_) => Err(syn::Error::new_spanned(input, "enums are not supported")),
+
+        Data::Union(_) => Err(syn::Error::new_spanned(input, "unions are not supported")),
+    }
+}
+
+fn expand_derive_copy_row_struct(
+    input: &DeriveInput,
+    fields: &Punctuated<Field, Comma>,
+) -> syn::Result<TokenStream> {
+    check_struct_attributes(input, fields)?;
+
+    // every field is copied as it is, into the column at the same position
+    if let Some(attr) = fields
+        .iter()
+        .flat_map(|field| &field.attrs)
+        .find(|attr| attr.path().is_ident("sqlx"))
+    {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "field attributes are not supported by `PgCopyRow`",
+        ));
+    }
+
+    let ident = &input.ident;
+
+    let generics = &input.generics;
+    let (_, ty_generics, _) = generics.split_for_impl();
+
+    let mut generics = generics.clone();
+    let predicates = &mut generics.make_where_clause().predicates;
+
+    for field in fields {
+        let ty = &field.ty;
+
+        predicates.push(
+            parse_quote!(#ty: for<'q> ::sqlx_oldapi::encode::Encode<'q, ::sqlx_oldapi::Postgres>),
+        );
+        predicates.push(parse_quote!(#ty: ::sqlx_oldapi::types::Type<::sqlx_oldapi::Postgres>));
+    }
+
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    let writes = fields.iter().enumerate().map(|(i, field)| -> Stmt {
+        let member = match &field.ident {
+            Some(id) => Member::Named(id.clone()),
+            None => Member::Unnamed(Index::from(i)),
+        };
+
+        parse_quote!(
+            row.encode(&self. #member);
+        )
+    });
+
+    Ok(quote!(
+        #[automatically_derived]
+        impl #impl_generics ::sqlx_oldapi::postgres::PgCopyRow for #ident #ty_generics
+        #where_clause
+        {
+            fn encode_row(&self, row: &mut ::sqlx_oldapi::postgres::PgCopyRowEncoder) {
+                #(#writes)*
+            }
+        }
+    ))
+}
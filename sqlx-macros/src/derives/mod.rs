@@ -1,9 +1,11 @@
 mod attributes;
+mod copy_row;
 mod decode;
 mod encode;
 mod row;
 mod r#type;
 
+pub(crate) use copy_row::expand_derive_copy_row;
 pub(crate) use decode::expand_derive_decode;
 pub(crate) use encode::expand_derive_encode;
 pub(crate) use r#type::expand_derive_type;
@@ -244,3 +244,320 @@ async fn test_pool_callbacks() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn pool_should_record_metrics() -> anyhow::Result<()> {
+    use sqlx_oldapi::pool::{CloseReason, PoolObserver};
+
+    #[derive(Default)]
+    struct Counts {
+        acquires: AtomicUsize,
+        releases: AtomicUsize,
+        connects: AtomicUsize,
+        closes: AtomicUsize,
+    }
+
+    impl PoolObserver for Counts {
+        fn on_acquire(&self, _wait: Duration) {
+            self.acquires.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn on_release(&self, _meta: &sqlx_oldapi::pool::PoolConnectionMetadata) {
+            self.releases.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn on_connect(&self, _elapsed: Duration) {
+            self.connects.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn on_close(&self, reason: CloseReason) {
+            assert_eq!(reason, CloseReason::PoolClosed);
+            self.closes.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    let counts = Arc::new(Counts::default());
+
+    let pool = AnyPoolOptions::new()
+        .max_connections(2)
+        .acquire_timeout(Duration::from_secs(5))
+        .observer(counts.clone())
+        .connect(&dotenvy::var("DATABASE_URL")?)
+        .await?;
+
+    let conn = pool.acquire().await?;
+
+    let metrics = pool.metrics();
+    assert_eq!(metrics.size, 1);
+    assert_eq!(metrics.in_use, 1);
+    assert_eq!(metrics.connections_opened, 1);
+    // `connect()` acquires once to test the connection
+    assert_eq!(metrics.acquires, 2);
+    assert_eq!(metrics.acquire_wait.count(), 2);
+
+    // checkouts are only tracked when leak detection is enabled
+    assert!(pool.checked_out_connections().is_empty());
+
+    // connections are released asynchronously
+    drop(conn);
+    while pool.num_idle() == 0 {
+        tokio::task::yield_now().await;
+    }
+
+    pool.close().await;
+
+    let metrics = pool.metrics();
+    assert_eq!(metrics.size, 0);
+    assert_eq!(metrics.closed_with_pool, 1);
+    assert_eq!(metrics.connections_closed(), 1);
+
+    assert_eq!(counts.acquires.load(Ordering::SeqCst), 2);
+    assert_eq!(counts.releases.load(Ordering::SeqCst), 1);
+    assert_eq!(counts.connects.load(Ordering::SeqCst), 1);
+    assert_eq!(counts.closes.load(Ordering::SeqCst), 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_should_resize() -> anyhow::Result<()> {
+    let pool = AnyPoolOptions::new()
+        .max_connections(1)
+        .acquire_timeout(Duration::from_secs(1))
+        .connect(&dotenvy::var("DATABASE_URL")?)
+        .await?;
+
+    let conn_1 = pool.acquire().await?;
+
+    // Raising the limit should wake up tasks waiting for a connection.
+    let waiter = tokio::spawn({
+        let pool = pool.clone();
+        async move { pool.acquire().await }
+    });
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    pool.set_max_connections(3);
+    assert_eq!(pool.max_connections(), 3);
+
+    let conn_2 = waiter.await??;
+    let conn_3 = pool.acquire().await?;
+    assert_eq!(pool.size(), 3);
+
+    // Lowering the limit should retire connections as they are returned.
+    pool.set_max_connections(1);
+    drop(conn_1);
+    drop(conn_2);
+    drop(conn_3);
+
+    while pool.size() > 1 || pool.num_idle() == 0 {
+        tokio::task::yield_now().await;
+    }
+
+    assert_eq!(pool.metrics().retired, 2);
+
+    let _conn = pool.acquire().await?;
+    assert!(matches!(
+        pool.acquire().await,
+        Err(sqlx_oldapi::Error::PoolTimedOut)
+    ));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn routed_pool_should_route_reads_to_replicas() -> anyhow::Result<()> {
+    use sqlx_oldapi::pool::RoutedPool;
+
+    let url = dotenvy::var("DATABASE_URL")?;
+    let connect = |max_connections| {
+        AnyPoolOptions::new()
+            .max_connections(max_connections)
+            .acquire_timeout(Duration::from_millis(500))
+            .connect_lazy(&url)
+    };
+
+    let pool = RoutedPool::builder(connect(2)?)
+        .weighted_replica(connect(1)?, 2)
+        .replica(connect(2)?)
+        .retry_interval(Duration::from_millis(100))
+        .build();
+
+    let (replica_a, replica_b) = {
+        let mut replicas = pool.replicas();
+        (replicas.next().unwrap(), replicas.next().unwrap())
+    };
+
+    pool.execute("SELECT 1").await?;
+    assert_eq!(pool.writer().metrics().acquires, 1);
+
+    for _ in 0..3 {
+        pool.reader().execute("SELECT 1").await?;
+        wait_for_release(replica_a).await;
+        wait_for_release(replica_b).await;
+    }
+
+    assert_eq!(pool.writer().metrics().acquires, 1);
+    assert_eq!(replica_a.metrics().acquires, 2);
+    assert_eq!(replica_b.metrics().acquires, 1);
+
+    // A busy replica is skipped without being taken out of rotation.
+    let held = replica_a.acquire().await?;
+
+    for _ in 0..3 {
+        pool.reader().execute("SELECT 1").await?;
+        wait_for_release(replica_b).await;
+    }
+
+    assert_eq!(pool.num_healthy_replicas(), 2);
+    assert_eq!(replica_b.metrics().acquires, 4);
+
+    drop(held);
+    pool.close().await;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn routed_pool_should_report_the_caller_of_reads() -> anyhow::Result<()> {
+    use sqlx_oldapi::pool::RoutedPool;
+    use sqlx_oldapi::Acquire;
+
+    let url = dotenvy::var("DATABASE_URL")?;
+    let pool = RoutedPool::builder(AnyPoolOptions::new().connect_lazy(&url)?)
+        .replica(
+            AnyPoolOptions::new()
+                .leak_detection_threshold(Duration::from_secs(60))
+                .connect_lazy(&url)?,
+        )
+        .build();
+    let replica = pool.replicas().next().unwrap();
+
+    let conn = pool.reader().acquire().await?;
+    let line = line!() - 1;
+
+    let checked_out = replica.checked_out_connections();
+    assert_eq!(checked_out.len(), 1);
+    assert!(checked_out[0].location.file().ends_with("pool.rs"));
+    assert_eq!(checked_out[0].location.line(), line);
+    drop(conn);
+    wait_for_release(replica).await;
+
+    let conn = Acquire::acquire(pool.reader()).await?;
+    let line = line!() - 1;
+
+    let checked_out = replica.checked_out_connections();
+    assert_eq!(checked_out.len(), 1);
+    assert_eq!(checked_out[0].location.line(), line);
+    drop(conn);
+
+    pool.close().await;
+
+    Ok(())
+}
+
+/// Wait for the connections used by reads to be returned, so the replica isn't seen as busy.
+async fn wait_for_release(replica: &sqlx_oldapi::Pool<sqlx_oldapi::Any>) {
+    while replica.num_idle() < replica.size() as usize {
+        tokio::task::yield_now().await;
+    }
+}
+
+#[sqlx_macros::test]
+async fn routed_pool_should_recover_unavailable_replicas() -> anyhow::Result<()> {
+    use sqlx_oldapi::pool::RoutedPool;
+    use std::sync::atomic::AtomicBool;
+
+    let url = dotenvy::var("DATABASE_URL")?;
+    let replica_down = Arc::new(AtomicBool::new(true));
+
+    // Opening a connection to the replica fails until `replica_down` is cleared.
+    let replica = AnyPoolOptions::new()
+        .max_connections(1)
+        .acquire_timeout(Duration::from_millis(200))
+        .after_connect({
+            let replica_down = replica_down.clone();
+            move |_conn, _meta| {
+                let down = replica_down.load(Ordering::SeqCst);
+                Box::pin(async move {
+                    if down {
+                        return Err(sqlx_oldapi::Error::Io(std::io::Error::new(
+                            std::io::ErrorKind::ConnectionRefused,
+                            "replica is down",
+                        )));
+                    }
+
+                    Ok(())
+                })
+            }
+        })
+        .connect_lazy(&url)?;
+
+    let writer = AnyPoolOptions::new()
+        .acquire_timeout(Duration::from_secs(2))
+        .connect_lazy(&url)?;
+
+    let pool = RoutedPool::builder(writer)
+        .replica(replica.clone())
+        .retry_interval(Duration::from_millis(100))
+        .build();
+
+    // Reads fall back to the writer and the replica is taken out of rotation.
+    pool.reader().execute("SELECT 1").await?;
+    assert_eq!(pool.num_healthy_replicas(), 0);
+    assert_eq!(pool.writer().metrics().acquires, 1);
+
+    pool.reader().execute("SELECT 1").await?;
+    assert_eq!(pool.writer().metrics().acquires, 2);
+
+    // Once the replica can be connected to again, it goes back into rotation.
+    replica_down.store(false, Ordering::SeqCst);
+
+    while pool.num_healthy_replicas() < 1 {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+
+    pool.reader().execute("SELECT 1").await?;
+    assert_eq!(pool.writer().metrics().acquires, 2);
+    assert_eq!(replica.metrics().acquires, 2);
+
+    pool.close().await;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_should_detect_leaked_connections() -> anyhow::Result<()> {
+    let leaks = Arc::new(AtomicUsize::new(0));
+
+    let pool = AnyPoolOptions::new()
+        .max_connections(2)
+        .leak_detection_threshold(Duration::from_millis(100))
+        .on_leak({
+            let leaks = leaks.clone();
+            move |_conn| {
+                leaks.fetch_add(1, Ordering::SeqCst);
+            }
+        })
+        .connect(&dotenvy::var("DATABASE_URL")?)
+        .await?;
+
+    let conn = pool.acquire().await?;
+    let line = line!() - 1;
+
+    let checked_out = pool.checked_out_connections();
+    assert_eq!(checked_out.len(), 1);
+    assert!(checked_out[0].location.file().ends_with("pool.rs"));
+    assert_eq!(checked_out[0].location.line(), line);
+
+    tokio::time::sleep(Duration::from_millis(300)).await;
+
+    // Each leaked connection is only reported once.
+    assert_eq!(leaks.load(Ordering::SeqCst), 1);
+
+    drop(conn);
+    assert!(pool.checked_out_connections().is_empty());
+
+    pool.close().await;
+
+    Ok(())
+}
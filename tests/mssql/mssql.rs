@@ -282,6 +282,43 @@ async fn it_can_work_with_transactions() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_restores_the_isolation_level_after_begin_with() -> anyhow::Result<()> {
+    use sqlx_oldapi::{IsolationLevel, TransactionOptions};
+
+    let mut conn = new::<Mssql>().await?;
+
+    let isolation_level =
+        "SELECT transaction_isolation_level FROM sys.dm_exec_sessions WHERE session_id = @@SPID";
+    let before: i16 = sqlx_oldapi::query_scalar(isolation_level)
+        .fetch_one(&mut conn)
+        .await?;
+
+    let options = TransactionOptions::new().isolation(IsolationLevel::Serializable);
+
+    let mut tx = conn.begin_with(options).await?;
+    let during: i16 = sqlx_oldapi::query_scalar(isolation_level)
+        .fetch_one(&mut tx)
+        .await?;
+    assert_eq!(during, 4);
+    tx.commit().await?;
+
+    let after: i16 = sqlx_oldapi::query_scalar(isolation_level)
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(after, before);
+
+    let tx = conn.begin_with(options).await?;
+    tx.rollback().await?;
+
+    let after: i16 = sqlx_oldapi::query_scalar(isolation_level)
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(after, before);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_can_work_with_nested_transactions() -> anyhow::Result<()> {
     let mut conn = new::<Mssql>().await?;
@@ -469,3 +469,30 @@ async fn it_can_work_with_transactions() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn it_keeps_session_settings_after_reset_on_release() -> anyhow::Result<()> {
+    let pool = MySqlPoolOptions::new()
+        .max_connections(1)
+        .reset_on_release(true)
+        .connect(&dotenvy::var("DATABASE_URL")?)
+        .await?;
+
+    let mut conn = pool.acquire().await?;
+    conn.execute("SET time_zone = '+02:00'").await?;
+    drop(conn);
+
+    // With only one connection, this is the same one, returned after the reset.
+    let mut conn = pool.acquire().await?;
+
+    let (time_zone, sql_mode): (String, String) =
+        sqlx_oldapi::query_as("SELECT @@time_zone, @@sql_mode")
+            .fetch_one(&mut *conn)
+            .await?;
+
+    assert_eq!(time_zone, "+00:00");
+    assert!(sql_mode.contains("PIPES_AS_CONCAT"), "{}", sql_mode);
+    assert!(sql_mode.contains("NO_ENGINE_SUBSTITUTION"), "{}", sql_mode);
+
+    Ok(())
+}
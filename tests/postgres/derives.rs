@@ -1,4 +1,8 @@
+#![allow(unexpected_cfgs)]
+
 use futures::TryStreamExt;
+#[cfg(postgres_14)]
+use sqlx_core::postgres::types::PgMultiRange;
 use sqlx_core::postgres::types::PgRange;
 use sqlx_oldapi::{Connection, Executor, FromRow, Postgres};
 use sqlx_test::{new, test_type};
@@ -129,6 +133,12 @@ struct InventoryItem {
 #[sqlx(type_name = "float_range")]
 struct FloatRange(PgRange<f64>);
 
+// Multirange of the custom range type, created along with it since Postgres 14
+#[cfg(postgres_14)]
+#[derive(sqlx_oldapi::Type, Debug, PartialEq)]
+#[sqlx(type_name = "float_multirange")]
+struct FloatMultiRange(PgMultiRange<f64>);
+
 test_type!(transparent<Transparent>(Postgres,
     "0" == Transparent(0),
     "23523" == Transparent(23523)
@@ -150,6 +160,14 @@ test_type!(floatrange<FloatRange>(Postgres,
     "'[1.234, 5.678]'::float_range" == FloatRange(PgRange::from((Bound::Included(1.234), Bound::Included(5.678)))),
 ));
 
+#[cfg(postgres_14)]
+test_type!(floatmultirange<FloatMultiRange>(Postgres,
+    "'{[1.234, 5.678], (7,)}'::float_multirange" == FloatMultiRange(PgMultiRange::from(vec![
+        PgRange::from((Bound::Included(1.234), Bound::Included(5.678))),
+        PgRange::from((Bound::Excluded(7.0), Bound::Unbounded)),
+    ])),
+));
+
 #[sqlx_macros::test]
 async fn test_enum_type() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
@@ -608,3 +626,67 @@ async fn test_flatten() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[cfg(feature = "macros")]
+#[sqlx_macros::test]
+async fn test_copy_row() -> anyhow::Result<()> {
+    #[derive(sqlx_oldapi::PgCopyRow)]
+    struct Item<'a> {
+        id: i32,
+        name: &'a str,
+        strong: Strong,
+        transparent: Option<Transparent>,
+    }
+
+    #[derive(sqlx_oldapi::PgCopyRow)]
+    struct Pair(i32, String);
+
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE items (id INT, name TEXT, strong TEXT, transparent INT)")
+        .await?;
+
+    let items = [
+        Item {
+            id: 1,
+            name: "one",
+            strong: Strong::One,
+            transparent: Some(Transparent(10)),
+        },
+        Item {
+            id: 2,
+            name: "two",
+            strong: Strong::Two,
+            transparent: None,
+        },
+    ];
+
+    let inserted = conn
+        .copy_in_rows("COPY items FROM STDIN WITH (FORMAT binary)", &items)
+        .await?;
+    assert_eq!(inserted, 2);
+
+    let inserted = conn
+        .copy_in_rows(
+            "COPY items (id, name) FROM STDIN WITH (FORMAT binary)",
+            [Pair(3, "three".to_owned())],
+        )
+        .await?;
+    assert_eq!(inserted, 1);
+
+    let rows: Vec<(i32, String, Option<String>, Option<i32>)> =
+        sqlx_oldapi::query_as("SELECT id, name, strong, transparent FROM items ORDER BY id")
+            .fetch_all(&mut conn)
+            .await?;
+
+    assert_eq!(
+        rows,
+        [
+            (1, "one".to_owned(), Some("one".to_owned()), Some(10)),
+            (2, "two".to_owned(), Some("two".to_owned()), None),
+            (3, "three".to_owned(), None, None),
+        ]
+    );
+
+    Ok(())
+}
@@ -22,6 +22,59 @@ async fn test_query() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn test_query_geometric_types() -> anyhow::Result<()> {
+    use sqlx_oldapi::postgres::types::{PgCircle, PgPoint};
+
+    let mut conn = new::<Postgres>().await?;
+
+    let row = sqlx_oldapi::query!(
+        "SELECT $1::point AS point, circle($1::point, 2) AS circle, array[$1::point] AS points",
+        PgPoint::new(1.0, 2.0)
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(row.point, Some(PgPoint::new(1.0, 2.0)));
+    assert_eq!(
+        row.circle,
+        Some(PgCircle {
+            center: PgPoint::new(1.0, 2.0),
+            radius: 2.0
+        })
+    );
+    assert_eq!(row.points, Some(vec![PgPoint::new(1.0, 2.0)]));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_query_text_search_types() -> anyhow::Result<()> {
+    use sqlx_oldapi::postgres::types::{PgTsQuery, PgTsQueryNode};
+
+    let mut conn = new::<Postgres>().await?;
+
+    let row = sqlx_oldapi::query!(
+        "SELECT to_tsvector('simple', 'fat rats') AS vector, $1::tsquery AS query, \
+         to_tsvector('simple', 'fat rats') @@ $1::tsquery AS matches",
+        PgTsQuery::from(PgTsQueryNode::lexeme("fat"))
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    let vector = row.vector.unwrap();
+
+    assert_eq!(vector.lexemes.len(), 2);
+    assert_eq!(vector.lexemes[0].word, "fat");
+    assert_eq!(
+        row.query,
+        Some(PgTsQuery::from(PgTsQueryNode::lexeme("fat")))
+    );
+    assert_eq!(row.matches, Some(true));
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn test_non_null() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
@@ -1,12 +1,18 @@
 use futures::{StreamExt, TryStreamExt};
+use sqlx_oldapi::error::ErrorKind;
 use sqlx_oldapi::postgres::types::Oid;
 use sqlx_oldapi::postgres::{
     PgAdvisoryLock, PgConnectOptions, PgConnection, PgDatabaseError, PgErrorPosition, PgListener,
-    PgPoolOptions, PgRow, PgSeverity, Postgres,
+    PgOutputMessage, PgOutputValue, PgPipelineResult, PgPoolOptions, PgRow, PgSeverity,
+    PgTargetSessionAttrs, Postgres,
+};
+use sqlx_oldapi::{
+    Column, Connection, Executor, IsolationLevel, RetryPolicy, Row, Statement, TransactionOptions,
+    TypeInfo,
 };
-use sqlx_oldapi::{Column, Connection, Executor, Row, Statement, TypeInfo};
 use sqlx_test::{new, pool, setup_if_needed};
 use std::env;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -510,6 +516,49 @@ async fn it_can_work_with_nested_transactions() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_can_work_with_named_savepoints() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE _sqlx_savepoints (id INTEGER PRIMARY KEY)")
+        .await?;
+
+    let mut tx = conn.begin().await?;
+
+    tx.execute("INSERT INTO _sqlx_savepoints VALUES (1)")
+        .await?;
+
+    // a failed statement inside a savepoint can be rolled back without aborting the transaction
+    let mut savepoint = tx.savepoint("batch").await?;
+    assert_eq!(savepoint.name(), "batch");
+    savepoint
+        .execute("INSERT INTO _sqlx_savepoints VALUES (2)")
+        .await?;
+    assert!(savepoint
+        .execute("INSERT INTO _sqlx_savepoints VALUES (1)")
+        .await
+        .is_err());
+    savepoint.rollback_to().await?;
+
+    let mut savepoint = tx.savepoint("batch").await?;
+    savepoint
+        .execute("INSERT INTO _sqlx_savepoints VALUES (3)")
+        .await?;
+    savepoint.release().await?;
+
+    assert!(tx.savepoint("not a name").await.is_err());
+
+    tx.commit().await?;
+
+    let ids: Vec<i32> = sqlx_oldapi::query_scalar("SELECT id FROM _sqlx_savepoints ORDER BY id")
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(ids, [1, 3]);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_can_drop_multiple_transactions() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
@@ -544,6 +593,400 @@ async fn it_can_drop_multiple_transactions() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_can_begin_with_transaction_options() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let options = TransactionOptions::new()
+        .isolation(IsolationLevel::Serializable)
+        .read_only(true)
+        .deferrable(true);
+
+    let mut tx = conn.begin_with(options).await?;
+
+    let (isolation, read_only, deferrable): (String, String, String) = sqlx_oldapi::query_as(
+        "SELECT current_setting('transaction_isolation'), current_setting('transaction_read_only'), current_setting('transaction_deferrable')",
+    )
+    .fetch_one(&mut tx)
+    .await?;
+
+    assert_eq!(isolation, "serializable");
+    assert_eq!(read_only, "on");
+    assert_eq!(deferrable, "on");
+
+    // options cannot be applied to a savepoint
+    assert!(tx.begin_with(options).await.is_err());
+
+    let res = tx
+        .execute("CREATE TEMPORARY TABLE _sqlx_read_only (id INTEGER)")
+        .await;
+    assert!(res.is_err());
+
+    tx.rollback().await?;
+
+    // the default options start a plain transaction
+    let mut tx = conn.begin_with(TransactionOptions::default()).await?;
+
+    let (isolation,): (String,) =
+        sqlx_oldapi::query_as("SELECT current_setting('transaction_isolation')")
+            .fetch_one(&mut tx)
+            .await?;
+
+    assert_eq!(isolation, "read committed");
+
+    tx.commit().await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_retries_serialization_failures() -> anyhow::Result<()> {
+    let pool = pool::<Postgres>().await?;
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute(
+        r#"
+CREATE TABLE IF NOT EXISTS _sqlx_retry_counter (id INTEGER PRIMARY KEY, value INTEGER NOT NULL);
+DELETE FROM _sqlx_retry_counter;
+INSERT INTO _sqlx_retry_counter VALUES (1, 0);
+    "#,
+    )
+    .await?;
+
+    let attempts = Arc::new(AtomicUsize::new(0));
+    let options = TransactionOptions::new().isolation(IsolationLevel::Serializable);
+
+    let value: i32 = conn
+        .transaction_with_retry(options, RetryPolicy::new(), |tx| {
+            let attempts = Arc::clone(&attempts);
+            let pool = pool.clone();
+
+            Box::pin(async move {
+                let value: i32 =
+                    sqlx_oldapi::query_scalar("SELECT value FROM _sqlx_retry_counter WHERE id = 1")
+                        .fetch_one(&mut **tx)
+                        .await?;
+
+                if attempts.fetch_add(1, Ordering::SeqCst) == 0 {
+                    // a concurrent transaction updates the row after we read it
+                    pool.execute("UPDATE _sqlx_retry_counter SET value = value + 100 WHERE id = 1")
+                        .await?;
+                }
+
+                sqlx_oldapi::query("UPDATE _sqlx_retry_counter SET value = $1 WHERE id = 1")
+                    .bind(value + 1)
+                    .execute(&mut **tx)
+                    .await?;
+
+                Ok::<_, sqlx_oldapi::Error>(value + 1)
+            })
+        })
+        .await?;
+
+    assert_eq!(value, 101);
+    assert_eq!(attempts.load(Ordering::SeqCst), 2);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_classifies_constraint_violations() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute(
+        r#"
+CREATE TEMPORARY TABLE _sqlx_error_kind (id INTEGER PRIMARY KEY, name TEXT NOT NULL);
+INSERT INTO _sqlx_error_kind VALUES (1, 'a');
+    "#,
+    )
+    .await?;
+
+    let err = conn
+        .execute("INSERT INTO _sqlx_error_kind VALUES (1, 'b')")
+        .await
+        .unwrap_err();
+    let err = err.as_database_error().unwrap();
+
+    assert_eq!(err.kind(), ErrorKind::UniqueViolation);
+    assert_eq!(err.constraint(), Some("_sqlx_error_kind_pkey"));
+    assert_eq!(err.table(), Some("_sqlx_error_kind"));
+
+    let err = conn
+        .execute("INSERT INTO _sqlx_error_kind VALUES (2, NULL)")
+        .await
+        .unwrap_err();
+    let err = err.as_database_error().unwrap();
+
+    assert_eq!(err.kind(), ErrorKind::NotNullViolation);
+    assert_eq!(err.column(), Some("name"));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_cancel_a_running_query() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+    let token = conn.cancel_token();
+
+    let cancel = sqlx_rt::spawn(async move {
+        sqlx_rt::sleep(Duration::from_millis(200)).await;
+        token.cancel().await
+    });
+
+    let err = sqlx_rt::timeout(Duration::from_secs(5), conn.execute("SELECT pg_sleep(10)"))
+        .await?
+        .unwrap_err();
+
+    assert_eq!(
+        err.as_database_error().unwrap().kind(),
+        ErrorKind::QueryCanceled
+    );
+    cancel.await??;
+
+    // the connection is still usable
+    let value: i32 = sqlx_oldapi::query_scalar("SELECT 1")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(value, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_cancels_abandoned_queries() -> anyhow::Result<()> {
+    sqlx_test::setup_if_needed();
+
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse().unwrap();
+    let mut conn = PgConnection::connect_with(&options.cancel_abandoned_queries(true)).await?;
+
+    let abandoned = sqlx_rt::timeout(
+        Duration::from_millis(200),
+        conn.execute("SELECT pg_sleep(10)"),
+    )
+    .await;
+    assert!(abandoned.is_err());
+
+    let value: i32 = sqlx_rt::timeout(
+        Duration::from_secs(5),
+        sqlx_oldapi::query_scalar("SELECT 1").fetch_one(&mut conn),
+    )
+    .await??;
+    assert_eq!(value, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_execute_a_pipeline() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE _sqlx_pipeline (id INTEGER PRIMARY KEY)")
+        .await?;
+
+    let mut pipeline = conn.pipeline();
+    for id in 1..=3_i32 {
+        pipeline.push(sqlx_oldapi::query("INSERT INTO _sqlx_pipeline VALUES ($1)").bind(id));
+    }
+    pipeline.push("SELECT id FROM _sqlx_pipeline ORDER BY id");
+
+    let results = pipeline.execute().await?;
+    assert_eq!(results.len(), 4);
+
+    for result in &results[..3] {
+        assert!(
+            matches!(result, PgPipelineResult::Completed(done, _) if done.rows_affected() == 1)
+        );
+    }
+
+    match &results[3] {
+        PgPipelineResult::Completed(_, rows) => {
+            let ids: Vec<i32> = rows.iter().map(|row| row.get(0)).collect();
+            assert_eq!(ids, [1, 2, 3]);
+        }
+        result => panic!("unexpected result: {:?}", result),
+    }
+
+    // a failure aborts the rest of the pipeline and rolls back the implicit transaction
+    let mut pipeline = conn.pipeline();
+    pipeline
+        .push(sqlx_oldapi::query("INSERT INTO _sqlx_pipeline VALUES ($1)").bind(4_i32))
+        .push(sqlx_oldapi::query("INSERT INTO _sqlx_pipeline VALUES ($1)").bind(1_i32))
+        .push(sqlx_oldapi::query("INSERT INTO _sqlx_pipeline VALUES ($1)").bind(5_i32));
+
+    let results = pipeline.execute().await?;
+
+    assert!(matches!(results[0], PgPipelineResult::Completed(..)));
+    match &results[1] {
+        PgPipelineResult::Failed(err) => {
+            assert_eq!(
+                err.as_database_error().unwrap().kind(),
+                ErrorKind::UniqueViolation
+            );
+        }
+        result => panic!("unexpected result: {:?}", result),
+    }
+    assert!(matches!(results[2], PgPipelineResult::Skipped));
+
+    let count: i64 = sqlx_oldapi::query_scalar("SELECT COUNT(*) FROM _sqlx_pipeline")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(count, 3);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_stream_logical_replication() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let wal_level: String = sqlx_oldapi::query_scalar("SHOW wal_level")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(
+        wal_level, "logical",
+        "logical replication needs the server to be started with `-c wal_level=logical`"
+    );
+
+    conn.execute(
+        r#"
+        DROP PUBLICATION IF EXISTS _sqlx_replication;
+        DROP TABLE IF EXISTS _sqlx_replication;
+        CREATE TABLE _sqlx_replication (id INTEGER PRIMARY KEY, name TEXT);
+        CREATE PUBLICATION _sqlx_replication FOR TABLE _sqlx_replication;
+        "#,
+    )
+    .await?;
+
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse()?;
+    let mut replication = PgConnection::connect_with(&options.replication(true)).await?;
+
+    let slot = replication
+        .create_replication_slot("_sqlx_replication", true)
+        .await?;
+    assert_eq!(slot.name(), "_sqlx_replication");
+
+    conn.execute("INSERT INTO _sqlx_replication VALUES (1, 'a')")
+        .await?;
+    conn.execute(
+        "UPDATE _sqlx_replication SET name = 'b' WHERE id = 1; DELETE FROM _sqlx_replication",
+    )
+    .await?;
+
+    let mut stream = replication
+        .start_replication(slot.name(), slot.consistent_point(), &["_sqlx_replication"])
+        .await?;
+
+    let mut messages = Vec::new();
+    while messages
+        .iter()
+        .filter(|message| matches!(message, PgOutputMessage::Commit(_)))
+        .count()
+        < 2
+    {
+        let message = sqlx_rt::timeout(Duration::from_secs(10), stream.recv())
+            .await??
+            .expect("replication stream ended");
+
+        if let PgOutputMessage::Commit(commit) = &message.data {
+            stream.progress().acknowledge(commit.end_lsn);
+        }
+
+        messages.push(message.data);
+    }
+
+    stream.stop().await?;
+
+    let values = |values: &[PgOutputValue]| -> Vec<Option<String>> {
+        values
+            .iter()
+            .map(|value| value.as_str().map(ToOwned::to_owned))
+            .collect()
+    };
+
+    assert!(matches!(messages[0], PgOutputMessage::Begin(_)));
+    match &messages[1] {
+        PgOutputMessage::Relation(relation) => {
+            assert_eq!(relation.name, "_sqlx_replication");
+            assert_eq!(relation.columns.len(), 2);
+            assert!(relation.columns[0].is_key);
+        }
+        other => panic!("unexpected message: {:?}", other),
+    }
+    match &messages[2] {
+        PgOutputMessage::Insert(insert) => {
+            assert_eq!(values(&insert.new), [Some("1".into()), Some("a".into())]);
+        }
+        other => panic!("unexpected message: {:?}", other),
+    }
+    assert!(matches!(messages[3], PgOutputMessage::Commit(_)));
+    assert!(matches!(messages[4], PgOutputMessage::Begin(_)));
+    match &messages[5] {
+        PgOutputMessage::Update(update) => {
+            assert_eq!(values(&update.new), [Some("1".into()), Some("b".into())]);
+        }
+        other => panic!("unexpected message: {:?}", other),
+    }
+    match &messages[6] {
+        PgOutputMessage::Delete(delete) => {
+            let key = delete.key.as_deref().expect("delete without key");
+            assert_eq!(values(key), [Some("1".into()), None]);
+        }
+        other => panic!("unexpected message: {:?}", other),
+    }
+    assert!(matches!(messages[7], PgOutputMessage::Commit(_)));
+
+    // a dropped stream is stopped the next time the connection is used
+    let stream = replication
+        .start_replication(slot.name(), slot.consistent_point(), &["_sqlx_replication"])
+        .await?;
+    drop(stream);
+
+    let row = replication.fetch_one("SELECT 1").await?;
+    assert_eq!(row.try_get::<i32, _>(0)?, 1);
+
+    replication.close().await?;
+    conn.execute("DROP PUBLICATION _sqlx_replication; DROP TABLE _sqlx_replication")
+        .await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_connects_to_the_first_matching_host() -> anyhow::Result<()> {
+    sqlx_test::setup_if_needed();
+
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse()?;
+
+    // nothing listens on port 1, so the second host is used
+    let fallback = options
+        .clone()
+        .port(1)
+        .add_host(options.get_host(), options.get_port())
+        .target_session_attrs(PgTargetSessionAttrs::ReadWrite);
+
+    let mut conn = PgConnection::connect_with(&fallback).await?;
+    let value: i32 = sqlx_oldapi::query_scalar("SELECT 1")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(value, 1);
+    conn.close().await?;
+
+    // the test server is not a standby
+    let standby = options
+        .clone()
+        .target_session_attrs(PgTargetSessionAttrs::Standby);
+    let error = PgConnection::connect_with(&standby).await.unwrap_err();
+    assert!(matches!(error, sqlx_oldapi::Error::Configuration(_)));
+
+    let prefer_standby = options.target_session_attrs(PgTargetSessionAttrs::PreferStandby);
+    PgConnection::connect_with(&prefer_standby)
+        .await?
+        .close()
+        .await?;
+
+    Ok(())
+}
+
 // run with `cargo test --features postgres -- --ignored --nocapture pool_smoke_test`
 #[ignore]
 #[sqlx_macros::test]
@@ -735,6 +1178,50 @@ async fn it_caches_statements() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_resets_session_on_release() -> anyhow::Result<()> {
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .reset_on_release(true)
+        .connect(&dotenvy::var("DATABASE_URL")?)
+        .await?;
+
+    let mut conn = pool.acquire().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE reset_on_release (id INT)")
+        .await?;
+
+    sqlx_oldapi::query("SELECT $1::int4")
+        .bind(1_i32)
+        .persistent(true)
+        .execute(&mut *conn)
+        .await?;
+
+    assert_eq!(1, conn.cached_statements_size());
+    drop(conn);
+
+    // With only one connection, this is the same one, returned after the reset.
+    let mut conn = pool.acquire().await?;
+
+    assert_eq!(0, conn.cached_statements_size());
+
+    let temp_table_exists: bool =
+        sqlx_oldapi::query_scalar("SELECT to_regclass('pg_temp.reset_on_release') IS NOT NULL")
+            .fetch_one(&mut *conn)
+            .await?;
+
+    assert!(!temp_table_exists);
+
+    // The statement was deallocated on the server, so preparing it again must succeed.
+    sqlx_oldapi::query("SELECT $1::int4")
+        .bind(1_i32)
+        .persistent(true)
+        .execute(&mut *conn)
+        .await?;
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_closes_statement_from_cache_issue_470() -> anyhow::Result<()> {
     sqlx_test::setup_if_needed();
@@ -1508,30 +1995,600 @@ async fn it_can_copy_out() -> anyhow::Result<()> {
 }
 
 #[sqlx_macros::test]
-async fn it_encodes_custom_array_issue_1504() -> anyhow::Result<()> {
-    use sqlx_oldapi::encode::IsNull;
-    use sqlx_oldapi::postgres::{PgArgumentBuffer, PgTypeInfo};
-    use sqlx_oldapi::{Decode, Encode, Type, ValueRef};
+async fn it_can_reuse_the_connection_after_an_unfinished_copy_out() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
 
-    #[derive(Debug, PartialEq)]
-    enum Value {
-        String(String),
-        Number(i32),
-        Array(Vec<Value>),
+    // the stream is dropped before all the data is read
+    {
+        let mut copy = conn
+            .copy_out_raw("COPY (SELECT generate_series(1, 100000)) TO STDOUT")
+            .await?;
+
+        assert_eq!(copy.next().await.unwrap()?.as_ref(), b"1\n");
     }
 
-    impl<'r> Decode<'r, Postgres> for Value {
-        fn decode(
-            value: sqlx_oldapi::postgres::PgValueRef<'r>,
-        ) -> std::result::Result<Self, Box<dyn std::error::Error + 'static + Send + Sync>> {
-            let typ = value.type_info().into_owned();
+    let value: i32 = sqlx_oldapi::query_scalar("select 1 + 1")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(2i32, value);
 
-            if typ == PgTypeInfo::with_name("text") {
-                let s = <String as Decode<'_, Postgres>>::decode(value)?;
+    // the statement fails before any data is sent
+    assert!(conn
+        .copy_out_raw("COPY (SELECT 1 / 0) TO STDOUT")
+        .await
+        .is_err());
 
-                Ok(Self::String(s))
-            } else if typ == PgTypeInfo::with_name("int4") {
-                let n = <i32 as Decode<'_, Postgres>>::decode(value)?;
+    let value: i32 = sqlx_oldapi::query_scalar("select 2 + 2")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(4i32, value);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_copy_in_rows() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+    conn.execute("CREATE TEMPORARY TABLE users (id INTEGER NOT NULL, name TEXT);")
+        .await?;
+
+    let rows = vec![(1_i32, Some("alice")), (2, None), (3, Some("carol"))];
+    let inserted = conn
+        .copy_in_rows(
+            "COPY users (id, name) FROM STDIN WITH (FORMAT binary)",
+            &rows,
+        )
+        .await?;
+    assert_eq!(inserted, 3);
+
+    let stream = futures::stream::iter(vec![(4_i32, "dave".to_owned())]);
+    let inserted = conn
+        .copy_in_row_stream(
+            "COPY users (id, name) FROM STDIN WITH (FORMAT binary)",
+            stream,
+        )
+        .await?;
+    assert_eq!(inserted, 1);
+
+    let users: Vec<(i32, Option<String>)> =
+        sqlx_oldapi::query_as("SELECT id, name FROM users ORDER BY id")
+            .fetch_all(&mut conn)
+            .await?;
+    assert_eq!(
+        users,
+        [
+            (1, Some("alice".to_owned())),
+            (2, None),
+            (3, Some("carol".to_owned())),
+            (4, Some("dave".to_owned())),
+        ]
+    );
+
+    // rows must match the columns, nothing is inserted otherwise
+    let res = conn
+        .copy_in_rows(
+            "COPY users (id, name) FROM STDIN WITH (FORMAT binary)",
+            [(5_i32,)],
+        )
+        .await;
+    assert!(res.is_err());
+
+    let res = conn
+        .copy_in_rows("COPY users (id) FROM STDIN WITH (FORMAT csv)", [(5_i32,)])
+        .await;
+    assert!(res.is_err());
+
+    // binary values are not converted to the type of their column
+    let res = conn
+        .copy_in_rows(
+            "COPY users (id, name) FROM STDIN WITH (FORMAT binary)",
+            [(5_i64, "eve")],
+        )
+        .await;
+    assert!(
+        matches!(&res, Err(sqlx_oldapi::Error::Protocol(msg)) if msg.contains("mismatched types")),
+        "{:?}",
+        res
+    );
+
+    let count: i64 = sqlx_oldapi::query_scalar("SELECT COUNT(*) FROM users")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(count, 4);
+
+    Ok(())
+}
+
+#[cfg(feature = "json")]
+#[sqlx_macros::test]
+async fn it_can_copy_in_json_rows() -> anyhow::Result<()> {
+    use sqlx_oldapi::types::Json;
+
+    let mut conn = new::<Postgres>().await?;
+    conn.execute("CREATE TEMPORARY TABLE json_rows (id INTEGER NOT NULL, obj json, obj2 jsonb);")
+        .await?;
+
+    // `json` and `jsonb` values are encoded differently, from the types of their columns
+    let rows = vec![
+        (
+            1_i32,
+            Json(serde_json::json!({ "a": 1 })),
+            Json(serde_json::json!([1, 2])),
+        ),
+        (
+            2,
+            Json(serde_json::json!("b")),
+            Json(serde_json::json!({ "c": null })),
+        ),
+    ];
+    let inserted = conn
+        .copy_in_rows(
+            "COPY json_rows (id, obj, obj2) FROM STDIN WITH (FORMAT binary)",
+            &rows,
+        )
+        .await?;
+    assert_eq!(inserted, 2);
+
+    let stream = futures::stream::iter(vec![(
+        3_i32,
+        Json(serde_json::json!(3)),
+        Json(serde_json::json!(true)),
+    )]);
+    let inserted = conn
+        .copy_in_row_stream("COPY json_rows FROM STDIN WITH (FORMAT binary)", stream)
+        .await?;
+    assert_eq!(inserted, 1);
+
+    let values: Vec<(i32, serde_json::Value, serde_json::Value)> =
+        sqlx_oldapi::query_as("SELECT id, obj, obj2 FROM json_rows ORDER BY id")
+            .fetch_all(&mut conn)
+            .await?;
+    assert_eq!(
+        values,
+        [
+            (1, serde_json::json!({ "a": 1 }), serde_json::json!([1, 2])),
+            (2, serde_json::json!("b"), serde_json::json!({ "c": null })),
+            (3, serde_json::json!(3), serde_json::json!(true)),
+        ]
+    );
+
+    // without the column types, the values can not be encoded
+    let mut copy = conn
+        .copy_in_raw("COPY json_rows FROM STDIN WITH (FORMAT binary)")
+        .await?;
+    assert!(copy.send_rows(&rows).await.is_err());
+    copy.abort("no column types").await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_copy_in_rows_without_generated_columns() -> anyhow::Result<()> {
+    // Generated columns are only supported in Postgres 12+
+    let mut conn = new::<Postgres>().await?;
+    if matches!(conn.server_version_num(), Some(version) if version < 120000) {
+        return Ok(());
+    }
+
+    conn.execute(
+        "CREATE TEMPORARY TABLE measures (id INTEGER NOT NULL, \
+         double INTEGER GENERATED ALWAYS AS (id * 2) STORED, value BIGINT NOT NULL);",
+    )
+    .await?;
+
+    // without a column list, `COPY` skips the generated column
+    let inserted = conn
+        .copy_in_rows(
+            "COPY measures FROM STDIN WITH (FORMAT binary)",
+            [(1_i32, 10_i64), (2, 20)],
+        )
+        .await?;
+    assert_eq!(inserted, 2);
+
+    let rows: Vec<(i32, i32, i64)> =
+        sqlx_oldapi::query_as("SELECT id, double, value FROM measures ORDER BY id")
+            .fetch_all(&mut conn)
+            .await?;
+    assert_eq!(rows, [(1, 2, 10), (2, 4, 20)]);
+
+    // the types of the fields are checked against the columns being copied
+    let res = conn
+        .copy_in_rows(
+            "COPY measures FROM STDIN WITH (FORMAT binary)",
+            [(3_i32, 30_i32)],
+        )
+        .await;
+    assert!(
+        matches!(&res, Err(sqlx_oldapi::Error::Protocol(msg)) if msg.contains("mismatched types")),
+        "{:?}",
+        res
+    );
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_copy_out_rows() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    {
+        let rows = conn
+            .copy_out_rows::<(i32, Option<String>)>(
+                "SELECT i, CASE WHEN i % 2 = 0 THEN 'even' END FROM generate_series(1, 3) AS i",
+            )
+            .await?;
+
+        let rows: Vec<_> = rows.try_collect().await?;
+        assert_eq!(rows, [(1, None), (2, Some("even".to_owned())), (3, None)]);
+    }
+
+    // types are checked against the columns of the query
+    {
+        let rows = conn.copy_out_rows::<(String,)>("SELECT 1::int4").await?;
+        assert!(rows.try_collect::<Vec<_>>().await.is_err());
+    }
+
+    // conn is safe for reuse
+    let value: i32 = sqlx_oldapi::query_scalar("select 1 + 1")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(2i32, value);
+
+    Ok(())
+}
+
+#[cfg(feature = "macros")]
+#[sqlx_macros::test]
+async fn it_can_copy_out_from_row_types() -> anyhow::Result<()> {
+    #[derive(Debug, PartialEq, sqlx_oldapi::FromRow)]
+    struct Item {
+        name: String,
+        id: i64,
+    }
+
+    let pool = pool::<Postgres>().await?;
+
+    let items: Vec<Item> = pool
+        .copy_out_rows(
+            "SELECT i::int8 AS id, 'item ' || i AS name FROM generate_series(1, 1000) AS i",
+        )
+        .await?
+        .try_collect()
+        .await?;
+
+    assert_eq!(items.len(), 1000);
+    assert_eq!(
+        items[999],
+        Item {
+            name: "item 1000".to_owned(),
+            id: 1000
+        }
+    );
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_fetch_rows_from_a_portal_in_batches() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    {
+        let mut portal = conn
+            .portal(
+                sqlx_oldapi::query("SELECT i FROM generate_series(1, $1) AS i").bind(5_i32),
+                2,
+            )
+            .await?;
+
+        let mut batches = Vec::new();
+        while !portal.is_completed() {
+            let rows = portal.next_batch().await?;
+            batches.push(
+                rows.iter()
+                    .map(|row| row.try_get::<i32, _>(0))
+                    .collect::<Result<Vec<_>, _>>()?,
+            );
+        }
+
+        assert_eq!(batches, [vec![1, 2], vec![3, 4], vec![5]]);
+    }
+
+    // a portal dropped before it is completed is closed
+    {
+        let mut portal = conn
+            .portal(sqlx_oldapi::query("SELECT generate_series(1, 1000)"), 10)
+            .await?;
+        assert_eq!(portal.next_batch().await?.len(), 10);
+    }
+
+    let rows: Vec<PgRow> = conn
+        .portal(sqlx_oldapi::query("SELECT generate_series(1, 1000)"), 100)
+        .await?
+        .into_stream()
+        .try_collect()
+        .await?;
+    assert_eq!(rows.len(), 1000);
+
+    // an error while fetching is returned and ends the portal
+    {
+        let mut portal = conn
+            .portal(
+                sqlx_oldapi::query("SELECT 10 / (5 - i) FROM generate_series(1, 10) AS i"),
+                2,
+            )
+            .await?;
+        assert_eq!(portal.next_batch().await?.len(), 2);
+        assert_eq!(portal.next_batch().await?.len(), 2);
+        assert!(portal.next_batch().await.is_err());
+        assert!(portal.is_completed());
+    }
+
+    // conn is safe for reuse
+    let value: i32 = sqlx_oldapi::query_scalar("select 1 + 1")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(2i32, value);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_fetch_rows_from_a_cursor() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    // cursors only exist in transactions
+    assert!(conn
+        .declare_cursor("_sqlx_cursor", sqlx_oldapi::query("SELECT 1"))
+        .await
+        .is_err());
+
+    let mut tx = conn.begin().await?;
+
+    {
+        let mut cursor = tx
+            .declare_cursor(
+                "_sqlx_cursor",
+                sqlx_oldapi::query("SELECT i, i::text FROM generate_series(1, $1) AS i")
+                    .bind(5_i32),
+            )
+            .await?;
+        assert_eq!(cursor.name(), "_sqlx_cursor");
+
+        let rows = cursor.fetch(3).await?;
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[2].try_get::<i32, _>(0)?, 3);
+        assert_eq!(rows[2].try_get::<String, _>(1)?, "3");
+
+        assert_eq!(cursor.fetch(3).await?.len(), 2);
+        assert!(cursor.fetch(3).await?.is_empty());
+
+        cursor.close().await?;
+    }
+
+    // the name can be reused once the cursor is closed
+    let rows: Vec<PgRow> = tx
+        .declare_cursor(
+            "_sqlx_cursor",
+            sqlx_oldapi::query("SELECT generate_series(1, 1000)"),
+        )
+        .await?
+        .into_stream(100)
+        .try_collect()
+        .await?;
+    assert_eq!(rows.len(), 1000);
+
+    // a count of 0 fetches all the remaining rows
+    {
+        let mut cursor = tx
+            .declare_cursor(
+                "_sqlx_cursor_all",
+                sqlx_oldapi::query("SELECT generate_series(1, 10)"),
+            )
+            .await?;
+
+        assert_eq!(cursor.fetch(4).await?.len(), 4);
+        assert_eq!(cursor.fetch(0).await?.len(), 6);
+        assert!(cursor.fetch(0).await?.is_empty());
+    }
+
+    let rows: Vec<PgRow> = tx
+        .declare_cursor(
+            "_sqlx_cursor_stream_all",
+            sqlx_oldapi::query("SELECT generate_series(1, 10)"),
+        )
+        .await?
+        .into_stream(0)
+        .try_collect()
+        .await?;
+    assert_eq!(rows.len(), 10);
+
+    tx.rollback().await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_streams_large_objects() -> anyhow::Result<()> {
+    use sqlx_oldapi::postgres::{PgLargeObject, PgLargeObjectMode};
+    use sqlx_rt::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+    use std::io::SeekFrom;
+
+    let mut conn = new::<Postgres>().await?;
+
+    // larger than the chunks sent in each call of `loread` / `lowrite`
+    let data: Vec<u8> = (0..1_000_000_u32).map(|i| (i % 251) as u8).collect();
+
+    let mut tx = conn.begin().await?;
+
+    let mut object = PgLargeObject::create(&mut tx).await?;
+    let oid = object.oid();
+
+    object.write_all(&data).await?;
+    object.flush().await?;
+
+    assert_eq!(object.seek(SeekFrom::Start(10)).await?, 10);
+
+    let mut buf = [0_u8; 5];
+    object.read_exact(&mut buf).await?;
+    assert_eq!(buf, data[10..15]);
+
+    // writes go at the position of the reader
+    object.write_all(b"hello").await?;
+    assert_eq!(object.stream_position().await?, 20);
+    assert_eq!(object.seek(SeekFrom::End(-1)).await?, 999_999);
+
+    object.close().await?;
+
+    let mut object = PgLargeObject::open(&mut tx, oid, PgLargeObjectMode::Read).await?;
+
+    let mut contents = Vec::new();
+    object.read_to_end(&mut contents).await?;
+    assert_eq!(contents.len(), data.len());
+    assert_eq!(&contents[..15], &data[..15]);
+    assert_eq!(&contents[15..20], b"hello");
+    assert_eq!(contents[20..], data[20..]);
+
+    object.close().await?;
+
+    PgLargeObject::unlink(&mut tx, oid).await?;
+    assert!(PgLargeObject::open(&mut tx, oid, PgLargeObjectMode::Read)
+        .await
+        .is_err());
+
+    tx.rollback().await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_shares_exported_snapshots() -> anyhow::Result<()> {
+    let pool = pool::<Postgres>().await?;
+
+    let mut tx = pool
+        .begin_with(TransactionOptions::new().isolation(IsolationLevel::RepeatableRead))
+        .await?;
+    let snapshot = tx.export_snapshot().await?;
+
+    let exported: String = sqlx_oldapi::query_scalar("SELECT txid_current_snapshot()::text")
+        .fetch_one(&mut *tx)
+        .await?;
+
+    // assign a transaction id, so that a new snapshot would differ
+    pool.execute("SELECT txid_current()").await?;
+
+    let mut conn = new::<Postgres>().await?;
+
+    for mut reader in [
+        pool.begin_with_snapshot(&snapshot).await?,
+        conn.begin_with_snapshot(&snapshot).await?,
+    ] {
+        let imported: String = sqlx_oldapi::query_scalar("SELECT txid_current_snapshot()::text")
+            .fetch_one(&mut *reader)
+            .await?;
+        assert_eq!(imported, exported);
+
+        reader.rollback().await?;
+    }
+
+    assert!(pool
+        .begin_with_snapshot("00000000-00000000-0")
+        .await
+        .is_err());
+
+    tx.commit().await?;
+
+    // the snapshot can only be imported while the exporting transaction is open
+    assert!(conn.begin_with_snapshot(&snapshot).await.is_err());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_calls_the_notice_handler() -> anyhow::Result<()> {
+    let notices = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse()?;
+    let options = options.notice_handler({
+        let notices = Arc::clone(&notices);
+        move |notice| notices.lock().unwrap().push(notice.clone())
+    });
+
+    let mut conn = PgConnection::connect_with(&options).await?;
+
+    conn.execute(
+        r#"
+        DO $$
+        BEGIN
+            RAISE NOTICE 'hello' USING DETAIL = 'some detail', HINT = 'some hint';
+            RAISE WARNING 'careful' USING ERRCODE = 'P0002';
+        END
+        $$
+        "#,
+    )
+    .await?;
+
+    let notices = notices.lock().unwrap();
+    assert_eq!(notices.len(), 2);
+
+    assert_eq!(notices[0].severity(), PgSeverity::Notice);
+    assert_eq!(notices[0].code(), "00000");
+    assert_eq!(notices[0].message(), "hello");
+    assert_eq!(notices[0].detail(), Some("some detail"));
+    assert_eq!(notices[0].hint(), Some("some hint"));
+    assert!(notices[0].r#where().unwrap().contains("PL/pgSQL"));
+
+    assert_eq!(notices[1].severity(), PgSeverity::Warning);
+    assert_eq!(notices[1].code(), "P0002");
+    assert_eq!(notices[1].message(), "careful");
+    assert_eq!(notices[1].detail(), None);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_reports_server_parameters() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    assert!(conn.server_parameter("server_version").is_some());
+    assert_eq!(conn.server_parameter("client_encoding"), Some("UTF8"));
+    assert_eq!(conn.server_parameter("unknown"), None);
+
+    conn.execute("SET TIME ZONE 'America/New_York'").await?;
+    assert_eq!(conn.server_parameter("TimeZone"), Some("America/New_York"));
+
+    conn.execute("SET TIME ZONE 'UTC'").await?;
+    assert_eq!(conn.server_parameter("TimeZone"), Some("UTC"));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_encodes_custom_array_issue_1504() -> anyhow::Result<()> {
+    use sqlx_oldapi::encode::IsNull;
+    use sqlx_oldapi::postgres::{PgArgumentBuffer, PgTypeInfo};
+    use sqlx_oldapi::{Decode, Encode, Type, ValueRef};
+
+    #[derive(Debug, PartialEq)]
+    enum Value {
+        String(String),
+        Number(i32),
+        Array(Vec<Value>),
+    }
+
+    impl<'r> Decode<'r, Postgres> for Value {
+        fn decode(
+            value: sqlx_oldapi::postgres::PgValueRef<'r>,
+        ) -> std::result::Result<Self, Box<dyn std::error::Error + 'static + Send + Sync>> {
+            let typ = value.type_info().into_owned();
+
+            if typ == PgTypeInfo::with_name("text") {
+                let s = <String as Decode<'_, Postgres>>::decode(value)?;
+
+                Ok(Self::String(s))
+            } else if typ == PgTypeInfo::with_name("int4") {
+                let n = <i32 as Decode<'_, Postgres>>::decode(value)?;
 
                 Ok(Self::Number(n))
             } else if typ == PgTypeInfo::with_name("_text") {
@@ -1729,6 +2786,94 @@ async fn test_advisory_locks() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn test_shared_and_transaction_advisory_locks() -> anyhow::Result<()> {
+    let pool = PgPoolOptions::new()
+        .max_connections(3)
+        .connect(&dotenvy::var("DATABASE_URL")?)
+        .await?;
+
+    let lock = PgAdvisoryLock::new("sqlx-postgres-tests-shared");
+
+    // shared locks can be held by several connections, but exclude exclusive locks
+    let conn1_shared = lock.acquire_shared(pool.acquire().await?).await?;
+    let conn2_shared = lock
+        .try_acquire_shared(pool.acquire().await?)
+        .await?
+        .left()
+        .expect("failed to acquire a second shared lock");
+
+    let conn3 = lock
+        .try_acquire(pool.acquire().await?)
+        .await?
+        .right()
+        .expect("acquired an exclusive lock while shared locks are held");
+
+    let mut conn3 = lock
+        .acquire_timeout(conn3, Duration::from_millis(50))
+        .await?
+        .right()
+        .expect("acquired an exclusive lock while shared locks are held");
+
+    // the previous `lock_timeout` is restored
+    let lock_timeout: String = sqlx_oldapi::query_scalar("SHOW lock_timeout")
+        .fetch_one(&mut *conn3)
+        .await?;
+    assert_eq!(lock_timeout, "0");
+
+    conn1_shared.release_now().await?;
+    conn2_shared.release_now().await?;
+
+    // outside of a transaction, the lock outlives the transaction the call is made in
+    let mut conn3 = lock
+        .acquire_timeout(conn3, Duration::MAX)
+        .await?
+        .left()
+        .expect("failed to acquire an exclusive lock");
+    let lock_timeout: String = sqlx_oldapi::query_scalar("SHOW lock_timeout")
+        .fetch_one(conn3.as_mut())
+        .await?;
+    assert_eq!(lock_timeout, "0");
+    assert!(lock
+        .try_acquire(pool.acquire().await?)
+        .await?
+        .right()
+        .is_some());
+    conn3.release_now().await?;
+
+    let mut tx = pool.begin().await?;
+    lock.acquire_xact(&mut tx).await?;
+
+    // the transaction can still be used after timing out
+    let mut other_tx = pool.begin().await?;
+    assert!(
+        !lock
+            .acquire_xact_shared_timeout(&mut other_tx, Duration::from_millis(50))
+            .await?
+    );
+    assert!(!lock.try_acquire_xact(&mut other_tx).await?);
+    other_tx.execute("SELECT 1").await?;
+
+    // transaction-scoped locks are released when the transaction ends
+    tx.commit().await?;
+
+    assert!(
+        lock.acquire_xact_timeout(&mut other_tx, Duration::from_secs(5))
+            .await?
+    );
+
+    // the `lock_timeout` of the transaction is restored once the lock is acquired
+    let lock_timeout: String = sqlx_oldapi::query_scalar("SHOW lock_timeout")
+        .fetch_one(&mut *other_tx)
+        .await?;
+    assert_eq!(lock_timeout, "0");
+    other_tx.rollback().await?;
+
+    pool.close().await;
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn test_postgres_bytea_hex_deserialization_errors() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
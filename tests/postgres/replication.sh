@@ -0,0 +1,4 @@
+#!/bin/sh
+# the logical replication tests connect with `replication=database`, which is only allowed by
+# `pg_hba.conf` entries for the `replication` database
+echo "host replication all all ${POSTGRES_HOST_AUTH_METHOD:-trust}" >> "$PGDATA/pg_hba.conf"
@@ -3,7 +3,10 @@ extern crate time_ as time;
 
 use std::ops::Bound;
 
-use sqlx_oldapi::postgres::types::{Oid, PgInterval, PgMoney, PgRange};
+use sqlx_oldapi::postgres::types::{
+    Oid, PgBox, PgCircle, PgInterval, PgLSeg, PgLine, PgMoney, PgPath, PgPoint, PgPolygon, PgRange,
+    PgTsLexeme, PgTsPosition, PgTsQuery, PgTsQueryNode, PgTsVector, PgTsWeight,
+};
 use sqlx_oldapi::postgres::Postgres;
 use sqlx_test::{test_decode_type, test_prepared_type, test_type};
 
@@ -316,6 +319,30 @@ mod chrono {
         "TIMETZ '05:10:20.115100-05'" == PgTimeTz { time: NaiveTime::from_hms_micro_opt(5, 10, 20, 115100).unwrap(), offset: FixedOffset::west_opt(60 * 60 * 5).unwrap() },
         "TIMETZ '05:10:20+02'" == PgTimeTz { time: NaiveTime::from_hms_opt(5, 10, 20).unwrap(), offset: FixedOffset::east_opt(60 * 60 * 2 ).unwrap()}
     ));
+
+    test_type!(chrono_tsrange<PgRange<NaiveDateTime>>(Postgres,
+        "'[\"2019-01-02 05:10:20\",\"2019-01-03 00:00:00\")'::tsrange"
+            == PgRange::from(
+                NaiveDate::from_ymd_opt(2019, 1, 2).unwrap().and_hms_opt(5, 10, 20).unwrap()
+                    ..NaiveDate::from_ymd_opt(2019, 1, 3).unwrap().and_hms_opt(0, 0, 0).unwrap()
+            ),
+        "'[\"2020-01-01 00:00:00\",)'::tsrange"
+            == PgRange::from(NaiveDate::from_ymd_opt(2020, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap()..),
+    ));
+
+    #[cfg(postgres_14)]
+    test_type!(chrono_tsmultirange<sqlx_oldapi::postgres::types::PgMultiRange<NaiveDateTime>>(Postgres,
+        "'{[\"2019-01-02 05:10:20\",\"2019-01-03 00:00:00\"), [\"2020-01-01 00:00:00\",)}'::tsmultirange"
+            == sqlx_oldapi::postgres::types::PgMultiRange::from(vec![
+                PgRange::from(
+                    NaiveDate::from_ymd_opt(2019, 1, 2).unwrap().and_hms_opt(5, 10, 20).unwrap()
+                        ..NaiveDate::from_ymd_opt(2019, 1, 3).unwrap().and_hms_opt(0, 0, 0).unwrap()
+                ),
+                PgRange::from(
+                    NaiveDate::from_ymd_opt(2020, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap()..
+                ),
+            ])
+    ));
 }
 
 #[cfg(feature = "time")]
@@ -528,6 +555,24 @@ test_type!(int4range<PgRange<i32>>(Postgres,
     "'[1,2]'::int4range" == PgRange::from((INC1, EXC3)),
 ));
 
+#[cfg(postgres_14)]
+test_type!(int4multirange<sqlx_oldapi::postgres::types::PgMultiRange<i32>>(Postgres,
+    "'{}'::int4multirange" == sqlx_oldapi::postgres::types::PgMultiRange::<i32>::default(),
+    "'{[1,2), (,2]}'::int4multirange" == sqlx_oldapi::postgres::types::PgMultiRange::from(vec![PgRange::from((UNB, EXC3))]),
+    "'{[5,), [1,2)}'::int4multirange" == sqlx_oldapi::postgres::types::PgMultiRange::from(vec![
+        PgRange::from((INC1, EXC2)),
+        PgRange::from((Bound::Included(5), UNB)),
+    ]),
+));
+
+#[cfg(postgres_14)]
+test_type!(int4multirange_vec<Vec<sqlx_oldapi::postgres::types::PgMultiRange<i32>>>(Postgres,
+    "array['{[1,2)}'::int4multirange, '{}'::int4multirange]" == vec![
+        sqlx_oldapi::postgres::types::PgMultiRange::from(vec![PgRange::from((INC1, EXC2))]),
+        sqlx_oldapi::postgres::types::PgMultiRange::<i32>::default(),
+    ],
+));
+
 test_prepared_type!(interval<PgInterval>(
     Postgres,
     "INTERVAL '1h'"
@@ -567,6 +612,107 @@ test_prepared_type!(money_vec<Vec<PgMoney>>(Postgres,
     "array[123.45,420.00,666.66]::money[]" == vec![PgMoney(12345), PgMoney(42000), PgMoney(66666)],
 ));
 
+test_type!(point<PgPoint>(Postgres,
+    "SELECT ({0}::text is not distinct from $1::text)::int4, {0} as _2, $2 as _3",
+    "'(1.5,-2)'::point" == PgPoint::new(1.5, -2.0),
+    "point(0, 1e20)" == PgPoint::new(0.0, 1e20),
+));
+
+test_type!(point_vec<Vec<PgPoint>>(Postgres,
+    "SELECT ({0}::text[] is not distinct from $1::text[])::int4, {0} as _2, $2 as _3",
+    "array['(1,2)'::point, '(3,4)'::point]" == vec![PgPoint::new(1.0, 2.0), PgPoint::new(3.0, 4.0)],
+));
+
+test_type!(line<PgLine>(Postgres,
+    "SELECT ({0}::text is not distinct from $1::text)::int4, {0} as _2, $2 as _3",
+    "'{1,-1,0.5}'::line" == PgLine { a: 1.0, b: -1.0, c: 0.5 },
+));
+
+test_type!(lseg<PgLSeg>(Postgres,
+    "SELECT ({0}::text is not distinct from $1::text)::int4, {0} as _2, $2 as _3",
+    "'[(0,0),(1,2)]'::lseg" == PgLSeg { start: PgPoint::new(0.0, 0.0), end: PgPoint::new(1.0, 2.0) },
+));
+
+test_type!(box_<PgBox>(Postgres,
+    "SELECT ({0}::text is not distinct from $1::text)::int4, {0} as _2, $2 as _3",
+    "'(1,2),(3,4)'::box" == PgBox { upper_right: PgPoint::new(3.0, 4.0), lower_left: PgPoint::new(1.0, 2.0) },
+));
+
+test_type!(path<PgPath>(Postgres,
+    "SELECT ({0}::text is not distinct from $1::text)::int4, {0} as _2, $2 as _3",
+    "'[(0,0),(1,1),(2,0)]'::path" == PgPath { closed: false, points: vec![PgPoint::new(0.0, 0.0), PgPoint::new(1.0, 1.0), PgPoint::new(2.0, 0.0)] },
+    "'((0,0),(1,1))'::path" == PgPath { closed: true, points: vec![PgPoint::new(0.0, 0.0), PgPoint::new(1.0, 1.0)] },
+));
+
+test_type!(polygon<PgPolygon>(Postgres,
+    "SELECT ({0}::text is not distinct from $1::text)::int4, {0} as _2, $2 as _3",
+    "'((0,0),(0,1),(1,0))'::polygon" == PgPolygon { points: vec![PgPoint::new(0.0, 0.0), PgPoint::new(0.0, 1.0), PgPoint::new(1.0, 0.0)] },
+));
+
+test_type!(circle<PgCircle>(Postgres,
+    "SELECT ({0}::text is not distinct from $1::text)::int4, {0} as _2, $2 as _3",
+    "'<(1,2),3.5>'::circle" == PgCircle { center: PgPoint::new(1.0, 2.0), radius: 3.5 },
+));
+
+test_prepared_type!(tsvector<PgTsVector>(Postgres,
+    "'a:1A,3 fat:2 rat'::tsvector" == PgTsVector {
+        lexemes: vec![
+            PgTsLexeme {
+                word: "a".to_owned(),
+                positions: vec![PgTsPosition { position: 1, weight: PgTsWeight::A }, PgTsPosition::new(3)],
+            },
+            PgTsLexeme { word: "fat".to_owned(), positions: vec![PgTsPosition::new(2)] },
+            PgTsLexeme { word: "rat".to_owned(), positions: vec![] },
+        ],
+    },
+    "''::tsvector" == PgTsVector::default(),
+));
+
+test_prepared_type!(tsvector_vec<Vec<PgTsVector>>(Postgres,
+    "array['fat:2'::tsvector]" == vec![PgTsVector {
+        lexemes: vec![PgTsLexeme { word: "fat".to_owned(), positions: vec![PgTsPosition::new(2)] }],
+    }],
+));
+
+test_prepared_type!(tsquery<PgTsQuery>(Postgres,
+    "SELECT ({0}::text is not distinct from $1::text)::int4, {0} as _2, $2 as _3",
+    "'fat & !rat'::tsquery" == PgTsQuery::from(PgTsQueryNode::And(
+        Box::new(PgTsQueryNode::lexeme("fat")),
+        Box::new(PgTsQueryNode::Not(Box::new(PgTsQueryNode::lexeme("rat")))),
+    )),
+    "'(a:*AB | b) <2> c'::tsquery" == PgTsQuery::from(PgTsQueryNode::Phrase(
+        Box::new(PgTsQueryNode::Or(
+            Box::new(PgTsQueryNode::Lexeme {
+                word: "a".to_owned(),
+                weights: vec![PgTsWeight::A, PgTsWeight::B],
+                prefix: true,
+            }),
+            Box::new(PgTsQueryNode::lexeme("b")),
+        )),
+        Box::new(PgTsQueryNode::lexeme("c")),
+        2,
+    )),
+));
+
+test_prepared_type!(tsquery_vec<Vec<PgTsQuery>>(Postgres,
+    "SELECT ({0}::text[] is not distinct from $1::text[])::int4, {0} as _2, $2 as _3",
+    "array['fat'::tsquery]" == vec![PgTsQuery::from(PgTsQueryNode::lexeme("fat"))],
+));
+
+test_type!(hstore<sqlx_oldapi::postgres::types::PgHStore>(Postgres,
+    "'a=>1, b=>NULL'::hstore" == sqlx_oldapi::postgres::types::PgHStore::from_iter([("a", Some("1")), ("b", None)]),
+    "'\"with space\"=>\"quote \\\"\"'::hstore" == sqlx_oldapi::postgres::types::PgHStore::from_iter([("with space", Some("quote \""))]),
+    "''::hstore" == sqlx_oldapi::postgres::types::PgHStore::default(),
+));
+
+test_type!(hstore_vec<Vec<sqlx_oldapi::postgres::types::PgHStore>>(Postgres,
+    "array['a=>1'::hstore, 'b=>NULL'::hstore]" ==
+        vec![
+            sqlx_oldapi::postgres::types::PgHStore::from_iter([("a", Some("1"))]),
+            sqlx_oldapi::postgres::types::PgHStore::from_iter([("b", None::<&str>)]),
+        ]
+));
+
 // FIXME: needed to disable `ltree` tests in version that don't have a binary format for it
 // but `PgLTree` should just fall back to text format
 #[cfg(postgres_14)]
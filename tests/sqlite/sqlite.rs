@@ -1,10 +1,11 @@
 use futures::TryStreamExt;
 use rand_xoshiro::rand_core::{Rng, SeedableRng};
 use rand_xoshiro::Xoshiro256PlusPlus;
-use sqlx_oldapi::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use sqlx_oldapi::error::{ErrorKind, RetryableError};
+use sqlx_oldapi::sqlite::{SqliteConnectOptions, SqlitePoolOptions, SqliteTransactionBehavior};
 use sqlx_oldapi::{
-    query, sqlite::Sqlite, sqlite::SqliteRow, Column, ConnectOptions, Connection, Executor, Row,
-    SqliteConnection, SqlitePool, Statement, TypeInfo,
+    query, sqlite::Sqlite, sqlite::SqliteRow, Column, ConnectOptions, Connection, Executor,
+    IsolationLevel, Row, SqliteConnection, SqlitePool, Statement, TransactionOptions, TypeInfo,
 };
 use sqlx_test::new;
 
@@ -581,6 +582,142 @@ async fn it_can_prepare_then_execute() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_can_begin_with_transaction_options() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    for behavior in [
+        SqliteTransactionBehavior::Deferred,
+        SqliteTransactionBehavior::Immediate,
+        SqliteTransactionBehavior::Exclusive,
+    ] {
+        let options = TransactionOptions::new()
+            .isolation(IsolationLevel::Serializable)
+            .sqlite_behavior(behavior);
+        let mut tx = conn.begin_with(options).await?;
+
+        sqlx_oldapi::query("INSERT INTO tweet ( id, text ) VALUES ( 3, 'Hello, World' )")
+            .execute(&mut tx)
+            .await?;
+
+        // options cannot be applied to a savepoint
+        assert!(tx.begin_with(options).await.is_err());
+
+        tx.rollback().await?;
+    }
+
+    let count: i64 = sqlx_oldapi::query_scalar("SELECT COUNT(*) FROM tweet WHERE id = 3")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(count, 0);
+
+    // read-only transactions are not supported
+    assert!(conn
+        .begin_with(TransactionOptions::new().read_only(true))
+        .await
+        .is_err());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_locks_the_database_when_an_immediate_transaction_begins() -> anyhow::Result<()> {
+    use std::{str::FromStr, time::Duration};
+
+    let url = dotenvy::var("DATABASE_URL")?;
+    let mut conn1 = SqliteConnection::connect(&url).await?;
+    let mut conn2 = SqliteConnectOptions::from_str(&url)?
+        .busy_timeout(Duration::ZERO)
+        .connect()
+        .await?;
+
+    let immediate = TransactionOptions::new().sqlite_behavior(SqliteTransactionBehavior::Immediate);
+    let tx1 = conn1.begin_with(immediate).await?;
+
+    // a deferred transaction only locks the database once it is used
+    let tx2 = conn2.begin().await?;
+    tx2.rollback().await?;
+
+    // an immediate transaction must wait for the first one to end
+    let err = conn2.begin_with(immediate).await.unwrap_err();
+    assert_eq!(
+        err.as_database_error().map(|e| e.kind()),
+        Some(ErrorKind::LockTimeout)
+    );
+    assert!(!err.is_retryable_conflict());
+
+    tx1.rollback().await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_work_with_named_savepoints() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE savepoints (id INTEGER PRIMARY KEY)")
+        .await?;
+
+    let mut tx = conn.begin().await?;
+
+    tx.execute("INSERT INTO savepoints VALUES (1)").await?;
+
+    let mut savepoint = tx.savepoint("batch").await?;
+    savepoint
+        .execute("INSERT INTO savepoints VALUES (2)")
+        .await?;
+    savepoint.rollback_to().await?;
+
+    let mut savepoint = tx.savepoint("batch").await?;
+    savepoint
+        .execute("INSERT INTO savepoints VALUES (3)")
+        .await?;
+    savepoint.release().await?;
+
+    tx.commit().await?;
+
+    let ids: Vec<i64> = sqlx_oldapi::query_scalar("SELECT id FROM savepoints ORDER BY id")
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(ids, [1, 3]);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_classifies_constraint_violations() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.execute(
+        "CREATE TEMPORARY TABLE error_kind (id INTEGER PRIMARY KEY, name TEXT NOT NULL UNIQUE)",
+    )
+    .await?;
+    conn.execute("INSERT INTO error_kind VALUES (1, 'a')")
+        .await?;
+
+    let err = conn
+        .execute("INSERT INTO error_kind VALUES (2, 'a')")
+        .await
+        .unwrap_err();
+    let err = err.as_database_error().unwrap();
+
+    assert_eq!(err.kind(), ErrorKind::UniqueViolation);
+    assert_eq!(err.table(), Some("error_kind"));
+    assert_eq!(err.column(), Some("name"));
+
+    let err = conn
+        .execute("INSERT INTO error_kind VALUES (3, NULL)")
+        .await
+        .unwrap_err();
+    let err = err.as_database_error().unwrap();
+
+    assert_eq!(err.kind(), ErrorKind::NotNullViolation);
+    assert_eq!(err.column(), Some("name"));
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_resets_prepared_statement_after_fetch_one() -> anyhow::Result<()> {
     let mut conn = new::<Sqlite>().await?;
@@ -0,0 +1,15 @@
+#[derive(sqlx_oldapi::PgCopyRow)]
+struct Fruit {
+    id: i32,
+    #[sqlx(try_from = "i64")]
+    count: i32,
+}
+
+#[derive(sqlx_oldapi::PgCopyRow)]
+struct Vegetable {
+    id: i32,
+    #[sqlx(default)]
+    name: String,
+}
+
+fn main() {}